@@ -0,0 +1,75 @@
+use crate::cutting::contour::{dist, PlacedContours, Pt};
+use serde::Serialize;
+
+/// A (partial) edge shared by the outer contours of two neighbouring items
+#[derive(Debug, Clone, Serialize)]
+pub struct SharedEdge {
+    /// Indices (into the list of placed contours) of the two items sharing the edge
+    pub pieces: (usize, usize),
+    pub start: Pt,
+    pub end: Pt,
+}
+
+impl SharedEdge {
+    pub fn length(&self) -> f32 {
+        dist(self.start, self.end)
+    }
+}
+
+fn bboxes_touch(a: &(Pt, Pt), b: &(Pt, Pt), tolerance: f32) -> bool {
+    a.0.0 <= b.1.0 + tolerance && b.0.0 <= a.1.0 + tolerance && a.0.1 <= b.1.1 + tolerance && b.0.1 <= a.1.1 + tolerance
+}
+
+/// Projects `p` onto the line through `a` with unit direction `dir`, returning the parameter and the distance to the line
+fn project(p: Pt, a: Pt, dir: Pt) -> (f32, f32) {
+    let v = (p.0 - a.0, p.1 - a.1);
+    let t = v.0 * dir.0 + v.1 * dir.1;
+    let d = (v.0 * dir.1 - v.1 * dir.0).abs();
+    (t, d)
+}
+
+/// Returns the overlapping part of segments `(a0, a1)` and `(b0, b1)` if they are collinear within `tolerance`
+pub fn collinear_overlap(a0: Pt, a1: Pt, b0: Pt, b1: Pt, tolerance: f32) -> Option<(Pt, Pt)> {
+    let len_a = dist(a0, a1);
+    if len_a <= tolerance || dist(b0, b1) <= tolerance {
+        return None;
+    }
+    let dir = ((a1.0 - a0.0) / len_a, (a1.1 - a0.1) / len_a);
+    let (tb0, db0) = project(b0, a0, dir);
+    let (tb1, db1) = project(b1, a0, dir);
+    if db0 > tolerance || db1 > tolerance {
+        return None;
+    }
+    let t_start = f32::max(0.0, f32::min(tb0, tb1));
+    let t_end = f32::min(len_a, f32::max(tb0, tb1));
+    match t_end - t_start > tolerance {
+        true => Some((
+            (a0.0 + dir.0 * t_start, a0.1 + dir.1 * t_start),
+            (a0.0 + dir.0 * t_end, a0.1 + dir.1 * t_end),
+        )),
+        false => None,
+    }
+}
+
+/// Detects all collinear edges shared between the outer contours of neighbouring items
+pub fn detect_shared_edges(pieces: &[PlacedContours], tolerance: f32) -> Vec<SharedEdge> {
+    let mut shared = vec![];
+    for i in 0..pieces.len() {
+        for j in (i + 1)..pieces.len() {
+            if !bboxes_touch(&pieces[i].bbox, &pieces[j].bbox, tolerance) {
+                continue;
+            }
+            let (pa, pb) = (&pieces[i].outer.points, &pieces[j].outer.points);
+            for ea in 0..pa.len() {
+                let (a0, a1) = (pa[ea], pa[(ea + 1) % pa.len()]);
+                for eb in 0..pb.len() {
+                    let (b0, b1) = (pb[eb], pb[(eb + 1) % pb.len()]);
+                    if let Some((start, end)) = collinear_overlap(a0, a1, b0, b1, tolerance) {
+                        shared.push(SharedEdge { pieces: (i, j), start, end });
+                    }
+                }
+            }
+        }
+    }
+    shared
+}
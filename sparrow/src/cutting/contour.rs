@@ -0,0 +1,193 @@
+use crate::cutting::CutDirection;
+use anyhow::{anyhow, Result};
+use jagua_rs::io::ext_repr::{ExtPolygon, ExtShape, ExtSimplePolygon};
+use jagua_rs::probs::spp::io::ext_repr::{ExtItem, ExtSPInstance, ExtSPSolution};
+use serde::Serialize;
+
+pub type Pt = (f32, f32);
+
+/// Outer ring of a polygon together with its inner rings
+type Rings = (Vec<Pt>, Vec<Vec<Pt>>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContourKind {
+    /// A hole or other internal feature of an item
+    Inner,
+    /// The outer boundary of an item
+    Outer,
+}
+
+/// A closed contour, stored without repeating the first point at the end
+#[derive(Debug, Clone)]
+pub struct Contour {
+    pub kind: ContourKind,
+    pub points: Vec<Pt>,
+}
+
+/// An item as placed in the final solution, with all contours in layout coordinates
+#[derive(Debug, Clone)]
+pub struct PlacedContours {
+    /// Index of the placement in the solution's list of placed items
+    pub placement_idx: usize,
+    pub item_id: usize,
+    pub outer: Contour,
+    pub inner: Vec<Contour>,
+    pub bbox: (Pt, Pt),
+}
+
+impl Contour {
+    pub fn new(kind: ContourKind, points: Vec<Pt>) -> Self {
+        let mut points = points;
+        points.dedup();
+        while points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+        Contour { kind, points }
+    }
+
+    pub fn signed_area(&self) -> f32 {
+        let n = self.points.len();
+        (0..n)
+            .map(|i| {
+                let (a, b) = (self.points[i], self.points[(i + 1) % n]);
+                a.0 * b.1 - b.0 * a.1
+            })
+            .sum::<f32>()
+            / 2.0
+    }
+
+    pub fn direction(&self) -> CutDirection {
+        match self.signed_area() >= 0.0 {
+            true => CutDirection::CounterClockwise,
+            false => CutDirection::Clockwise,
+        }
+    }
+
+    pub fn oriented(&self, direction: CutDirection) -> Contour {
+        let mut points = self.points.clone();
+        if self.direction() != direction {
+            points.reverse();
+        }
+        Contour { kind: self.kind, points }
+    }
+
+    /// Returns the contour as a closed polyline, starting and ending at the vertex with index `start`
+    pub fn closed_from(&self, start: usize) -> Vec<Pt> {
+        let n = self.points.len();
+        (0..=n).map(|i| self.points[(start + i) % n]).collect()
+    }
+
+    /// Absolute turning angle (in radians) of the contour at vertex `i`
+    pub fn turning_angle(&self, i: usize) -> f32 {
+        let n = self.points.len();
+        let (prev, curr, next) = (self.points[(i + n - 1) % n], self.points[i], self.points[(i + 1) % n]);
+        let a_in = f32::atan2(curr.1 - prev.1, curr.0 - prev.0);
+        let a_out = f32::atan2(next.1 - curr.1, next.0 - curr.0);
+        let mut turn = a_out - a_in;
+        while turn > std::f32::consts::PI {
+            turn -= 2.0 * std::f32::consts::PI;
+        }
+        while turn < -std::f32::consts::PI {
+            turn += 2.0 * std::f32::consts::PI;
+        }
+        turn.abs()
+    }
+
+    /// Selects the vertex to start cutting from: the closest one to `from`,
+    /// restricted to sufficiently sharp corners if `corner_threshold` is set and such corners exist.
+    pub fn select_start(&self, from: Pt, corner_threshold: Option<f32>) -> usize {
+        let closest = |candidates: &mut dyn Iterator<Item = usize>| {
+            candidates.min_by(|&a, &b| dist(from, self.points[a]).total_cmp(&dist(from, self.points[b])))
+        };
+        if let Some(threshold) = corner_threshold {
+            let mut corners = (0..self.points.len()).filter(|&i| self.turning_angle(i) >= threshold);
+            if let Some(i) = closest(&mut corners) {
+                return i;
+            }
+        }
+        closest(&mut (0..self.points.len())).unwrap_or(0)
+    }
+
+    pub fn contains(&self, p: Pt) -> bool {
+        let n = self.points.len();
+        let mut inside = false;
+        for i in 0..n {
+            let (a, b) = (self.points[i], self.points[(i + 1) % n]);
+            if (a.1 > p.1) != (b.1 > p.1) && p.0 < (b.0 - a.0) * (p.1 - a.1) / (b.1 - a.1) + a.0 {
+                inside = !inside;
+            }
+        }
+        inside
+    }
+}
+
+pub fn dist(a: Pt, b: Pt) -> f32 {
+    ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+}
+
+pub fn polyline_length(points: &[Pt]) -> f32 {
+    points.windows(2).map(|w| dist(w[0], w[1])).sum()
+}
+
+fn ring(polygon: &ExtSimplePolygon) -> Vec<Pt> {
+    polygon.0.clone()
+}
+
+fn rings(polygon: &ExtPolygon) -> Rings {
+    (ring(&polygon.outer), polygon.inner.iter().map(ring).collect())
+}
+
+/// Converts the external representation of an item shape into polygons (outer ring with inner rings)
+fn shape_rings(shape: &ExtShape) -> Vec<Rings> {
+    match shape {
+        ExtShape::Rectangle { x_min, y_min, width, height } => {
+            let (x, y, w, h) = (*x_min, *y_min, *width, *height);
+            vec![(vec![(x, y), (x + w, y), (x + w, y + h), (x, y + h)], vec![])]
+        }
+        ExtShape::SimplePolygon(polygon) => vec![(ring(polygon), vec![])],
+        ExtShape::Polygon(polygon) => vec![rings(polygon)],
+        ExtShape::MultiPolygon(polygons) => polygons.iter().map(rings).collect(),
+    }
+}
+
+/// Extracts the contours of all placed items from the external representation of an instance and its solution.
+/// Rotations of placed items are expected in degrees, applied before the translation.
+pub fn placed_contours(instance: &ExtSPInstance, solution: &ExtSPSolution) -> Result<Vec<PlacedContours>> {
+    let mut result = vec![];
+    for (placement_idx, pi) in solution.layout.placed_items.iter().enumerate() {
+        let item_id = pi.item_id as usize;
+        let item: &ExtItem = instance
+            .items
+            .iter()
+            .find(|i| i.base.id == pi.item_id)
+            .or_else(|| instance.items.get(item_id))
+            .ok_or_else(|| anyhow!("unknown item id {item_id}"))?;
+        let rotation = pi.transformation.rotation;
+        let (tx, ty) = pi.transformation.translation;
+        let (sin, cos) = rotation.to_radians().sin_cos();
+        let apply = |ring: Vec<Pt>| -> Vec<Pt> {
+            ring.into_iter()
+                .map(|(x, y)| (x * cos - y * sin + tx, x * sin + y * cos + ty))
+                .collect()
+        };
+
+        for (outer, inner) in shape_rings(&item.base.shape) {
+            let outer = Contour::new(ContourKind::Outer, apply(outer));
+            if outer.points.len() < 3 {
+                continue;
+            }
+            let inner = inner
+                .into_iter()
+                .map(|ring| Contour::new(ContourKind::Inner, apply(ring)))
+                .filter(|c| c.points.len() >= 3)
+                .collect();
+            let bbox = outer.points.iter().fold(
+                ((f32::MAX, f32::MAX), (f32::MIN, f32::MIN)),
+                |(min, max), p| ((min.0.min(p.0), min.1.min(p.1)), (max.0.max(p.0), max.1.max(p.1))),
+            );
+            result.push(PlacedContours { placement_idx, item_id, outer, inner, bbox });
+        }
+    }
+    Ok(result)
+}
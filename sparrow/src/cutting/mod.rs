@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+pub mod common_line;
+pub mod contour;
+pub mod sequence;
+
+/// Direction in which a contour is traversed by the knife, in layout coordinates (y-axis up).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CutDirection {
+    Clockwise,
+    CounterClockwise,
+}
+
+impl CutDirection {
+    pub fn reversed(self) -> Self {
+        match self {
+            CutDirection::Clockwise => CutDirection::CounterClockwise,
+            CutDirection::CounterClockwise => CutDirection::Clockwise,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct CutSequenceConfig {
+    /// Position of the knife before the first cut (parking position of the cutter)
+    pub home: (f32, f32),
+    /// Direction in which outer contours are cut, inner contours are cut in the opposite direction
+    pub outer_direction: CutDirection,
+    /// Only start contours at a corner sharper than this turning angle (in radians), if the contour has one.
+    /// Disabled if `None`.
+    pub corner_start_threshold: Option<f32>,
+    /// Maximum distance between two edges of neighbouring items to consider them a common line which is cut only once.
+    /// Disabled if `None`.
+    pub common_line_tolerance: Option<f32>,
+}
+
+pub const DEFAULT_CUT_SEQUENCE_CONFIG: CutSequenceConfig = CutSequenceConfig {
+    home: (0.0, 0.0),
+    outer_direction: CutDirection::CounterClockwise,
+    corner_start_threshold: Some(f32::to_radians(30.0)),
+    common_line_tolerance: Some(0.01),
+};
@@ -0,0 +1,215 @@
+use crate::cutting::common_line::{collinear_overlap, detect_shared_edges, SharedEdge};
+use crate::cutting::contour::{dist, placed_contours, polyline_length, Contour, ContourKind, PlacedContours, Pt};
+use crate::cutting::{CutDirection, CutSequenceConfig};
+use crate::util::io::SPOutput;
+use anyhow::Result;
+use log::debug;
+use serde::Serialize;
+
+/// A single contour of an item, cut with the knife down.
+#[derive(Debug, Clone, Serialize)]
+pub struct CutOperation {
+    pub placement_idx: usize,
+    pub item_id: usize,
+    pub kind: ContourKind,
+    pub direction: CutDirection,
+    /// Knife-down polylines, cut in order.
+    /// A contour is split into multiple strokes when parts of it were already cut as a common line.
+    pub strokes: Vec<Vec<Pt>>,
+    pub cut_length: f32,
+    /// Length of the air moves before and in between the strokes of this operation
+    pub travel_length: f32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CutSequence {
+    pub operations: Vec<CutOperation>,
+    pub shared_edges: Vec<SharedEdge>,
+    pub total_cut_length: f32,
+    pub total_travel_length: f32,
+    /// Cut length saved by cutting common lines only once
+    pub saved_cut_length: f32,
+}
+
+/// Builds a cutting sequence for the final solution of a strip packing instance.
+pub fn cut_sequence_from_output(output: &SPOutput, config: &CutSequenceConfig) -> Result<CutSequence> {
+    let pieces = placed_contours(&output.instance, &output.solution)?;
+    Ok(build_cut_sequence(pieces, config))
+}
+
+/// Orders the contours of all placed items to minimize the travel of the knife.
+/// Items are visited greedily by closest entry point, inner contours of an item are always cut before its outer contour
+/// and items lying inside a hole of another item are cut before that hole.
+pub fn build_cut_sequence(pieces: Vec<PlacedContours>, config: &CutSequenceConfig) -> CutSequence {
+    let shared_edges = match config.common_line_tolerance {
+        Some(tolerance) => detect_shared_edges(&pieces, tolerance),
+        None => vec![],
+    };
+    debug!("[CUT] detected {} common line segments", shared_edges.len());
+
+    // for every piece, the pieces placed inside one of its holes
+    let nested_in: Vec<Vec<usize>> = pieces
+        .iter()
+        .map(|outer| {
+            (0..pieces.len())
+                .filter(|&j| {
+                    let p = pieces[j].outer.points[0];
+                    outer.inner.iter().any(|hole| hole.contains(p))
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut pos = config.home;
+    let mut cut = vec![false; pieces.len()];
+    let mut operations = vec![];
+
+    while let Some(next) = (0..pieces.len())
+        .filter(|&i| !cut[i] && nested_in[i].iter().all(|&j| cut[j]))
+        .min_by(|&a, &b| entry_distance(&pieces[a], pos, config).total_cmp(&entry_distance(&pieces[b], pos, config)))
+        .or_else(|| (0..pieces.len()).find(|&i| !cut[i]))
+    {
+        let piece = &pieces[next];
+        let skip: Vec<&SharedEdge> = shared_edges
+            .iter()
+            .filter(|se| (se.pieces.0 == next && cut[se.pieces.1]) || (se.pieces.1 == next && cut[se.pieces.0]))
+            .collect();
+
+        let mut remaining_inner: Vec<&Contour> = piece.inner.iter().collect();
+        while !remaining_inner.is_empty() {
+            let (idx, _) = remaining_inner
+                .iter()
+                .enumerate()
+                .map(|(idx, c)| (idx, contour_entry_distance(c, pos, config.outer_direction.reversed(), config)))
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .unwrap();
+            let contour = remaining_inner.swap_remove(idx);
+            let op = cut_contour(piece, contour, config.outer_direction.reversed(), &[], &mut pos, config);
+            operations.push(op);
+        }
+        let op = cut_contour(piece, &piece.outer, config.outer_direction, &skip, &mut pos, config);
+        operations.push(op);
+        cut[next] = true;
+    }
+
+    let total_cut_length = operations.iter().map(|op| op.cut_length).sum();
+    let total_travel_length = operations.iter().map(|op| op.travel_length).sum();
+    let saved_cut_length = shared_edges.iter().map(|se| se.length()).sum();
+
+    CutSequence {
+        operations,
+        shared_edges,
+        total_cut_length,
+        total_travel_length,
+        saved_cut_length,
+    }
+}
+
+fn contour_entry_distance(contour: &Contour, pos: Pt, direction: CutDirection, config: &CutSequenceConfig) -> f32 {
+    let oriented = contour.oriented(direction);
+    let start = oriented.select_start(pos, config.corner_start_threshold);
+    dist(pos, oriented.points[start])
+}
+
+fn entry_distance(piece: &PlacedContours, pos: Pt, config: &CutSequenceConfig) -> f32 {
+    match piece.inner.is_empty() {
+        true => contour_entry_distance(&piece.outer, pos, config.outer_direction, config),
+        false => piece
+            .inner
+            .iter()
+            .map(|c| contour_entry_distance(c, pos, config.outer_direction.reversed(), config))
+            .fold(f32::MAX, f32::min),
+    }
+}
+
+fn cut_contour(
+    piece: &PlacedContours,
+    contour: &Contour,
+    direction: CutDirection,
+    skip: &[&SharedEdge],
+    pos: &mut Pt,
+    config: &CutSequenceConfig,
+) -> CutOperation {
+    let oriented = contour.oriented(direction);
+    let start = oriented.select_start(*pos, config.corner_start_threshold);
+    let path = oriented.closed_from(start);
+    let strokes = match (skip.is_empty(), config.common_line_tolerance) {
+        (false, Some(tolerance)) => split_strokes(&path, skip, tolerance),
+        _ => vec![path],
+    };
+
+    let mut travel_length = 0.0;
+    let mut cut_length = 0.0;
+    for stroke in &strokes {
+        travel_length += dist(*pos, stroke[0]);
+        cut_length += polyline_length(stroke);
+        *pos = *stroke.last().unwrap();
+    }
+
+    CutOperation {
+        placement_idx: piece.placement_idx,
+        item_id: piece.item_id,
+        kind: contour.kind,
+        direction,
+        strokes,
+        cut_length,
+        travel_length,
+    }
+}
+
+/// Splits a polyline into the strokes that remain after removing the segments already cut as common lines
+fn split_strokes(path: &[Pt], skip: &[&SharedEdge], tolerance: f32) -> Vec<Vec<Pt>> {
+    let mut strokes: Vec<Vec<Pt>> = vec![];
+    let mut current: Vec<Pt> = vec![];
+    for w in path.windows(2) {
+        let (p, q) = (w[0], w[1]);
+        let len = dist(p, q);
+        if len == 0.0 {
+            continue;
+        }
+        let param = |x: Pt| ((x.0 - p.0) * (q.0 - p.0) + (x.1 - p.1) * (q.1 - p.1)) / (len * len);
+        let mut excluded: Vec<(f32, f32)> = skip
+            .iter()
+            .filter_map(|se| collinear_overlap(p, q, se.start, se.end, tolerance))
+            .map(|(a, b)| {
+                let (ta, tb) = (param(a), param(b));
+                (f32::min(ta, tb), f32::max(ta, tb))
+            })
+            .collect();
+        excluded.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut kept = vec![];
+        let mut t = 0.0;
+        for (t0, t1) in excluded {
+            if t0 > t {
+                kept.push((t, t0));
+            }
+            t = f32::max(t, t1);
+        }
+        if t < 1.0 {
+            kept.push((t, 1.0));
+        }
+
+        let lerp = |t: f32| (p.0 + (q.0 - p.0) * t, p.1 + (q.1 - p.1) * t);
+        for (t0, t1) in kept {
+            if (t1 - t0) * len <= tolerance {
+                continue;
+            }
+            let (a, b) = (lerp(t0), lerp(t1));
+            match current.last() {
+                Some(last) if dist(*last, a) <= tolerance => {}
+                _ => {
+                    if current.len() >= 2 {
+                        strokes.push(std::mem::take(&mut current));
+                    }
+                    current = vec![a];
+                }
+            }
+            current.push(b);
+        }
+    }
+    if current.len() >= 2 {
+        strokes.push(current);
+    }
+    strokes
+}
@@ -18,6 +18,8 @@ use anyhow::{bail, Result};
 use sparrow::consts::{DEFAULT_COMPRESS_TIME_RATIO, DEFAULT_EXPLORE_TIME_RATIO, DEFAULT_FAIL_DECAY_RATIO_CMPR, DEFAULT_MAX_CONSEQ_FAILS_EXPL, LOG_LEVEL_FILTER_DEBUG, LOG_LEVEL_FILTER_RELEASE};
 use sparrow::util::svg_exporter::SvgExporter;
 use sparrow::util::ctrlc_terminator::CtrlCTerminator;
+use sparrow::cutting::DEFAULT_CUT_SEQUENCE_CONFIG;
+use sparrow::cutting::sequence::cut_sequence_from_output;
 
 pub const OUTPUT_DIR: &str = "output";
 
@@ -163,5 +165,18 @@ fn main() -> Result<()>{
     };
     // io::write_json(&json_output, Path::new(json_path.as_str()), Level::Info)?; // Disabled final JSON export
 
+    if args.cut_sequence {
+        let cut_sequence = cut_sequence_from_output(&json_output, &DEFAULT_CUT_SEQUENCE_CONFIG)?;
+        info!(
+            "[MAIN] cut sequence: {} operations, cut length {:.3}, travel length {:.3}, saved by common lines {:.3}",
+            cut_sequence.operations.len(),
+            cut_sequence.total_cut_length,
+            cut_sequence.total_travel_length,
+            cut_sequence.saved_cut_length
+        );
+        let cut_path = format!("{OUTPUT_DIR}/cut_{}.json", json_output.instance.name);
+        io::write_json(&cut_sequence, Path::new(cut_path.as_str()), Level::Info)?;
+    }
+
     Ok(())
 }
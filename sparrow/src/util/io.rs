@@ -34,6 +34,10 @@ pub struct MainCli {
 
     #[arg(short = 's', long, help = "Fixed seed for the random number generator")]
     pub rng_seed: Option<u64>,
+
+    /// Export an ordered cutting sequence of the final solution
+    #[arg(long, help = "Export an ordered knife cutting sequence of the final solution")]
+    pub cut_sequence: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -0,0 +1,148 @@
+use jagua_rs::probs::spp::io::ext_repr::{ExtSPInstance, ExtSPSolution};
+use serde_json::{json, Value};
+use sparrow::cutting::common_line::detect_shared_edges;
+use sparrow::cutting::contour::{placed_contours, Contour, ContourKind, PlacedContours, Pt};
+use sparrow::cutting::sequence::build_cut_sequence;
+use sparrow::cutting::{CutDirection, CutSequenceConfig, DEFAULT_CUT_SEQUENCE_CONFIG};
+
+const EPS: f32 = 1e-3;
+
+fn square(x: f32, y: f32, size: f32) -> Vec<Pt> {
+    vec![(x, y), (x + size, y), (x + size, y + size), (x, y + size)]
+}
+
+fn simple_item(id: u64, ring: Vec<Pt>) -> Value {
+    json!({ "id": id, "demand": 1, "shape": { "type": "simple_polygon", "data": ring } })
+}
+
+/// Places the given items in a layout, `placements` being `(item_id, rotation in degrees, translation)`
+fn layout(items: Vec<Value>, placements: &[(u64, f32, Pt)]) -> Vec<PlacedContours> {
+    let instance: ExtSPInstance =
+        serde_json::from_value(json!({ "name": "cutting", "strip_height": 100.0, "items": items })).unwrap();
+    let placed_items: Vec<Value> = placements
+        .iter()
+        .map(|(item_id, rotation, translation)| {
+            json!({ "item_id": item_id, "transformation": { "rotation": rotation, "translation": translation } })
+        })
+        .collect();
+    let solution: ExtSPSolution = serde_json::from_value(json!({
+        "strip_width": 100.0,
+        "density": 0.5,
+        "run_time_sec": 0,
+        "layout": { "container_id": 0, "placed_items": placed_items, "density": 0.5 },
+    }))
+    .unwrap();
+    placed_contours(&instance, &solution).unwrap()
+}
+
+fn assert_points(actual: &[Pt], expected: &[Pt]) {
+    assert_eq!(actual.len(), expected.len(), "{actual:?} vs {expected:?}");
+    for (a, e) in actual.iter().zip(expected) {
+        assert!((a.0 - e.0).abs() < EPS && (a.1 - e.1).abs() < EPS, "{actual:?} vs {expected:?}");
+    }
+}
+
+#[test]
+fn placed_items_are_parsed_and_transformed() {
+    let items = vec![
+        json!({
+            "id": 7, "demand": 1,
+            "shape": { "type": "rectangle", "data": { "x_min": 0.0, "y_min": 0.0, "width": 10.0, "height": 5.0 } },
+        }),
+        json!({
+            "id": 3, "demand": 2,
+            "shape": { "type": "polygon", "data": { "outer": square(0.0, 0.0, 30.0), "inner": [square(10.0, 10.0, 10.0)] } },
+        }),
+    ];
+    let pieces = layout(items, &[(7, 90.0, (5.0, 0.0)), (3, 0.0, (40.0, 0.0))]);
+    assert_eq!(pieces.len(), 2);
+
+    let rect = &pieces[0];
+    assert_eq!((rect.placement_idx, rect.item_id), (0, 7));
+    assert_points(&rect.outer.points, &[(5.0, 0.0), (5.0, 10.0), (0.0, 10.0), (0.0, 0.0)]);
+    assert_points(&[rect.bbox.0, rect.bbox.1], &[(0.0, 0.0), (5.0, 10.0)]);
+    assert!(rect.inner.is_empty());
+
+    let frame = &pieces[1];
+    assert_eq!((frame.placement_idx, frame.item_id), (1, 3));
+    assert_eq!(frame.outer.kind, ContourKind::Outer);
+    assert_points(&frame.outer.points, &square(40.0, 0.0, 30.0));
+    assert_eq!(frame.inner.len(), 1);
+    assert_eq!(frame.inner[0].kind, ContourKind::Inner);
+    assert_points(&frame.inner[0].points, &square(50.0, 10.0, 10.0));
+}
+
+#[test]
+fn contours_are_cut_inside_out_and_nearest_first() {
+    let frame = json!({
+        "id": 0, "demand": 1,
+        "shape": { "type": "polygon", "data": { "outer": square(0.0, 0.0, 30.0), "inner": [square(10.0, 10.0, 10.0)] } },
+    });
+    let items = vec![frame, simple_item(1, square(0.0, 0.0, 6.0)), simple_item(2, square(0.0, 0.0, 10.0))];
+    // the small square sits in the frame's hole, the other two are loose pieces to the right
+    let pieces = layout(items, &[(0, 0.0, (0.0, 0.0)), (1, 0.0, (12.0, 12.0)), (2, 0.0, (60.0, 0.0)), (2, 0.0, (40.0, 0.0))]);
+    let sequence = build_cut_sequence(pieces, &DEFAULT_CUT_SEQUENCE_CONFIG);
+
+    let order: Vec<(usize, ContourKind, CutDirection)> = sequence
+        .operations
+        .iter()
+        .map(|op| (op.placement_idx, op.kind, op.direction))
+        .collect();
+    assert_eq!(
+        order,
+        [
+            (1, ContourKind::Outer, CutDirection::CounterClockwise),
+            (0, ContourKind::Inner, CutDirection::Clockwise),
+            (0, ContourKind::Outer, CutDirection::CounterClockwise),
+            (3, ContourKind::Outer, CutDirection::CounterClockwise),
+            (2, ContourKind::Outer, CutDirection::CounterClockwise),
+        ]
+    );
+
+    for op in &sequence.operations {
+        assert_eq!(op.strokes.len(), 1);
+        let stroke = &op.strokes[0];
+        assert_eq!(stroke.first(), stroke.last(), "contours are cut closed");
+        assert_eq!(Contour::new(op.kind, stroke.clone()).direction(), op.direction);
+    }
+    // every contour starts at its corner closest to where the previous one ended
+    let starts: Vec<Pt> = sequence.operations.iter().map(|op| op.strokes[0][0]).collect();
+    assert_points(&starts, &[(12.0, 12.0), (10.0, 10.0), (0.0, 0.0), (40.0, 0.0), (60.0, 0.0)]);
+    assert!((sequence.total_cut_length - (24.0 + 40.0 + 120.0 + 40.0 + 40.0)).abs() < EPS);
+    assert!(sequence.saved_cut_length.abs() < EPS);
+}
+
+/// Two squares sharing an edge and a third one standing apart
+fn three_squares() -> Vec<PlacedContours> {
+    let items = vec![simple_item(0, square(0.0, 0.0, 10.0))];
+    layout(items, &[(0, 0.0, (0.0, 0.0)), (0, 0.0, (10.0, 0.0)), (0, 0.0, (30.0, 0.0))])
+}
+
+#[test]
+fn common_lines_are_detected_and_cut_once() {
+    let pieces = three_squares();
+
+    let shared = detect_shared_edges(&pieces, 0.01);
+    assert_eq!(shared.len(), 1, "{shared:?}");
+    assert_eq!(shared[0].pieces, (0, 1));
+    assert!((shared[0].length() - 10.0).abs() < EPS);
+    let mut ends = [shared[0].start, shared[0].end];
+    ends.sort_by(|a, b| a.1.total_cmp(&b.1));
+    assert_points(&ends, &[(10.0, 0.0), (10.0, 10.0)]);
+
+    let sequence = build_cut_sequence(pieces, &DEFAULT_CUT_SEQUENCE_CONFIG);
+    let second = &sequence.operations[1];
+    assert_eq!(second.placement_idx, 1);
+    assert_eq!(second.strokes.len(), 1);
+    assert_points(&second.strokes[0], &[(10.0, 0.0), (20.0, 0.0), (20.0, 10.0), (10.0, 10.0)]);
+    assert!((sequence.saved_cut_length - 10.0).abs() < EPS);
+    assert!((sequence.total_cut_length - 110.0).abs() < EPS);
+    assert!((sequence.total_travel_length - 30.0).abs() < EPS);
+
+    let without = build_cut_sequence(
+        three_squares(),
+        &CutSequenceConfig { common_line_tolerance: None, ..DEFAULT_CUT_SEQUENCE_CONFIG },
+    );
+    assert!(without.shared_edges.is_empty());
+    assert!((without.total_cut_length - 120.0).abs() < EPS);
+}
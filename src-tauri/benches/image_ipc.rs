@@ -0,0 +1,115 @@
+//! Backend cost of handing an app-data image to the webview: the old `read_binary_file_as_base64` path encodes the
+//! file and wraps it in a JSON string that the webview parses and decodes again, while `read_binary_file` and the
+//! `coinop-file` protocol pass the bytes through as they are. Both new paths run the real handlers against a
+//! temporary directory standing in for app data. Run with `cargo bench --bench image_ipc`; see
+//! `docs/image-ipc-benchmark.md` for how to read the results.
+
+use base64::{ engine::general_purpose, Engine as _ };
+use coinop_lib::binary_ipc;
+use criterion::{ black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput };
+use rand::RngCore;
+use std::alloc::{ GlobalAlloc, Layout, System };
+use std::path::Path;
+use std::sync::atomic::{ AtomicUsize, Ordering };
+
+const SIZES: [(&str, usize); 3] = [("256KiB", 256 * 1024), ("4MiB", 4 * 1024 * 1024), ("16MiB", 16 * 1024 * 1024)];
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// One way of getting a file's bytes from app data to the webview
+type Transfer = fn(&Path, &str) -> Vec<u8>;
+
+/// Tracks live heap bytes and their high-water mark so each path's peak allocation can be reported.
+struct CountingAllocator;
+
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+fn grew(size: usize) {
+    let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(live, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            grew(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+            grew(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Heap bytes allocated on top of what was live before `run`, at its highest point.
+fn peak_allocation<T>(run: impl FnOnce() -> T) -> usize {
+    let before = LIVE.load(Ordering::Relaxed);
+    PEAK.store(before, Ordering::Relaxed);
+    drop(black_box(run()));
+    PEAK.load(Ordering::Relaxed) - before
+}
+
+/// The old command followed by what the webview did with its result.
+fn base64_json(root: &Path, path: &str) -> Vec<u8> {
+    let encoded = general_purpose::STANDARD.encode(binary_ipc::read_file_under(root, path).unwrap());
+    let message = serde_json::to_string(&encoded).unwrap();
+    drop(encoded);
+    let received: String = serde_json::from_str(&message).unwrap();
+    drop(message);
+    general_purpose::STANDARD.decode(received).unwrap()
+}
+
+fn read_binary_file(root: &Path, path: &str) -> Vec<u8> {
+    binary_ipc::read_file_under(root, path).unwrap()
+}
+
+fn protocol(root: &Path, path: &str) -> Vec<u8> {
+    binary_ipc::file_route(root, path).unwrap().0
+}
+
+fn image_transfer(c: &mut Criterion) {
+    let dir = tempfile::tempdir().unwrap();
+    let paths: [(&str, Transfer); 3] = [
+        ("base64_json", base64_json),
+        ("read_binary_file", read_binary_file),
+        ("protocol", protocol),
+    ];
+    let mut group = c.benchmark_group("image_transfer");
+    group.sample_size(20);
+    for (label, size) in SIZES {
+        let mut data = vec![0u8; size];
+        rand::rng().fill_bytes(&mut data);
+        data[..PNG_SIGNATURE.len()].copy_from_slice(PNG_SIGNATURE);
+        let file = format!("{}.png", label);
+        std::fs::write(dir.path().join(&file), &data).unwrap();
+        group.throughput(Throughput::Bytes(size as u64));
+
+        for (name, transfer) in paths {
+            assert_eq!(transfer(dir.path(), &file), data);
+            let peak = peak_allocation(|| transfer(dir.path(), &file));
+            println!("image_transfer/{}/{}: peak allocation {:.2}x file size", name, label, peak as f64 / size as f64);
+            group.bench_with_input(BenchmarkId::new(name, label), &file, |b, file| {
+                b.iter(|| black_box(transfer(dir.path(), file)))
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, image_transfer);
+criterion_main!(benches);
@@ -0,0 +1,619 @@
+use crate::fs_sandbox;
+use crate::generation::image_mime_type;
+use crate::image_provenance;
+use base64::{ engine::general_purpose, Engine as _ };
+use chrono::{ DateTime, Utc };
+use once_cell::sync::Lazy;
+use serde::{ Deserialize, Serialize };
+use serde_json::Value;
+use sha2::{ Digest, Sha256 };
+use std::collections::{ BTreeMap, BTreeSet };
+use std::path::{ Path, PathBuf };
+use std::sync::Mutex;
+use tauri::Manager;
+
+const ASSETS_DIR: &str = "assets";
+const INDEX_FILE: &str = "index.json";
+const DEFAULT_THUMBNAIL_SIZE: u32 = 256;
+
+/// Every asset in the library by content hash
+static ASSET_LIBRARY: Lazy<Mutex<BTreeMap<String, Asset>>> = Lazy::new(|| Mutex::new(BTreeMap::new()));
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Asset {
+    /// SHA-256 of the file contents
+    pub id: String,
+    /// Path of the stored file relative to the assets directory
+    pub file: String,
+    #[serde(rename = "mimeType")]
+    pub mime_type: String,
+    pub size: u64,
+    #[serde(default)]
+    pub width: Option<u32>,
+    #[serde(default)]
+    pub height: Option<u32>,
+    #[serde(default)]
+    pub provider: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub prompt: Option<String>,
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[serde(default)]
+    pub workflow: Option<Value>,
+    #[serde(default)]
+    pub parent: Option<String>,
+    #[serde(default)]
+    pub tags: BTreeSet<String>,
+    #[serde(default)]
+    pub favourite: bool,
+    #[serde(default)]
+    pub metadata: Value,
+    #[serde(rename = "createdAt")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AssetInfo {
+    #[serde(default)]
+    pub provider: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub prompt: Option<String>,
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[serde(default)]
+    pub workflow: Option<Value>,
+    #[serde(default)]
+    pub parent: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub metadata: Option<Value>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AssetQuery {
+    /// Case-insensitive match against prompt, model, provider and tags
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub provider: Option<String>,
+    /// Assets must carry every one of these tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub favourite: Option<bool>,
+    #[serde(default)]
+    pub parent: Option<String>,
+    #[serde(default)]
+    pub since: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub until: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub offset: Option<usize>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AssetPage {
+    pub total: usize,
+    pub assets: Vec<Asset>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AssetExport {
+    pub directory: String,
+    pub files: usize,
+}
+
+impl AssetQuery {
+    fn matches(&self, asset: &Asset) -> bool {
+        if let Some(text) = self.text.as_deref().map(str::to_lowercase).filter(|t| !t.is_empty()) {
+            let found = [&asset.prompt, &asset.model, &asset.provider]
+                .iter()
+                .filter_map(|field| field.as_deref())
+                .chain(asset.tags.iter().map(String::as_str))
+                .any(|field| field.to_lowercase().contains(&text));
+            if !found {
+                return false;
+            }
+        }
+        self.provider.as_ref().is_none_or(|p| asset.provider.as_ref() == Some(p)) &&
+            self.tags.iter().all(|t| asset.tags.contains(t)) &&
+            self.favourite.is_none_or(|f| asset.favourite == f) &&
+            self.parent.as_ref().is_none_or(|p| asset.parent.as_ref() == Some(p)) &&
+            self.since.is_none_or(|since| asset.created_at >= since) &&
+            self.until.is_none_or(|until| asset.created_at <= until)
+    }
+}
+
+fn assets_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    Ok(app_data_dir.join(ASSETS_DIR))
+}
+
+fn extension(mime_type: &str) -> &'static str {
+    match mime_type {
+        "image/png" => "png",
+        "image/jpeg" => "jpg",
+        "image/tiff" => "tiff",
+        "image/webp" => "webp",
+        _ => "bin",
+    }
+}
+
+fn save_index(dir: &Path, library: &BTreeMap<String, Asset>) -> Result<(), String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create directory: {}", e))?;
+    let assets: Vec<&Asset> = library.values().collect();
+    let json = serde_json::to_string_pretty(&assets).map_err(|e| format!("Failed to serialize asset index: {}", e))?;
+    let temp = dir.join(format!("{}.tmp", INDEX_FILE));
+    std::fs::write(&temp, json).map_err(|e| format!("Failed to write asset index: {}", e))?;
+    std::fs::rename(&temp, dir.join(INDEX_FILE)).map_err(|e| format!("Failed to write asset index: {}", e))
+}
+
+/// Loads the asset index at startup; a missing or unreadable index leaves the library empty.
+pub fn load_assets(app: &tauri::AppHandle) {
+    let Ok(dir) = assets_dir(app) else {
+        return;
+    };
+    let Ok(json) = std::fs::read_to_string(dir.join(INDEX_FILE)) else {
+        return;
+    };
+    match serde_json::from_str::<Vec<Asset>>(&json) {
+        Ok(assets) => {
+            let mut library = ASSET_LIBRARY.lock().unwrap();
+            for asset in assets {
+                library.insert(asset.id.clone(), asset);
+            }
+        }
+        Err(e) => log::warn!("Ignoring unreadable asset index: {}", e),
+    }
+}
+
+fn merge_info(asset: &mut Asset, info: AssetInfo) {
+    asset.tags.extend(info.tags);
+    asset.provider = asset.provider.take().or(info.provider);
+    asset.model = asset.model.take().or(info.model);
+    asset.prompt = asset.prompt.take().or(info.prompt);
+    asset.seed = asset.seed.or(info.seed);
+    asset.workflow = asset.workflow.take().or(info.workflow);
+    asset.parent = asset.parent.take().or(info.parent);
+}
+
+/// Writes content that is not in the library yet and describes it. Runs without the library lock: the file is
+/// named by its hash, so concurrent writers of the same content produce the same file.
+fn store_new(dir: &Path, id: &str, data: &[u8], info: &AssetInfo) -> Result<Asset, String> {
+    let mime_type = image_mime_type(data).to_string();
+    let file = format!("objects/{}/{}.{}", &id[..2], id, extension(&mime_type));
+    let path = dir.join(&file);
+    if !path.exists() {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+        }
+        let temp = path.with_extension(format!("{}.tmp", uuid::Uuid::new_v4()));
+        std::fs::write(&temp, data).map_err(|e| format!("Failed to write asset: {}", e))?;
+        std::fs::rename(&temp, &path).map_err(|e| format!("Failed to write asset: {}", e))?;
+    }
+
+    let provenance = image_provenance::read_provenance(data).ok().flatten().unwrap_or_default();
+    let dimensions = image::io::Reader
+        ::new(std::io::Cursor::new(data))
+        .with_guessed_format()
+        .ok()
+        .and_then(|reader| reader.into_dimensions().ok());
+    let info = info.clone();
+    Ok(Asset {
+        id: id.to_string(),
+        file,
+        mime_type,
+        size: data.len() as u64,
+        width: dimensions.map(|d| d.0),
+        height: dimensions.map(|d| d.1),
+        provider: info.provider.or(Some(provenance.provider).filter(|p| !p.is_empty())),
+        model: info.model.or(provenance.model),
+        prompt: info.prompt.or(provenance.prompt),
+        seed: info.seed.or(provenance.seed),
+        workflow: info.workflow.or(provenance.workflow).or(provenance.comfy_prompt),
+        parent: info.parent,
+        tags: info.tags.into_iter().collect(),
+        favourite: false,
+        metadata: info.metadata.unwrap_or(Value::Null),
+        created_at: Utc::now(),
+    })
+}
+
+/// Saves the index after `library` was changed in place, putting `previous` back under `id` if that fails.
+fn commit(
+    dir: &Path,
+    library: &mut BTreeMap<String, Asset>,
+    id: &str,
+    previous: Option<Asset>
+) -> Result<(), String> {
+    save_index(dir, library).inspect_err(|_| {
+        match previous {
+            Some(asset) => library.insert(id.to_string(), asset),
+            None => library.remove(id),
+        };
+    })
+}
+
+/// Stores bytes under their hash and indexes them. Adding content that is already in the library
+/// merges the new tags into the existing entry and fills any fields it was missing.
+pub fn add_asset(app: &tauri::AppHandle, data: &[u8], info: AssetInfo) -> Result<Asset, String> {
+    add_to_library(&assets_dir(app)?, data, info)
+}
+
+fn add_to_library(dir: &Path, data: &[u8], info: AssetInfo) -> Result<Asset, String> {
+    let id = format!("{:x}", Sha256::digest(data));
+    let known = ASSET_LIBRARY.lock().unwrap().contains_key(&id);
+    let stored = if known { None } else { Some(store_new(dir, &id, data, &info)?) };
+
+    let mut library = ASSET_LIBRARY.lock().unwrap();
+    if let Some(parent) = &info.parent {
+        if !library.contains_key(parent) {
+            return Err(format!("Parent asset {} does not exist", parent));
+        }
+    }
+    let previous = library.get(&id).cloned();
+    let asset = match (library.get_mut(&id), stored) {
+        (Some(existing), _) => {
+            merge_info(existing, info);
+            existing.clone()
+        }
+        (None, Some(stored)) => {
+            library.insert(id.clone(), stored.clone());
+            stored
+        }
+        // Deleted since it was looked up; store it afresh
+        (None, None) => {
+            drop(library);
+            return add_to_library(dir, data, info);
+        }
+    };
+    commit(dir, &mut library, &id, previous)?;
+    Ok(asset)
+}
+
+fn update_asset(app: &tauri::AppHandle, id: &str, change: impl FnOnce(&mut Asset)) -> Result<Asset, String> {
+    update_in_library(&assets_dir(app)?, id, change)
+}
+
+fn update_in_library(dir: &Path, id: &str, change: impl FnOnce(&mut Asset)) -> Result<Asset, String> {
+    let mut library = ASSET_LIBRARY.lock().unwrap();
+    let asset = library.get_mut(id).ok_or_else(|| format!("No asset {}", id))?;
+    let previous = asset.clone();
+    change(asset);
+    let asset = asset.clone();
+    commit(dir, &mut library, id, Some(previous))?;
+    Ok(asset)
+}
+
+fn asset(id: &str) -> Result<Asset, String> {
+    ASSET_LIBRARY.lock()
+        .unwrap()
+        .get(id)
+        .cloned()
+        .ok_or_else(|| format!("No asset {}", id))
+}
+
+fn decode_image_data(data: &str) -> Result<Vec<u8>, String> {
+    let base64_data = match data.split_once(";base64,") {
+        Some((_, encoded)) => encoded,
+        None => data,
+    };
+    general_purpose::STANDARD.decode(base64_data).map_err(|e| format!("Failed to decode base64: {}", e))
+}
+
+#[tauri::command]
+pub async fn assets_add(app: tauri::AppHandle, data: String, info: Option<AssetInfo>) -> Result<Asset, String> {
+    let bytes = decode_image_data(&data)?;
+    add_asset(&app, &bytes, info.unwrap_or_default())
+}
+
+/// Imports a file relative to the app data directory, such as a Replicate download, or one picked through a dialog.
+#[tauri::command]
+pub async fn assets_add_file(app: tauri::AppHandle, path: String, info: Option<AssetInfo>) -> Result<Asset, String> {
+    let path = fs_sandbox::frontend_path(&app, &path)?;
+    let bytes = std::fs::read(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    add_asset(&app, &bytes, info.unwrap_or_default())
+}
+
+#[tauri::command]
+pub async fn assets_get(id: String) -> Result<Asset, String> {
+    asset(&id)
+}
+
+/// Newest first.
+#[tauri::command]
+pub async fn assets_search(query: Option<AssetQuery>) -> Result<AssetPage, String> {
+    let query = query.unwrap_or_default();
+    let mut matches: Vec<Asset> = ASSET_LIBRARY.lock()
+        .unwrap()
+        .values()
+        .filter(|asset| query.matches(asset))
+        .cloned()
+        .collect();
+    matches.sort_by(|a, b| b.created_at.cmp(&a.created_at).then_with(|| a.id.cmp(&b.id)));
+    let total = matches.len();
+    let assets = matches
+        .into_iter()
+        .skip(query.offset.unwrap_or(0))
+        .take(query.limit.unwrap_or(usize::MAX))
+        .collect();
+    Ok(AssetPage { total, assets })
+}
+
+#[tauri::command]
+pub async fn assets_read(app: tauri::AppHandle, id: String) -> Result<String, String> {
+    let (data, _) = asset_bytes(&app, &id)?;
+    Ok(general_purpose::STANDARD.encode(data))
+}
+
+#[tauri::command]
+pub async fn assets_tag(
+    app: tauri::AppHandle,
+    id: String,
+    add: Option<Vec<String>>,
+    remove: Option<Vec<String>>
+) -> Result<Asset, String> {
+    update_asset(&app, &id, |asset| {
+        for tag in remove.unwrap_or_default() {
+            asset.tags.remove(&tag);
+        }
+        asset.tags.extend(
+            add
+                .unwrap_or_default()
+                .into_iter()
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty())
+        );
+    })
+}
+
+#[tauri::command]
+pub async fn assets_set_favourite(app: tauri::AppHandle, id: String, favourite: bool) -> Result<Asset, String> {
+    update_asset(&app, &id, |asset| {
+        asset.favourite = favourite;
+    })
+}
+
+/// Removes assets with their files and cached thumbnails. Children keep their `parent` id as a record of lineage.
+#[tauri::command]
+pub async fn assets_delete(app: tauri::AppHandle, ids: Vec<String>) -> Result<usize, String> {
+    let dir = assets_dir(&app)?;
+    let mut library = ASSET_LIBRARY.lock().unwrap();
+    let removed: Vec<Asset> = ids
+        .iter()
+        .filter_map(|id| library.remove(id))
+        .collect();
+    if removed.is_empty() {
+        return Ok(0);
+    }
+    if let Err(e) = save_index(&dir, &library) {
+        library.extend(removed.into_iter().map(|asset| (asset.id.clone(), asset)));
+        return Err(e);
+    }
+    drop(library);
+
+    for asset in &removed {
+        let _ = std::fs::remove_file(dir.join(&asset.file));
+        if let Ok(entries) = std::fs::read_dir(dir.join("thumbnails")) {
+            for entry in entries.flatten() {
+                if entry.file_name().to_string_lossy().starts_with(&asset.id) {
+                    let _ = std::fs::remove_file(entry.path());
+                }
+            }
+        }
+    }
+    Ok(removed.len())
+}
+
+/// A PNG no larger than `size` on either side, cached on disk after the first request.
+pub fn thumbnail_bytes(app: &tauri::AppHandle, id: &str, size: Option<u32>) -> Result<Vec<u8>, String> {
+    let asset = asset(id)?;
+    let size = size.unwrap_or(DEFAULT_THUMBNAIL_SIZE).clamp(16, 2048);
+    let dir = assets_dir(app)?;
+    let thumbnail_path = dir.join("thumbnails").join(format!("{}_{}.png", asset.id, size));
+
+    if let Ok(cached) = std::fs::read(&thumbnail_path) {
+        return Ok(cached);
+    }
+
+    let data = std::fs::read(dir.join(&asset.file)).map_err(|e| format!("Failed to read asset: {}", e))?;
+    let image = image::load_from_memory(&data).map_err(|e| format!("Failed to decode asset: {}", e))?;
+    let mut thumbnail = Vec::new();
+    image
+        .thumbnail(size, size)
+        .write_to(&mut std::io::Cursor::new(&mut thumbnail), image::ImageOutputFormat::Png)
+        .map_err(|e| format!("Failed to encode thumbnail: {}", e))?;
+
+    if let Some(parent) = thumbnail_path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    std::fs::write(&thumbnail_path, &thumbnail).map_err(|e| format!("Failed to write thumbnail: {}", e))?;
+    Ok(thumbnail)
+}
+
+/// The stored file of an asset with its mime type.
+pub fn asset_bytes(app: &tauri::AppHandle, id: &str) -> Result<(Vec<u8>, String), String> {
+    let asset = asset(id)?;
+    let data = std::fs::read(assets_dir(app)?.join(&asset.file)).map_err(|e| format!("Failed to read asset: {}", e))?;
+    Ok((data, asset.mime_type))
+}
+
+#[tauri::command]
+pub async fn assets_thumbnail(app: tauri::AppHandle, id: String, size: Option<u32>) -> Result<String, String> {
+    Ok(general_purpose::STANDARD.encode(thumbnail_bytes(&app, &id, size)?))
+}
+
+/// Copies the files into `directory` named by id, alongside an `assets.json` with their index entries.
+#[tauri::command]
+pub async fn assets_export(app: tauri::AppHandle, ids: Vec<String>, directory: String) -> Result<AssetExport, String> {
+    let dir = assets_dir(&app)?;
+    let assets = ids
+        .iter()
+        .map(|id| asset(id))
+        .collect::<Result<Vec<Asset>, String>>()?;
+    let target = fs_sandbox::user_path(&app, &directory)?;
+    std::fs::create_dir_all(&target).map_err(|e| format!("Failed to create directory: {}", e))?;
+
+    let mut manifest = Vec::new();
+    for asset in assets {
+        let name = format!("{}.{}", asset.id, extension(&asset.mime_type));
+        std::fs
+            ::copy(dir.join(&asset.file), target.join(&name))
+            .map_err(|e| format!("Failed to export {}: {}", asset.id, e))?;
+        manifest.push(Asset { file: name, ..asset });
+    }
+    let json = serde_json::to_string_pretty(&manifest).map_err(|e| format!("Failed to serialize manifest: {}", e))?;
+    std::fs::write(target.join("assets.json"), json).map_err(|e| format!("Failed to write manifest: {}", e))?;
+
+    Ok(AssetExport { directory, files: manifest.len() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A PNG no other test produces, so entries in the shared library do not collide
+    fn unique_png(width: u32, height: u32) -> Vec<u8> {
+        let seed = uuid::Uuid::new_v4();
+        let mut image = image::RgbImage::new(width, height);
+        for (i, pixel) in image.pixels_mut().enumerate() {
+            let byte = seed.as_bytes()[i % 16];
+            *pixel = image::Rgb([byte, byte.wrapping_add(i as u8), 7]);
+        }
+        let mut data = Vec::new();
+        image::DynamicImage
+            ::ImageRgb8(image)
+            .write_to(&mut std::io::Cursor::new(&mut data), image::ImageOutputFormat::Png)
+            .unwrap();
+        data
+    }
+
+    fn info(tags: &[&str]) -> AssetInfo {
+        AssetInfo { tags: tags.iter().map(|t| t.to_string()).collect(), ..AssetInfo::default() }
+    }
+
+    fn indexed(dir: &Path) -> Vec<Asset> {
+        serde_json::from_str(&std::fs::read_to_string(dir.join(INDEX_FILE)).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn identical_content_is_stored_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let data = unique_png(3, 2);
+
+        let first = add_to_library(dir.path(), &data, info(&["synth"])).unwrap();
+        assert_eq!(first.id, format!("{:x}", Sha256::digest(&data)));
+        assert_eq!((first.mime_type.as_str(), first.width, first.height), ("image/png", Some(3), Some(2)));
+        assert_eq!(first.size, data.len() as u64);
+        assert_eq!(std::fs::read(dir.path().join(&first.file)).unwrap(), data);
+
+        let second = add_to_library(dir.path(), &data, info(&[])).unwrap();
+        assert_eq!(second.id, first.id);
+        assert_eq!(second.created_at, first.created_at);
+        let objects: Vec<_> = std::fs::read_dir(dir.path().join("objects").join(&first.id[..2])).unwrap().collect();
+        assert_eq!(objects.len(), 1);
+        assert_eq!(indexed(dir.path()).iter().filter(|a| a.id == first.id).count(), 1);
+    }
+
+    #[test]
+    fn re_adding_merges_tags_and_fills_missing_fields() {
+        let dir = tempfile::tempdir().unwrap();
+        let data = unique_png(2, 2);
+        let mut first = info(&["synth"]);
+        first.prompt = Some("blue jacket".to_string());
+        add_to_library(dir.path(), &data, first).unwrap();
+
+        let mut again = info(&["composite", "synth"]);
+        again.prompt = Some("ignored, already set".to_string());
+        again.model = Some("flux-dev".to_string());
+        again.seed = Some(42);
+        let merged = add_to_library(dir.path(), &data, again).unwrap();
+
+        assert_eq!(merged.tags, BTreeSet::from(["composite".to_string(), "synth".to_string()]));
+        assert_eq!(merged.prompt.as_deref(), Some("blue jacket"));
+        assert_eq!(merged.model.as_deref(), Some("flux-dev"));
+        assert_eq!(merged.seed, Some(42));
+        let saved = indexed(dir.path()).into_iter().find(|a| a.id == merged.id).unwrap();
+        assert_eq!(saved.tags, merged.tags);
+        assert_eq!(saved.model, merged.model);
+    }
+
+    #[test]
+    fn parents_must_exist_and_failed_saves_leave_the_library_unchanged() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut orphan = info(&[]);
+        orphan.parent = Some("0".repeat(64));
+        assert!(add_to_library(dir.path(), &unique_png(1, 1), orphan).unwrap_err().contains("does not exist"));
+
+        let parent = add_to_library(dir.path(), &unique_png(1, 1), info(&[])).unwrap();
+        let mut child = info(&[]);
+        child.parent = Some(parent.id.clone());
+        assert_eq!(add_to_library(dir.path(), &unique_png(1, 1), child).unwrap().parent, Some(parent.id.clone()));
+
+        // The index cannot be written where a directory stands in its place
+        let blocked = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(blocked.path().join(INDEX_FILE).join("x")).unwrap();
+        let data = unique_png(1, 1);
+        assert!(add_to_library(blocked.path(), &data, info(&[])).is_err());
+        assert!(asset(&format!("{:x}", Sha256::digest(&data))).is_err());
+        assert!(update_in_library(blocked.path(), &parent.id, |a| a.favourite = true).is_err());
+        assert!(!asset(&parent.id).unwrap().favourite);
+    }
+
+    /// Total and ids of the assets tagged `tag` that match `query`
+    async fn search(tag: &str, query: AssetQuery) -> (usize, Vec<String>) {
+        let page = assets_search(Some(AssetQuery { tags: vec![tag.to_string()], ..query })).await.unwrap();
+        (
+            page.total,
+            page.assets
+                .into_iter()
+                .map(|a| a.id)
+                .collect(),
+        )
+    }
+
+    #[tokio::test]
+    async fn search_filters_and_pages_newest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let tag = format!("search-{}", uuid::Uuid::new_v4());
+        let mut ids = Vec::new();
+        for (prompt, provider, favourite) in [
+            ("Red Hoodie", "replicate", false),
+            ("red shirt", "comfyui", true),
+            ("green shirt", "replicate", true),
+        ] {
+            let mut details = info(&[&tag]);
+            details.prompt = Some(prompt.to_string());
+            details.provider = Some(provider.to_string());
+            let asset = add_to_library(dir.path(), &unique_png(2, 1), details).unwrap();
+            update_in_library(dir.path(), &asset.id, |a| a.favourite = favourite).unwrap();
+            ids.push(asset.id);
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+        assert_eq!(search(&tag, AssetQuery::default()).await, (3, vec![ids[2].clone(), ids[1].clone(), ids[0].clone()]));
+        let red = AssetQuery { text: Some("RED".to_string()), ..AssetQuery::default() };
+        assert_eq!(search(&tag, red).await, (2, vec![ids[1].clone(), ids[0].clone()]));
+        let favourite_replicate = AssetQuery {
+            provider: Some("replicate".to_string()),
+            favourite: Some(true),
+            ..AssetQuery::default()
+        };
+        assert_eq!(search(&tag, favourite_replicate).await, (1, vec![ids[2].clone()]));
+        let page = AssetQuery { offset: Some(1), limit: Some(1), ..AssetQuery::default() };
+        assert_eq!(search(&tag, page).await, (3, vec![ids[1].clone()]));
+        let tag_text = AssetQuery { text: Some(tag[..12].to_uppercase()), ..AssetQuery::default() };
+        assert_eq!(search(&tag, tag_text).await.0, 3);
+        let untagged = AssetQuery { tags: vec![tag.clone(), "missing".to_string()], ..AssetQuery::default() };
+        assert_eq!(assets_search(Some(untagged)).await.unwrap().total, 0);
+    }
+}
@@ -0,0 +1,226 @@
+use crate::assets;
+use crate::fs_sandbox::{ app_data_path, app_data_root, resolve_within, user_path };
+use crate::generation::image_mime_type;
+use std::path::Path;
+use tauri::http::{ header, Request, Response, StatusCode };
+use tauri::ipc::{ InvokeBody, Request as IpcRequest, Response as IpcResponse };
+
+/// Scheme of the app-local protocol. Routes are `file/<path under app data>`, `asset/<id>` and `thumbnail/<id>?size=<px>`.
+pub const PROTOCOL: &str = "coinop-file";
+
+/// Origins of the bundled frontend (macOS/Linux, then Windows/Android without and with https)
+const APP_ORIGINS: [&str; 3] = ["tauri://localhost", "http://tauri.localhost", "https://tauri.localhost"];
+/// `build.devUrl` from tauri.conf.json, only trusted in debug builds
+const DEV_ORIGIN: &str = "http://localhost:1422";
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3).and_then(|h| std::str::from_utf8(h).ok());
+        match (bytes[i], hex.and_then(|h| u8::from_str_radix(h, 16).ok())) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn raw_body<'a>(request: &'a IpcRequest<'_>) -> Result<&'a [u8], String> {
+    match request.body() {
+        InvokeBody::Raw(bytes) => Ok(bytes),
+        InvokeBody::Json(_) => Err("Expected a binary request body".to_string()),
+    }
+}
+
+/// Header values are percent-encoded by the frontend so paths with non-ASCII characters survive.
+fn header_value(request: &IpcRequest<'_>, name: &str) -> Result<String, String> {
+    request
+        .headers()
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(percent_decode)
+        .ok_or_else(|| format!("Missing {} header", name))
+}
+
+/// Bytes of `path` under `root`; `root` is the app data directory everywhere but the benchmarks.
+pub fn read_file_under(root: &Path, path: &str) -> Result<Vec<u8>, String> {
+    let full_path = resolve_within(root, path)?;
+    std::fs::read(&full_path).map_err(|e| format!("Failed to read binary file: {}", e))
+}
+
+#[tauri::command]
+pub async fn read_binary_file(app: tauri::AppHandle, path: String) -> Result<IpcResponse, String> {
+    Ok(IpcResponse::new(read_file_under(&app_data_root(&app)?, &path)?))
+}
+
+/// Raw-body counterpart of `save_binary_file`, with the app-data relative path in the `path` header.
+#[tauri::command]
+pub async fn save_binary_file_raw(app: tauri::AppHandle, request: IpcRequest<'_>) -> Result<(), String> {
+    let full_path = app_data_path(&app, &header_value(&request, "path")?)?;
+    if let Some(parent) = full_path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create parent directories: {}", e))?;
+    }
+    std::fs::write(&full_path, raw_body(&request)?).map_err(|e| format!("Failed to write binary file: {}", e))
+}
+
+/// Raw-body counterpart of `write_image_file`, with the destination in the `file-path` header.
+#[tauri::command]
+pub async fn write_image_bytes(app: tauri::AppHandle, request: IpcRequest<'_>) -> Result<String, String> {
+    let file_path = header_value(&request, "file-path")?;
+    let target = user_path(&app, &file_path)?;
+    std::fs::write(&target, raw_body(&request)?).map_err(|e| format!("Failed to write file: {}", e))?;
+    Ok(format!("Image saved successfully: {}", file_path))
+}
+
+#[tauri::command]
+pub async fn download_image_bytes(image_url: String) -> Result<IpcResponse, String> {
+    Ok(IpcResponse::new(crate::download_image_bytes(&image_url).await?))
+}
+
+#[tauri::command]
+pub async fn comfyui_download_image_bytes(
+    comfy_url: String,
+    filename: String,
+    subfolder: Option<String>,
+    image_type: Option<String>
+) -> Result<IpcResponse, String> {
+    let bytes = crate::comfyui_view_bytes(
+        &comfy_url,
+        &filename,
+        subfolder.as_deref(),
+        image_type.as_deref()
+    ).await?;
+    Ok(IpcResponse::new(bytes))
+}
+
+/// Raw-body counterpart of `comfyui_upload_image`, with the server in the `comfy-url` header.
+#[tauri::command]
+pub async fn comfyui_upload_image_bytes(request: IpcRequest<'_>) -> Result<String, String> {
+    let comfy_url = header_value(&request, "comfy-url")?;
+    let bytes = raw_body(&request)?.to_vec();
+    crate::comfyui_upload_bytes(&comfy_url, bytes).await
+}
+
+/// The request's origin if it is the app's own page. Where the protocol is served from its own origin
+/// (`http://coinop-file.localhost`), only the app may `fetch` it; other pages get no CORS grant.
+fn allowed_origin(request: &Request<Vec<u8>>) -> Option<&str> {
+    let origin = request.headers().get(header::ORIGIN)?.to_str().ok()?;
+    (APP_ORIGINS.contains(&origin) || (cfg!(debug_assertions) && origin == DEV_ORIGIN)).then_some(origin)
+}
+
+fn respond(result: Result<(Vec<u8>, String), (StatusCode, String)>, origin: Option<&str>) -> Response<Vec<u8>> {
+    let mut builder = Response::builder().header(header::VARY, "Origin");
+    if let Some(origin) = origin {
+        builder = builder.header(header::ACCESS_CONTROL_ALLOW_ORIGIN, origin);
+    }
+    let response = match result {
+        Ok((data, mime_type)) =>
+            builder
+                .status(StatusCode::OK)
+                .header(header::CONTENT_TYPE, mime_type)
+                .header(header::CACHE_CONTROL, "no-cache")
+                .body(data),
+        Err((status, message)) =>
+            builder.status(status).header(header::CONTENT_TYPE, "text/plain").body(message.into_bytes()),
+    };
+    response.unwrap_or_else(|_| Response::new(Vec::new()))
+}
+
+/// The `file/<path>` route of the protocol, serving `path` under `root` with its image MIME type.
+pub fn file_route(root: &Path, path: &str) -> Result<(Vec<u8>, String), (StatusCode, String)> {
+    let full_path = resolve_within(root, path).map_err(|e| (StatusCode::FORBIDDEN, e))?;
+    let data = std::fs::read(&full_path).map_err(|e| (StatusCode::NOT_FOUND, format!("Failed to read {}: {}", path, e)))?;
+    let mime_type = image_mime_type(&data).to_string();
+    Ok((data, mime_type))
+}
+
+fn route(app: &tauri::AppHandle, request: &Request<Vec<u8>>) -> Result<(Vec<u8>, String), (StatusCode, String)> {
+    let path = percent_decode(request.uri().path().trim_start_matches('/'));
+    let (kind, rest) = path.split_once('/').ok_or((StatusCode::NOT_FOUND, format!("Unknown route {}", path)))?;
+    let not_found = |e: String| (StatusCode::NOT_FOUND, e);
+    match kind {
+        "file" => {
+            let root = app_data_root(app).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+            file_route(&root, rest)
+        }
+        "asset" => assets::asset_bytes(app, rest).map_err(not_found),
+        "thumbnail" => {
+            let size = request
+                .uri()
+                .query()
+                .into_iter()
+                .flat_map(|q| q.split('&'))
+                .find_map(|pair| pair.strip_prefix("size="))
+                .and_then(|s| s.parse().ok());
+            assets::thumbnail_bytes(app, rest, size)
+                .map(|data| (data, "image/png".to_string()))
+                .map_err(not_found)
+        }
+        _ => Err(not_found(format!("Unknown route {}", path))),
+    }
+}
+
+/// Serves the `coinop-file` protocol off the webview thread, so large files never pass through JSON.
+pub fn handle_protocol(app: tauri::AppHandle, request: Request<Vec<u8>>, responder: tauri::UriSchemeResponder) {
+    std::thread::spawn(move || {
+        responder.respond(respond(route(&app, &request), allowed_origin(&request)));
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request_from(origin: Option<&str>) -> Request<Vec<u8>> {
+        let mut builder = Request::builder().uri("coinop-file://localhost/file/a.png");
+        if let Some(origin) = origin {
+            builder = builder.header(header::ORIGIN, origin);
+        }
+        builder.body(Vec::new()).unwrap()
+    }
+
+    #[test]
+    fn only_the_app_origin_may_read_protocol_responses() {
+        for origin in APP_ORIGINS {
+            let response = respond(Ok((vec![1, 2, 3], "image/png".to_string())), allowed_origin(&request_from(Some(origin))));
+            assert_eq!(response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN], origin);
+            assert_eq!(response.headers()[header::VARY], "Origin");
+        }
+        for origin in [Some("https://evil.example"), Some("null"), Some("http://tauri.localhost.evil.example"), None] {
+            let request = request_from(origin);
+            assert_eq!(allowed_origin(&request), None);
+            let response = respond(Err((StatusCode::NOT_FOUND, "missing".to_string())), allowed_origin(&request));
+            assert!(!response.headers().contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN));
+        }
+        assert_eq!(allowed_origin(&request_from(Some(DEV_ORIGIN))).is_some(), cfg!(debug_assertions));
+    }
+
+    #[test]
+    fn files_are_served_from_under_the_root_only() {
+        let root = tempfile::tempdir().unwrap();
+        let png = [&b"\x89PNG\r\n\x1a\n"[..], &[0u8; 16]].concat();
+        std::fs::create_dir_all(root.path().join("designs")).unwrap();
+        std::fs::write(root.path().join("designs/synth.png"), &png).unwrap();
+
+        assert_eq!(file_route(root.path(), "designs/synth.png").unwrap(), (png.clone(), "image/png".to_string()));
+        assert_eq!(read_file_under(root.path(), "designs/synth.png").unwrap(), png);
+        assert_eq!(file_route(root.path(), "../synth.png").unwrap_err().0, StatusCode::FORBIDDEN);
+        assert_eq!(file_route(root.path(), "designs/missing.png").unwrap_err().0, StatusCode::NOT_FOUND);
+        assert!(read_file_under(root.path(), "/etc/passwd").is_err());
+    }
+
+    #[test]
+    fn percent_decoding_keeps_invalid_escapes() {
+        assert_eq!(percent_decode("caf%C3%A9%20%2Fx"), "café /x");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz"), "%zz");
+    }
+}
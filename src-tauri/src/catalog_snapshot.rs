@@ -0,0 +1,453 @@
+use crate::subgraph_client::{ self, RawChildEntity, RawMetadata, RawTemplate };
+use chrono::{ DateTime, Utc };
+use once_cell::sync::Lazy;
+use serde::{ Deserialize, Serialize };
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{ Path, PathBuf };
+use std::sync::{ Arc, RwLock };
+use tauri::Manager;
+
+const BUNDLED_SNAPSHOT: &str = include_str!("../snapshots/catalog.json");
+const SNAPSHOT_DIR: &str = "catalog";
+/// Layout of the snapshot files; newer files are skipped by older builds rather than misread.
+const FORMAT_VERSION: u32 = 1;
+/// Refreshed snapshots kept in app data so earlier versions stay available for diffs.
+const KEEP_SNAPSHOTS: usize = 10;
+
+static BUNDLED: Lazy<Arc<CatalogSnapshot>> = Lazy::new(||
+    Arc::new(serde_json::from_str(BUNDLED_SNAPSHOT).expect("bundled catalog snapshot is valid"))
+);
+static CURRENT: Lazy<RwLock<Option<Arc<CatalogSnapshot>>>> = Lazy::new(|| RwLock::new(None));
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogSnapshot {
+    #[serde(rename = "formatVersion")]
+    pub format_version: u32,
+    pub version: u64,
+    #[serde(rename = "createdAt")]
+    pub created_at: DateTime<Utc>,
+    /// `bundled`, or the endpoint a refresh was fetched from
+    pub source: String,
+    pub templates: Vec<RawTemplate>,
+    pub childs: Vec<RawChildEntity>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SnapshotInfo {
+    pub version: u64,
+    #[serde(rename = "createdAt")]
+    pub created_at: DateTime<Utc>,
+    pub source: String,
+    pub templates: usize,
+    pub childs: usize,
+    pub bundled: bool,
+    pub current: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum SnapshotRef {
+    Bundled,
+    Current,
+    Live,
+    /// A refreshed snapshot saved in app data. Saved and bundled version numbers can overlap, so the bundled
+    /// snapshot is only reached through `Bundled`.
+    Version {
+        version: u64,
+    },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PriceChange {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiffEntry {
+    pub key: String,
+    pub title: Option<String>,
+    pub price: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangedEntry {
+    pub key: String,
+    pub title: Option<String>,
+    #[serde(rename = "priceChange")]
+    pub price_change: Option<PriceChange>,
+    /// Other top-level fields whose values differ
+    pub fields: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EntityDiff {
+    pub added: Vec<DiffEntry>,
+    pub removed: Vec<DiffEntry>,
+    pub changed: Vec<ChangedEntry>,
+}
+
+impl EntityDiff {
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CatalogDiff {
+    pub from: SnapshotInfo,
+    pub to: SnapshotInfo,
+    pub templates: EntityDiff,
+    pub childs: EntityDiff,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RefreshResult {
+    /// False when the live catalog matched the current snapshot and nothing was written
+    pub saved: bool,
+    pub snapshot: SnapshotInfo,
+    pub diff: CatalogDiff,
+}
+
+trait CatalogEntry: Serialize + PartialEq {
+    fn key(&self) -> String;
+    fn metadata(&self) -> Option<&RawMetadata>;
+    fn price(&self) -> &str;
+}
+
+impl CatalogEntry for RawTemplate {
+    fn key(&self) -> String {
+        format!("{}:{}", self.template_contract, self.template_id)
+    }
+    fn metadata(&self) -> Option<&RawMetadata> {
+        self.metadata.as_ref()
+    }
+    fn price(&self) -> &str {
+        &self.physical_price
+    }
+}
+
+impl CatalogEntry for RawChildEntity {
+    fn key(&self) -> String {
+        format!("{}:{}", self.child_contract, self.child_id)
+    }
+    fn metadata(&self) -> Option<&RawMetadata> {
+        self.metadata.as_ref()
+    }
+    fn price(&self) -> &str {
+        &self.physical_price
+    }
+}
+
+fn diff_entry<T: CatalogEntry>(item: &T) -> DiffEntry {
+    DiffEntry {
+        key: item.key(),
+        title: item.metadata().and_then(|m| m.title.clone()),
+        price: item.price().to_string(),
+    }
+}
+
+fn changed_fields<T: CatalogEntry>(from: &T, to: &T) -> Vec<String> {
+    let (Ok(Value::Object(from)), Ok(Value::Object(to))) = (serde_json::to_value(from), serde_json::to_value(to)) else {
+        return Vec::new();
+    };
+    let mut fields: Vec<String> = from
+        .keys()
+        .chain(to.keys())
+        .filter(|field| *field != "physicalPrice" && from.get(*field) != to.get(*field))
+        .cloned()
+        .collect();
+    fields.sort();
+    fields.dedup();
+    fields
+}
+
+fn diff_entities<T: CatalogEntry>(from: &[T], to: &[T]) -> EntityDiff {
+    let from: BTreeMap<String, &T> = from
+        .iter()
+        .map(|item| (item.key(), item))
+        .collect();
+    let to: BTreeMap<String, &T> = to
+        .iter()
+        .map(|item| (item.key(), item))
+        .collect();
+
+    let mut diff = EntityDiff::default();
+    for (key, old) in &from {
+        match to.get(key) {
+            None => diff.removed.push(diff_entry(*old)),
+            Some(new) if old != new => {
+                let price_change = (old.price() != new.price()).then(|| PriceChange {
+                    from: old.price().to_string(),
+                    to: new.price().to_string(),
+                });
+                diff.changed.push(ChangedEntry {
+                    key: key.clone(),
+                    title: new.metadata().and_then(|m| m.title.clone()),
+                    price_change,
+                    fields: changed_fields(*old, *new),
+                });
+            }
+            Some(_) => {}
+        }
+    }
+    diff.added = to
+        .iter()
+        .filter(|(key, _)| !from.contains_key(*key))
+        .map(|(_, item)| diff_entry(*item))
+        .collect();
+    diff
+}
+
+fn is_bundled(snapshot: &CatalogSnapshot) -> bool {
+    snapshot.source == "bundled"
+}
+
+fn info(snapshot: &CatalogSnapshot) -> SnapshotInfo {
+    let current = current();
+    SnapshotInfo {
+        version: snapshot.version,
+        created_at: snapshot.created_at,
+        source: snapshot.source.clone(),
+        templates: snapshot.templates.len(),
+        childs: snapshot.childs.len(),
+        bundled: is_bundled(snapshot),
+        current: is_bundled(snapshot) == is_bundled(&current) && snapshot.version == current.version,
+    }
+}
+
+fn diff(from: &CatalogSnapshot, to: &CatalogSnapshot) -> CatalogDiff {
+    CatalogDiff {
+        from: info(from),
+        to: info(to),
+        templates: diff_entities(&from.templates, &to.templates),
+        childs: diff_entities(&from.childs, &to.childs),
+    }
+}
+
+/// The newest refreshed snapshot, or the bundled one if none has been saved.
+pub fn current() -> Arc<CatalogSnapshot> {
+    CURRENT.read().unwrap().clone().unwrap_or_else(|| BUNDLED.clone())
+}
+
+fn snapshot_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    Ok(app_data_dir.join(SNAPSHOT_DIR))
+}
+
+fn snapshot_file(dir: &Path, version: u64) -> PathBuf {
+    dir.join(format!("v{}.json", version))
+}
+
+/// Saved versions, newest first.
+fn saved_versions(dir: &Path) -> Vec<u64> {
+    let mut versions: Vec<u64> = std::fs
+        ::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name();
+            name.to_str()?.strip_prefix('v')?.strip_suffix(".json")?.parse().ok()
+        })
+        .collect();
+    versions.sort_unstable_by(|a, b| b.cmp(a));
+    versions
+}
+
+fn read_snapshot(dir: &Path, version: u64) -> Result<CatalogSnapshot, String> {
+    let json = std::fs
+        ::read_to_string(snapshot_file(dir, version))
+        .map_err(|e| format!("Failed to read catalog snapshot v{}: {}", version, e))?;
+    let snapshot: CatalogSnapshot = serde_json
+        ::from_str(&json)
+        .map_err(|e| format!("Failed to parse catalog snapshot v{}: {}", version, e))?;
+    if snapshot.format_version > FORMAT_VERSION {
+        return Err(format!("Catalog snapshot v{} was written by a newer version of the app", version));
+    }
+    Ok(snapshot)
+}
+
+fn save_snapshot(dir: &Path, snapshot: &CatalogSnapshot) -> Result<(), String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create directory: {}", e))?;
+    let json = serde_json
+        ::to_string_pretty(snapshot)
+        .map_err(|e| format!("Failed to serialize catalog snapshot: {}", e))?;
+    let target = snapshot_file(dir, snapshot.version);
+    let temp = target.with_extension("json.tmp");
+    std::fs::write(&temp, json).map_err(|e| format!("Failed to write catalog snapshot: {}", e))?;
+    std::fs::rename(&temp, &target).map_err(|e| format!("Failed to write catalog snapshot: {}", e))?;
+
+    for old in saved_versions(dir).into_iter().skip(KEEP_SNAPSHOTS) {
+        let _ = std::fs::remove_file(snapshot_file(dir, old));
+    }
+    Ok(())
+}
+
+/// Loads the newest readable snapshot from app data at startup, staying on the bundled one otherwise.
+/// A saved snapshot older than the bundled one (from before an app update) is ignored.
+pub fn load_snapshots(app: &tauri::AppHandle) {
+    let Ok(dir) = snapshot_dir(app) else {
+        return;
+    };
+    for version in saved_versions(&dir) {
+        if version <= BUNDLED.version {
+            break;
+        }
+        match read_snapshot(&dir, version) {
+            Ok(snapshot) => {
+                *CURRENT.write().unwrap() = Some(Arc::new(snapshot));
+                return;
+            }
+            Err(e) => log::warn!("{}", e),
+        }
+    }
+}
+
+async fn live_snapshot() -> Result<CatalogSnapshot, String> {
+    let templates = subgraph_client::live_templates().await.map_err(|e| e.to_string())?;
+    let childs = subgraph_client::live_childs().await.map_err(|e| e.to_string())?;
+    Ok(CatalogSnapshot {
+        format_version: FORMAT_VERSION,
+        version: 0,
+        created_at: Utc::now(),
+        source: subgraph_client::config().endpoint,
+        templates,
+        childs,
+    })
+}
+
+async fn resolve(dir: &Path, snapshot: SnapshotRef) -> Result<Arc<CatalogSnapshot>, String> {
+    match snapshot {
+        SnapshotRef::Bundled => Ok(BUNDLED.clone()),
+        SnapshotRef::Current => Ok(current()),
+        SnapshotRef::Live => Ok(Arc::new(live_snapshot().await?)),
+        SnapshotRef::Version { version } => Ok(Arc::new(read_snapshot(dir, version)?)),
+    }
+}
+
+#[tauri::command]
+pub async fn catalog_list_snapshots(app: tauri::AppHandle) -> Result<Vec<SnapshotInfo>, String> {
+    let dir = snapshot_dir(&app)?;
+    let mut snapshots: Vec<SnapshotInfo> = saved_versions(&dir)
+        .into_iter()
+        .filter(|version| *version > BUNDLED.version)
+        .filter_map(|version| read_snapshot(&dir, version).ok())
+        .map(|snapshot| info(&snapshot))
+        .collect();
+    snapshots.push(info(&BUNDLED));
+    Ok(snapshots)
+}
+
+/// Fetches the live catalog and saves it as the next snapshot version when it differs from the current one.
+#[tauri::command]
+pub async fn catalog_refresh_snapshot(app: tauri::AppHandle) -> Result<RefreshResult, String> {
+    let dir = snapshot_dir(&app)?;
+    let previous = current();
+    let mut snapshot = live_snapshot().await?;
+    snapshot.version = saved_versions(&dir).first().copied().unwrap_or(0).max(previous.version) + 1;
+
+    let changes = diff(&previous, &snapshot);
+    if changes.templates.is_empty() && changes.childs.is_empty() {
+        return Ok(RefreshResult { saved: false, snapshot: info(&previous), diff: changes });
+    }
+
+    save_snapshot(&dir, &snapshot)?;
+    *CURRENT.write().unwrap() = Some(Arc::new(snapshot));
+    let current = current();
+    Ok(RefreshResult { saved: true, snapshot: info(&current), diff: diff(&previous, &current) })
+}
+
+#[tauri::command]
+pub async fn catalog_diff(app: tauri::AppHandle, from: SnapshotRef, to: SnapshotRef) -> Result<CatalogDiff, String> {
+    let dir = snapshot_dir(&app)?;
+    let from = resolve(&dir, from).await?;
+    let to = resolve(&dir, to).await?;
+    Ok(diff(&from, &to))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_snapshot_parses() {
+        let snapshot: CatalogSnapshot = serde_json::from_str(BUNDLED_SNAPSHOT).unwrap();
+        assert!(snapshot.format_version <= FORMAT_VERSION);
+        assert!(is_bundled(&snapshot));
+        assert!(!snapshot.templates.is_empty());
+        assert!(snapshot.templates.iter().all(|template| template.metadata.is_some()));
+
+        let info = info(&BUNDLED);
+        assert!(info.bundled);
+        assert_eq!(info.version, snapshot.version);
+        assert_eq!((info.templates, info.childs), (snapshot.templates.len(), snapshot.childs.len()));
+    }
+
+    fn template(id: &str, price: &str, child_id: &str) -> RawTemplate {
+        serde_json
+            ::from_value(
+                serde_json::json!({
+                    "templateContract": "0xtemplates",
+                    "templateId": id,
+                    "physicalPrice": price,
+                    "metadata": { "title": format!("Template {}", id) },
+                    "childReferences": [{ "placementURI": "ipfs://front", "childId": child_id, "childContract": "0xchilds" }],
+                })
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn diff_reports_added_removed_repriced_and_rereferenced_templates() {
+        let from = vec![template("1", "10", "a"), template("2", "20", "a"), template("3", "30", "a"), template("4", "40", "a")];
+        let to = vec![template("2", "25", "a"), template("3", "30", "b"), template("4", "40", "a"), template("5", "50", "a")];
+
+        let diff = diff_entities(&from, &to);
+        let keys = |entries: &[DiffEntry]| entries.iter().map(|entry| entry.key.clone()).collect::<Vec<_>>();
+        assert_eq!(keys(&diff.added), ["0xtemplates:5"]);
+        assert_eq!(diff.added[0].price, "50");
+        assert_eq!(keys(&diff.removed), ["0xtemplates:1"]);
+        assert_eq!(diff.removed[0].title.as_deref(), Some("Template 1"));
+
+        assert_eq!(diff.changed.len(), 2);
+        let repriced = &diff.changed[0];
+        assert_eq!(repriced.key, "0xtemplates:2");
+        let price_change = repriced.price_change.as_ref().unwrap();
+        assert_eq!((price_change.from.as_str(), price_change.to.as_str()), ("20", "25"));
+        assert!(repriced.fields.is_empty());
+
+        let rereferenced = &diff.changed[1];
+        assert_eq!(rereferenced.key, "0xtemplates:3");
+        assert!(rereferenced.price_change.is_none());
+        assert_eq!(rereferenced.fields, ["childReferences"]);
+
+        assert!(diff_entities(&from, &from).is_empty());
+    }
+
+    #[tokio::test]
+    async fn saved_versions_are_not_shadowed_by_the_bundled_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let saved = CatalogSnapshot {
+            version: BUNDLED.version,
+            source: "https://subgraph.example/graphql".to_string(),
+            templates: BUNDLED.templates[..1].to_vec(),
+            ..(**BUNDLED).clone()
+        };
+        save_snapshot(dir.path(), &saved).unwrap();
+
+        let resolved = resolve(dir.path(), SnapshotRef::Version { version: BUNDLED.version }).await.unwrap();
+        assert_eq!(resolved.source, saved.source);
+        assert_eq!(resolved.templates.len(), 1);
+        let bundled = resolve(dir.path(), SnapshotRef::Bundled).await.unwrap();
+        assert!(is_bundled(&bundled));
+
+        let (saved_info, bundled_info) = (info(&resolved), info(&bundled));
+        assert!(!saved_info.bundled && !saved_info.current);
+        assert!(bundled_info.bundled && bundled_info.current);
+        assert!(resolve(dir.path(), SnapshotRef::Version { version: BUNDLED.version + 1 }).await.is_err());
+    }
+}
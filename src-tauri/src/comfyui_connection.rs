@@ -0,0 +1,539 @@
+use crate::credentials;
+use base64::{ engine::general_purpose, Engine as _ };
+use once_cell::sync::Lazy;
+use serde::{ Deserialize, Serialize };
+use sha2::{ Digest, Sha256 };
+use std::collections::HashMap;
+use std::sync::{ Arc, Mutex };
+use std::time::Duration;
+use tauri::Manager;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::handshake::client::Request;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::Connector;
+
+const PROFILES_FILE: &str = "comfyui_profiles.json";
+
+/// Connection profiles by normalised server URL
+type ProfileRegistry = Arc<Mutex<HashMap<String, ConnectionProfile>>>;
+
+static COMFYUI_PROFILES: Lazy<ProfileRegistry> = Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
+
+/// DER certificates whose SHA-256 matched a pinned fingerprint, by fingerprint
+static PINNED_CERTIFICATES: Lazy<Mutex<HashMap<String, Vec<u8>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum TlsMode {
+    /// Verify against the operating system's trusted roots
+    #[default]
+    System,
+    /// Also trust the CA certificate(s) in this PEM
+    CustomCa {
+        pem: String,
+    },
+    /// Trust only the server certificate with this SHA-256 fingerprint, which should be self-signed
+    Pinned {
+        sha256: String,
+    },
+    /// Skip certificate checks; `confirmed` must be set to show this was chosen on purpose
+    Insecure {
+        confirmed: bool,
+    },
+}
+
+/// How to authenticate with a server. Tokens and passwords live in the credential store under `credential`, which
+/// must be bound to the server's origin; profiles only ever hold the name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Auth {
+    Bearer {
+        credential: String,
+    },
+    Basic {
+        username: String,
+        credential: String,
+    },
+}
+
+impl Auth {
+    fn credential(&self) -> &str {
+        match self {
+            Auth::Bearer { credential } | Auth::Basic { credential, .. } => credential,
+        }
+    }
+
+    fn header_value(&self, comfy_url: &str) -> Result<HeaderValue, String> {
+        let secret = credentials::secret_for(self.credential(), comfy_url)?;
+        let value = match self {
+            Auth::Bearer { .. } => format!("Bearer {}", secret),
+            Auth::Basic { username, .. } =>
+                format!("Basic {}", general_purpose::STANDARD.encode(format!("{}:{}", username, secret))),
+        };
+        let mut header = HeaderValue::from_str(&value).map_err(|e| format!("Invalid auth header: {}", e))?;
+        header.set_sensitive(true);
+        Ok(header)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionProfile {
+    pub url: String,
+    #[serde(default)]
+    pub tls: TlsMode,
+    #[serde(default)]
+    pub auth: Option<Auth>,
+    #[serde(rename = "connectTimeoutSecs", default)]
+    pub connect_timeout_secs: Option<u64>,
+    #[serde(rename = "timeoutSecs", default)]
+    pub timeout_secs: Option<u64>,
+}
+
+impl ConnectionProfile {
+    fn for_url(url: &str) -> Self {
+        ConnectionProfile {
+            url: url.to_string(),
+            tls: TlsMode::System,
+            auth: None,
+            connect_timeout_secs: None,
+            timeout_secs: None,
+        }
+    }
+}
+
+fn profile_key(comfy_url: &str) -> String {
+    comfy_url.trim().trim_end_matches('/').to_lowercase()
+}
+
+fn normalize_fingerprint(fingerprint: &str) -> String {
+    fingerprint
+        .chars()
+        .filter(|c| c.is_ascii_hexdigit())
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// The profile registered for a server, or system TLS with no auth if there is none.
+pub fn profile_for(comfy_url: &str) -> ConnectionProfile {
+    COMFYUI_PROFILES.lock()
+        .unwrap()
+        .get(&profile_key(comfy_url))
+        .cloned()
+        .unwrap_or_else(|| ConnectionProfile::for_url(comfy_url))
+}
+
+fn validate_profile(profile: &ConnectionProfile) -> Result<(), String> {
+    let url = reqwest::Url::parse(&profile.url).map_err(|e| format!("Invalid ComfyUI URL {}: {}", profile.url, e))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!("Unsupported ComfyUI URL: {}", profile.url));
+    }
+    match &profile.tls {
+        TlsMode::System => {}
+        TlsMode::CustomCa { pem } => {
+            reqwest::Certificate::from_pem(pem.as_bytes()).map_err(|e| format!("Invalid CA certificate: {}", e))?;
+        }
+        TlsMode::Pinned { sha256 } => {
+            if normalize_fingerprint(sha256).len() != 64 {
+                return Err("Pinned fingerprint must be a SHA-256 hash (64 hex digits)".to_string());
+            }
+        }
+        TlsMode::Insecure { confirmed } => {
+            if !confirmed {
+                return Err(format!("Insecure TLS for {} must be confirmed explicitly", profile.url));
+            }
+        }
+    }
+    if let Some(auth) = &profile.auth {
+        credentials::check_destination(auth.credential(), &profile.url)?;
+    }
+    Ok(())
+}
+
+/// Connects to the server without verification and returns its certificate if the SHA-256 fingerprint matches.
+async fn pinned_certificate(comfy_url: &str, sha256: &str, timeout: Duration) -> Result<Vec<u8>, String> {
+    let expected = normalize_fingerprint(sha256);
+    if let Some(der) = PINNED_CERTIFICATES.lock().unwrap().get(&expected) {
+        return Ok(der.clone());
+    }
+
+    let url = reqwest::Url::parse(comfy_url).map_err(|e| format!("Invalid ComfyUI URL {}: {}", comfy_url, e))?;
+    let host = url.host_str().ok_or(format!("No host in {}", comfy_url))?.to_string();
+    let port = url.port_or_known_default().unwrap_or(443);
+    let der = tokio::task
+        ::spawn_blocking(move || -> Result<Vec<u8>, String> {
+            use std::net::ToSocketAddrs;
+            let address = (host.as_str(), port)
+                .to_socket_addrs()
+                .map_err(|e| format!("Failed to resolve {}: {}", host, e))?
+                .next()
+                .ok_or(format!("Failed to resolve {}", host))?;
+            let stream = std::net::TcpStream
+                ::connect_timeout(&address, timeout)
+                .map_err(|e| format!("Failed to connect to {}: {}", host, e))?;
+            let connector = native_tls::TlsConnector
+                ::builder()
+                .danger_accept_invalid_certs(true)
+                .danger_accept_invalid_hostnames(true)
+                .build()
+                .map_err(|e| format!("Failed to create TLS connector: {}", e))?;
+            let tls = connector.connect(&host, stream).map_err(|e| format!("TLS handshake with {} failed: {}", host, e))?;
+            let certificate = tls
+                .peer_certificate()
+                .map_err(|e| format!("Failed to read certificate: {}", e))?
+                .ok_or(format!("{} sent no certificate", host))?;
+            certificate.to_der().map_err(|e| format!("Failed to encode certificate: {}", e))
+        }).await
+        .map_err(|e| format!("Certificate check failed: {}", e))??;
+
+    let actual = Sha256::digest(&der)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<String>();
+    if actual != expected {
+        return Err(format!("Certificate fingerprint of {} is {}, expected {}", comfy_url, actual, expected));
+    }
+    PINNED_CERTIFICATES.lock().unwrap().insert(expected, der.clone());
+    Ok(der)
+}
+
+/// Builds an HTTP client for a ComfyUI server using its connection profile.
+pub async fn http_client(comfy_url: &str) -> Result<reqwest::Client, String> {
+    let profile = profile_for(comfy_url);
+    let connect_timeout = Duration::from_secs(profile.connect_timeout_secs.unwrap_or(10));
+    let mut builder = reqwest::Client::builder().connect_timeout(connect_timeout);
+    if let Some(secs) = profile.timeout_secs {
+        builder = builder.timeout(Duration::from_secs(secs));
+    }
+
+    builder = match &profile.tls {
+        TlsMode::System => builder,
+        TlsMode::CustomCa { pem } => {
+            let certificate = reqwest::Certificate
+                ::from_pem(pem.as_bytes())
+                .map_err(|e| format!("Invalid CA certificate: {}", e))?;
+            builder.add_root_certificate(certificate)
+        }
+        TlsMode::Pinned { sha256 } => {
+            let der = pinned_certificate(comfy_url, sha256, connect_timeout).await?;
+            let certificate = reqwest::Certificate
+                ::from_der(&der)
+                .map_err(|e| format!("Invalid pinned certificate: {}", e))?;
+            builder
+                .tls_built_in_root_certs(false)
+                .add_root_certificate(certificate)
+                .danger_accept_invalid_hostnames(true)
+        }
+        TlsMode::Insecure { confirmed: true } => builder.danger_accept_invalid_certs(true),
+        TlsMode::Insecure { confirmed: false } => {
+            return Err(format!("Insecure TLS for {} must be confirmed explicitly", comfy_url));
+        }
+    };
+
+    if let Some(auth) = &profile.auth {
+        let mut headers = reqwest::header::HeaderMap::new();
+        let value = reqwest::header::HeaderValue
+            ::from_bytes(auth.header_value(&profile.url)?.as_bytes())
+            .map_err(|e| format!("Invalid auth header: {}", e))?;
+        headers.insert(reqwest::header::AUTHORIZATION, value);
+        builder = builder.default_headers(headers);
+    }
+
+    builder.build().map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
+/// Builds the websocket handshake request and TLS connector for a ComfyUI server using its connection profile.
+pub async fn websocket_request(comfy_url: &str, ws_url: &str) -> Result<(Request, Option<Connector>), String> {
+    let profile = profile_for(comfy_url);
+    let mut request = ws_url.into_client_request().map_err(|e| format!("Invalid websocket URL: {}", e))?;
+    if let Some(auth) = &profile.auth {
+        request.headers_mut().insert("Authorization", auth.header_value(&profile.url)?);
+    }
+    if !ws_url.starts_with("wss://") {
+        return Ok((request, None));
+    }
+
+    let mut tls = native_tls::TlsConnector::builder();
+    match &profile.tls {
+        TlsMode::System => {}
+        TlsMode::CustomCa { pem } => {
+            let certificate = native_tls::Certificate
+                ::from_pem(pem.as_bytes())
+                .map_err(|e| format!("Invalid CA certificate: {}", e))?;
+            tls.add_root_certificate(certificate);
+        }
+        TlsMode::Pinned { sha256 } => {
+            let connect_timeout = Duration::from_secs(profile.connect_timeout_secs.unwrap_or(10));
+            let der = pinned_certificate(comfy_url, sha256, connect_timeout).await?;
+            let certificate = native_tls::Certificate
+                ::from_der(&der)
+                .map_err(|e| format!("Invalid pinned certificate: {}", e))?;
+            tls.disable_built_in_roots(true).add_root_certificate(certificate).danger_accept_invalid_hostnames(true);
+        }
+        TlsMode::Insecure { confirmed: true } => {
+            tls.danger_accept_invalid_certs(true);
+        }
+        TlsMode::Insecure { confirmed: false } => {
+            return Err(format!("Insecure TLS for {} must be confirmed explicitly", comfy_url));
+        }
+    }
+    let tls = tls.build().map_err(|e| format!("Failed to create TLS connector: {}", e))?;
+    Ok((request, Some(Connector::NativeTls(tls))))
+}
+
+fn profiles_path(app: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    Ok(app_data_dir.join(PROFILES_FILE))
+}
+
+fn save_profiles(app: &tauri::AppHandle) -> Result<(), String> {
+    let path = profiles_path(app)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    let profiles: Vec<ConnectionProfile> = COMFYUI_PROFILES.lock().unwrap().values().cloned().collect();
+    let json = serde_json
+        ::to_string_pretty(&profiles)
+        .map_err(|e| format!("Failed to serialize profiles: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write profiles: {}", e))
+}
+
+/// Loads saved connection profiles at startup; a missing or unreadable file leaves the registry empty.
+pub fn load_profiles(app: &tauri::AppHandle) {
+    let Ok(path) = profiles_path(app) else {
+        return;
+    };
+    let Ok(json) = std::fs::read_to_string(&path) else {
+        return;
+    };
+    match serde_json::from_str::<Vec<serde_json::Value>>(&json) {
+        Ok(profiles) => {
+            let mut dropped = false;
+            {
+                let mut registry = COMFYUI_PROFILES.lock().unwrap();
+                for value in profiles {
+                    // Profiles from before auth moved into the credential store carry inline secrets
+                    match serde_json::from_value::<ConnectionProfile>(value) {
+                        Ok(profile) => {
+                            registry.insert(profile_key(&profile.url), profile);
+                        }
+                        Err(e) => {
+                            log::warn!("Dropping connection profile from {}: {}", PROFILES_FILE, e);
+                            dropped = true;
+                        }
+                    }
+                }
+            }
+            if dropped {
+                if let Err(e) = save_profiles(app) {
+                    log::warn!("Failed to rewrite {}: {}", PROFILES_FILE, e);
+                }
+            }
+        }
+        Err(e) => log::warn!("Ignoring unreadable {}: {}", PROFILES_FILE, e),
+    }
+}
+
+#[tauri::command]
+pub async fn comfyui_set_connection_profile(app: tauri::AppHandle, profile: ConnectionProfile) -> Result<(), String> {
+    validate_profile(&profile)?;
+    COMFYUI_PROFILES.lock().unwrap().insert(profile_key(&profile.url), profile);
+    save_profiles(&app)
+}
+
+#[tauri::command]
+pub async fn comfyui_remove_connection_profile(app: tauri::AppHandle, comfy_url: String) -> Result<bool, String> {
+    let removed = COMFYUI_PROFILES.lock().unwrap().remove(&profile_key(&comfy_url)).is_some();
+    if removed {
+        save_profiles(&app)?;
+    }
+    Ok(removed)
+}
+
+#[tauri::command]
+pub async fn comfyui_list_connection_profiles() -> Result<Vec<ConnectionProfile>, String> {
+    let mut profiles: Vec<ConnectionProfile> = COMFYUI_PROFILES.lock().unwrap().values().cloned().collect();
+    profiles.sort_by(|a, b| a.url.cmp(&b.url));
+    Ok(profiles)
+}
+
+#[tauri::command]
+pub async fn comfyui_test_connection(comfy_url: String) -> Result<serde_json::Value, String> {
+    let response = http_client(&comfy_url).await?
+        .get(format!("{}/system_stats", comfy_url.trim_end_matches('/')))
+        .send().await
+        .map_err(|e| format!("Failed to reach ComfyUI: {}", e))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("HTTP {}: {}", status, error_text));
+    }
+    response.json().await.map_err(|e| format!("Failed to parse system stats: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{ StubResponse, StubServer };
+    use std::io::{ Read, Write };
+
+    const CERTIFICATE: &str = include_str!("../tests/fixtures/self_signed.pem");
+    const PRIVATE_KEY: &str = include_str!("../tests/fixtures/self_signed.key");
+
+    fn register(profile: ConnectionProfile) {
+        COMFYUI_PROFILES.lock().unwrap().insert(profile_key(&profile.url), profile);
+    }
+
+    fn profile(url: &str, tls: TlsMode, auth: Option<Auth>) -> ConnectionProfile {
+        ConnectionProfile { tls, auth, ..ConnectionProfile::for_url(url) }
+    }
+
+    fn certificate_fingerprint() -> String {
+        let der = native_tls::Certificate::from_pem(CERTIFICATE.as_bytes()).unwrap().to_der().unwrap();
+        Sha256::digest(der)
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    /// An HTTPS server presenting the self-signed fixture certificate, answering every request with `{"ok":true}`
+    fn tls_stub() -> String {
+        let identity = native_tls::Identity::from_pkcs8(CERTIFICATE.as_bytes(), PRIVATE_KEY.as_bytes()).unwrap();
+        let acceptor = native_tls::TlsAcceptor::new(identity).unwrap();
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("https://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let Ok(mut tls) = acceptor.accept(stream) else {
+                    continue;
+                };
+                let mut head = Vec::new();
+                let mut byte = [0u8; 1];
+                while !head.ends_with(b"\r\n\r\n") && tls.read(&mut byte).unwrap_or(0) == 1 {
+                    head.push(byte[0]);
+                }
+                let body = br#"{"ok":true}"#;
+                let head = format!("HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n", body.len());
+                let _ = tls.write_all(head.as_bytes());
+                let _ = tls.write_all(body);
+            }
+        });
+        url
+    }
+
+    #[test]
+    fn profiles_are_found_by_normalised_url() {
+        register(profile("https://Comfy.Lookup.Test:8188/", TlsMode::Insecure { confirmed: true }, None));
+
+        let found = profile_for(" https://comfy.lookup.test:8188");
+        assert_eq!(found.url, "https://Comfy.Lookup.Test:8188/");
+        assert!(matches!(found.tls, TlsMode::Insecure { confirmed: true }));
+
+        let fallback = profile_for("https://comfy.lookup.test:8189");
+        assert_eq!(fallback.url, "https://comfy.lookup.test:8189");
+        assert!(matches!(fallback.tls, TlsMode::System) && fallback.auth.is_none());
+    }
+
+    #[tokio::test]
+    async fn unconfirmed_insecure_tls_is_refused() {
+        let url = "https://comfy.insecure.test";
+        let unconfirmed = profile(url, TlsMode::Insecure { confirmed: false }, None);
+        let expected = "Insecure TLS for https://comfy.insecure.test must be confirmed explicitly";
+        assert_eq!(validate_profile(&unconfirmed).unwrap_err(), expected);
+
+        // a profile saved before validation existed is still refused when it is used
+        register(unconfirmed);
+        assert_eq!(http_client(url).await.unwrap_err(), expected);
+        assert_eq!(websocket_request(url, "wss://comfy.insecure.test/ws").await.err().unwrap(), expected);
+
+        assert!(validate_profile(&profile(url, TlsMode::Insecure { confirmed: true }, None)).is_ok());
+    }
+
+    #[test]
+    fn malformed_fingerprints_pems_and_urls_are_rejected() {
+        let url = "https://comfy.validate.test";
+        let pinned = |sha256: &str| {
+            validate_profile(&profile(url, TlsMode::Pinned { sha256: sha256.to_string() }, None))
+        };
+        let fingerprint = certificate_fingerprint();
+
+        assert!(pinned(&fingerprint).is_ok());
+        let colons = fingerprint
+            .to_uppercase()
+            .as_bytes()
+            .chunks(2)
+            .map(|pair| String::from_utf8_lossy(pair).to_string())
+            .collect::<Vec<_>>()
+            .join(":");
+        assert!(pinned(&colons).is_ok());
+        assert_eq!(normalize_fingerprint(&colons), fingerprint);
+        for bad in ["", "abc123", &fingerprint[..62], &format!("{}00", fingerprint), &fingerprint.replace('a', "g")] {
+            let error = pinned(bad).unwrap_err();
+            assert_eq!(error, "Pinned fingerprint must be a SHA-256 hash (64 hex digits)", "{}", bad);
+        }
+
+        let custom_ca = |pem: &str| validate_profile(&profile(url, TlsMode::CustomCa { pem: pem.to_string() }, None));
+        assert!(custom_ca(CERTIFICATE).is_ok());
+        assert!(custom_ca("-----BEGIN CERTIFICATE-----\nnot base64\n-----END CERTIFICATE-----").is_err());
+        assert!(custom_ca(PRIVATE_KEY).unwrap_err().starts_with("Invalid CA certificate"));
+
+        assert!(validate_profile(&profile("ftp://comfy.validate.test", TlsMode::System, None)).is_err());
+        assert!(validate_profile(&profile("comfy.validate.test", TlsMode::System, None)).is_err());
+    }
+
+    #[tokio::test]
+    async fn pinned_certificates_must_match_the_fingerprint() {
+        let url = tls_stub();
+        let fingerprint = certificate_fingerprint();
+        let wrong = format!("{}{}", &fingerprint[1..], &fingerprint[..1]);
+
+        register(profile(&url, TlsMode::Pinned { sha256: wrong.clone() }, None));
+        let error = http_client(&url).await.unwrap_err();
+        assert_eq!(error, format!("Certificate fingerprint of {} is {}, expected {}", url, fingerprint, wrong));
+        assert!(!PINNED_CERTIFICATES.lock().unwrap().contains_key(&wrong));
+
+        register(profile(&url, TlsMode::Pinned { sha256: fingerprint.to_uppercase() }, None));
+        assert_eq!(comfyui_test_connection(url.clone()).await.unwrap(), serde_json::json!({ "ok": true }));
+        assert!(PINNED_CERTIFICATES.lock().unwrap().contains_key(&fingerprint));
+
+        let (_, connector) = websocket_request(&url, &format!("{}/ws", url.replacen("https", "wss", 1))).await.unwrap();
+        assert!(matches!(connector, Some(Connector::NativeTls(_))));
+    }
+
+    #[tokio::test]
+    async fn auth_headers_are_built_from_the_credential_store() {
+        let server = StubServer::start(|_| StubResponse::json(200, serde_json::json!({}))).await;
+        credentials::insert_for_tests("comfy-connection-test", &server.url, "s3cret");
+
+        let bearer = Auth::Bearer { credential: "comfy-connection-test".to_string() };
+        register(profile(&server.url, TlsMode::System, Some(bearer)));
+        comfyui_test_connection(server.url.clone()).await.unwrap();
+        let ws_url = format!("{}/ws", server.url.replacen("http", "ws", 1));
+        let (request, connector) = websocket_request(&server.url, &ws_url).await.unwrap();
+        assert_eq!(request.headers()["Authorization"], "Bearer s3cret");
+        assert!(connector.is_none());
+
+        let basic = Auth::Basic {
+            username: "pattern-maker".to_string(),
+            credential: "comfy-connection-test".to_string(),
+        };
+        register(profile(&server.url, TlsMode::System, Some(basic)));
+        comfyui_test_connection(server.url.clone()).await.unwrap();
+
+        let sent: Vec<String> = server
+            .requests_to("GET", "/system_stats")
+            .iter()
+            .map(|r| r.header("authorization").unwrap_or("").to_string())
+            .collect();
+        let encoded = general_purpose::STANDARD.encode("pattern-maker:s3cret");
+        assert_eq!(sent, ["Bearer s3cret".to_string(), format!("Basic {}", encoded)]);
+
+        let elsewhere = "http://comfy.elsewhere.test";
+        let stolen = Auth::Bearer { credential: "comfy-connection-test".to_string() };
+        assert!(validate_profile(&profile(elsewhere, TlsMode::System, Some(stolen.clone()))).is_err());
+        register(profile(elsewhere, TlsMode::System, Some(stolen)));
+        assert!(http_client(elsewhere).await.is_err());
+    }
+}
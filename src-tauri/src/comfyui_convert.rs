@@ -0,0 +1,717 @@
+use crate::comfyui_schema::{ self, NodeSchema };
+use crate::comfyui_validate::id_key;
+use serde::Serialize;
+use serde_json::{ json, Map, Value };
+use std::collections::{ BTreeMap, HashMap };
+
+/// Editor-only node types that never reach the server.
+const VIRTUAL_NODES: [&str; 4] = ["Reroute", "PrimitiveNode", "Note", "MarkdownNote"];
+const MODE_MUTED: u64 = 2;
+const MODE_BYPASS: u64 = 4;
+const CONTROL_VALUES: [&str; 4] = ["fixed", "increment", "decrement", "randomize"];
+
+const NODE_WIDTH: f32 = 315.0;
+const COLUMN_GAP: f32 = 80.0;
+const ROW_GAP: f32 = 60.0;
+const LAYOUT_ORIGIN: f32 = 50.0;
+
+/// Width and height of a node in the editor
+type NodeSize = (f32, f32);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConvertedWorkflow {
+    pub prompt: Map<String, Value>,
+    pub positions: HashMap<String, (f32, f32)>,
+    pub warnings: Vec<String>,
+}
+
+/// Editor exports carry `nodes` and `links` arrays; API prompts are a map of node id to node.
+pub fn is_ui_workflow(workflow: &Value) -> bool {
+    workflow["nodes"].is_array() && workflow.get("links").is_some()
+}
+
+fn node_id(value: &Value) -> Option<String> {
+    match value {
+        Value::Number(n) => Some(n.to_string()),
+        Value::String(s) => Some(s.clone()),
+        _ => None,
+    }
+}
+
+fn ui_links(workflow: &Value) -> HashMap<u64, (String, usize)> {
+    let mut links = HashMap::new();
+    for link in workflow["links"].as_array().into_iter().flatten() {
+        let parsed = match link {
+            Value::Array(parts) =>
+                (parts.first().and_then(Value::as_u64), parts.get(1).and_then(node_id), parts.get(2).and_then(Value::as_u64)),
+            Value::Object(_) =>
+                (link["id"].as_u64(), node_id(&link["origin_id"]), link["origin_slot"].as_u64()),
+            _ => continue,
+        };
+        if let (Some(id), Some(from), Some(slot)) = parsed {
+            links.insert(id, (from, slot as usize));
+        }
+    }
+    links
+}
+
+fn ui_position(node: &Value) -> Option<(f32, f32)> {
+    let pos = &node["pos"];
+    let coordinate = |i: usize| {
+        pos.get(i)
+            .or_else(|| pos.get(i.to_string()))
+            .and_then(Value::as_f64)
+    };
+    Some((coordinate(0)? as f32, coordinate(1)? as f32))
+}
+
+enum LinkSource {
+    Output(String, usize),
+    Value(Value),
+}
+
+/// Follows a link back to the node that produces it, skipping reroutes and bypassed nodes and inlining primitive values.
+/// Links from muted nodes resolve to nothing, as the server never runs them.
+fn resolve_link(link: u64, links: &HashMap<u64, (String, usize)>, nodes: &HashMap<String, &Value>) -> Option<LinkSource> {
+    let mut link = link;
+    for _ in 0..64 {
+        let (from, slot) = links.get(&link)?;
+        let Some(node) = nodes.get(from) else {
+            return Some(LinkSource::Output(from.clone(), *slot));
+        };
+        if node["mode"].as_u64() == Some(MODE_MUTED) {
+            return None;
+        }
+        let inputs = node["inputs"].as_array().cloned().unwrap_or_default();
+        match node["type"].as_str().unwrap_or("") {
+            "Reroute" => {
+                link = inputs.first()?["link"].as_u64()?;
+            }
+            "PrimitiveNode" => {
+                return node["widgets_values"].get(0).cloned().map(LinkSource::Value);
+            }
+            _ if node["mode"].as_u64() == Some(MODE_BYPASS) => {
+                let output_type = node["outputs"][*slot]["type"].as_str().unwrap_or("*");
+                link = inputs
+                    .iter()
+                    .find(|i| i["type"].as_str() == Some(output_type) && i["link"].is_u64())?["link"].as_u64()?;
+            }
+            _ => {
+                return Some(LinkSource::Output(from.clone(), *slot));
+            }
+        }
+    }
+    None
+}
+
+fn widget_inputs(schema: &NodeSchema, widgets: &Value) -> Map<String, Value> {
+    let mut inputs = Map::new();
+    if let Some(named) = widgets.as_object() {
+        for spec in schema.inputs.iter().filter(|i| i.is_widget()) {
+            if let Some(value) = named.get(&spec.name) {
+                inputs.insert(spec.name.clone(), value.clone());
+            }
+        }
+        return inputs;
+    }
+
+    let values = widgets.as_array().cloned().unwrap_or_default();
+    let mut index = 0;
+    for spec in schema.inputs.iter().filter(|i| i.is_widget()) {
+        let Some(value) = values.get(index) else {
+            break;
+        };
+        inputs.insert(spec.name.clone(), value.clone());
+        index += 1;
+        let control = values.get(index).and_then(Value::as_str);
+        if spec.control_after_generate && control.is_some_and(|c| CONTROL_VALUES.contains(&c)) {
+            index += 1;
+        }
+    }
+    inputs
+}
+
+/// Converts an editor export to an API prompt, mapping `widgets_values` to named inputs through the node schemas.
+pub fn ui_to_api(workflow: &Value, schemas: &BTreeMap<String, NodeSchema>) -> Result<ConvertedWorkflow, String> {
+    let ui_nodes = workflow["nodes"].as_array().ok_or("Workflow has no nodes array")?;
+    let links = ui_links(workflow);
+    let nodes: HashMap<String, &Value> = ui_nodes
+        .iter()
+        .filter_map(|n| Some((node_id(&n["id"])?, n)))
+        .collect();
+
+    let mut ids: Vec<&String> = nodes.keys().collect();
+    ids.sort_by_key(|id| id_key(id));
+
+    let mut prompt = Map::new();
+    let mut positions = HashMap::new();
+    let mut warnings = Vec::new();
+    for id in ids {
+        let node = nodes[id];
+        let class_type = node["type"].as_str().unwrap_or("").to_string();
+        let mode = node["mode"].as_u64().unwrap_or(0);
+        if VIRTUAL_NODES.contains(&class_type.as_str()) || mode == MODE_MUTED || mode == MODE_BYPASS {
+            continue;
+        }
+
+        let schema = schemas.get(&class_type);
+        let mut inputs = match schema {
+            Some(schema) => widget_inputs(schema, &node["widgets_values"]),
+            None => {
+                warnings.push(format!("Node {} ({}) is not installed, so its widget values were not mapped", id, class_type));
+                Map::new()
+            }
+        };
+
+        for input in node["inputs"].as_array().into_iter().flatten() {
+            let Some(link) = input["link"].as_u64() else {
+                continue;
+            };
+            let name = input["widget"]["name"]
+                .as_str()
+                .or_else(|| input["name"].as_str())
+                .unwrap_or("")
+                .to_string();
+            match resolve_link(link, &links, &nodes) {
+                Some(LinkSource::Output(from, slot)) => {
+                    inputs.insert(name, json!([from, slot]));
+                }
+                Some(LinkSource::Value(value)) => {
+                    inputs.insert(name, value);
+                }
+                None => warnings.push(format!("Input {} of node {} is not linked to an active node", name, id)),
+            }
+        }
+
+        let title = node["title"]
+            .as_str()
+            .map(str::to_string)
+            .or_else(|| schema.map(|s| s.display_name.clone()))
+            .unwrap_or_else(|| class_type.clone());
+        prompt.insert(
+            id.clone(),
+            json!({
+                "class_type": class_type,
+                "inputs": inputs,
+                "_meta": { "title": title },
+            })
+        );
+        if let Some(position) = ui_position(node) {
+            positions.insert(id.clone(), position);
+        }
+    }
+
+    Ok(ConvertedWorkflow { prompt, positions, warnings })
+}
+
+fn prompt_links(prompt: &Map<String, Value>) -> Vec<(String, String, usize, String)> {
+    let mut links = Vec::new();
+    for (id, node) in prompt {
+        for (name, value) in node["inputs"].as_object().into_iter().flatten() {
+            let (Some(from), Some(slot)) = (value.get(0).and_then(node_id), value.get(1).and_then(Value::as_u64)) else {
+                continue;
+            };
+            if value.as_array().is_some_and(|a| a.len() == 2) {
+                links.push((from, name.clone(), slot as usize, id.clone()));
+            }
+        }
+    }
+    links
+}
+
+/// Places nodes in columns by their depth in the graph, ordering each column by where its inputs come from.
+pub fn auto_layout(sizes: &[(String, NodeSize)], edges: &[(String, String)]) -> HashMap<String, (f32, f32)> {
+    let mut depth: HashMap<&str, usize> = sizes.iter().map(|(id, _)| (id.as_str(), 0)).collect();
+    for _ in 0..sizes.len() {
+        let mut changed = false;
+        for (from, to) in edges {
+            let (Some(&d), Some(&current)) = (depth.get(from.as_str()), depth.get(to.as_str())) else {
+                continue;
+            };
+            if d + 1 > current && d + 1 < sizes.len() {
+                depth.insert(to.as_str(), d + 1);
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let mut columns: BTreeMap<usize, Vec<(&str, NodeSize)>> = BTreeMap::new();
+    for (id, size) in sizes {
+        columns.entry(depth[id.as_str()]).or_default().push((id.as_str(), *size));
+    }
+
+    let mut positions: HashMap<String, (f32, f32)> = HashMap::new();
+    let mut x = LAYOUT_ORIGIN;
+    for column in columns.values_mut() {
+        let anchor = |id: &str| {
+            let parents: Vec<f32> = edges
+                .iter()
+                .filter(|(_, to)| to == id)
+                .filter_map(|(from, _)| positions.get(from).map(|p| p.1))
+                .collect();
+            if parents.is_empty() { f32::MAX } else { parents.iter().sum::<f32>() / (parents.len() as f32) }
+        };
+        column.sort_by(|a, b| anchor(a.0).total_cmp(&anchor(b.0)).then_with(|| id_key(a.0).cmp(&id_key(b.0))));
+
+        let mut y = LAYOUT_ORIGIN;
+        let mut width: f32 = 0.0;
+        for (id, (w, h)) in column.iter() {
+            positions.insert(id.to_string(), (x, y));
+            y += h + ROW_GAP;
+            width = width.max(*w);
+        }
+        x += width + COLUMN_GAP;
+    }
+    positions
+}
+
+/// Converts an API prompt to an editor workflow with generated links, widget values and an automatic layout.
+pub fn api_to_ui(prompt: &Map<String, Value>, schemas: &BTreeMap<String, NodeSchema>) -> Result<Value, String> {
+    let mut ids: Vec<&String> = prompt.keys().collect();
+    ids.sort_by_key(|id| id_key(id));
+
+    let mut next_id = ids
+        .iter()
+        .filter_map(|id| id.parse::<u64>().ok())
+        .max()
+        .unwrap_or(0);
+    let mut ui_ids: HashMap<&str, u64> = HashMap::new();
+    for id in &ids {
+        let ui_id = match id.parse::<u64>() {
+            Ok(n) => n,
+            Err(_) => {
+                next_id += 1;
+                next_id
+            }
+        };
+        ui_ids.insert(id.as_str(), ui_id);
+    }
+
+    let links = prompt_links(prompt);
+    let output_type = |from: &str, slot: usize| -> String {
+        prompt
+            .get(from)
+            .and_then(|n| schemas.get(n["class_type"].as_str().unwrap_or("")))
+            .and_then(|s| s.outputs.get(slot))
+            .map(|o| o.output_type.clone())
+            .unwrap_or_else(|| "*".to_string())
+    };
+
+    let mut ui_links = Vec::new();
+    let mut outgoing: HashMap<(&str, usize), Vec<u64>> = HashMap::new();
+    let mut incoming: HashMap<(&str, &str), u64> = HashMap::new();
+    for (index, (from, input, slot, to)) in links.iter().enumerate() {
+        let link_id = (index + 1) as u64;
+        outgoing.entry((from.as_str(), *slot)).or_default().push(link_id);
+        incoming.insert((to.as_str(), input.as_str()), link_id);
+        ui_links.push((link_id, from, *slot, to, input));
+    }
+
+    let mut target_slots: HashMap<u64, usize> = HashMap::new();
+    let mut ui_nodes = Vec::new();
+    let mut sizes = Vec::new();
+    for id in &ids {
+        let node = &prompt[id.as_str()];
+        let class_type = node["class_type"].as_str().ok_or(format!("Missing class_type for node {}", id))?;
+        let values = node["inputs"].as_object().cloned().unwrap_or_default();
+        let schema = schemas.get(class_type);
+
+        let mut inputs = Vec::new();
+        let mut widgets = Vec::new();
+        let mut extra_height = 0.0;
+        let mut add_slot = |name: &str, slot_type: &str, widget: bool, inputs: &mut Vec<Value>| {
+            let link = incoming.get(&(id.as_str(), name)).copied();
+            if let Some(link) = link {
+                target_slots.insert(link, inputs.len());
+            }
+            let mut slot = json!({ "name": name, "type": slot_type, "link": link });
+            if widget {
+                slot["widget"] = json!({ "name": name });
+            }
+            inputs.push(slot);
+        };
+
+        match schema {
+            Some(schema) => {
+                for spec in &schema.inputs {
+                    let linked = incoming.contains_key(&(id.as_str(), spec.name.as_str()));
+                    if !spec.is_widget() {
+                        add_slot(&spec.name, &spec.input_type, false, &mut inputs);
+                        continue;
+                    }
+                    if linked {
+                        add_slot(&spec.name, &spec.input_type, true, &mut inputs);
+                    }
+                    let value = values
+                        .get(&spec.name)
+                        .filter(|_| !linked)
+                        .or(spec.default.as_ref())
+                        .cloned()
+                        .unwrap_or(Value::Null);
+                    widgets.push(value);
+                    if spec.control_after_generate {
+                        widgets.push(json!("fixed"));
+                    }
+                    if spec.multiline == Some(true) {
+                        extra_height += 100.0;
+                    }
+                }
+                for name in values.keys().filter(|name| schema.input(name).is_none()) {
+                    if incoming.contains_key(&(id.as_str(), name.as_str())) {
+                        add_slot(name, "*", false, &mut inputs);
+                    }
+                }
+            }
+            None => {
+                for (name, value) in &values {
+                    if incoming.contains_key(&(id.as_str(), name.as_str())) {
+                        add_slot(name, "*", false, &mut inputs);
+                    } else {
+                        widgets.push(value.clone());
+                    }
+                }
+            }
+        }
+
+        let outputs: Vec<Value> = match schema {
+            Some(schema) =>
+                schema.outputs
+                    .iter()
+                    .enumerate()
+                    .map(|(slot, output)| {
+                        json!({
+                            "name": output.name,
+                            "type": output.output_type,
+                            "links": outgoing.get(&(id.as_str(), slot)).cloned().unwrap_or_default(),
+                            "slot_index": slot,
+                        })
+                    })
+                    .collect(),
+            None => {
+                let count = links
+                    .iter()
+                    .filter(|(from, ..)| from == *id)
+                    .map(|(_, _, slot, _)| slot + 1)
+                    .max()
+                    .unwrap_or(0);
+                (0..count)
+                    .map(|slot| {
+                        json!({
+                            "name": format!("output_{}", slot),
+                            "type": "*",
+                            "links": outgoing.get(&(id.as_str(), slot)).cloned().unwrap_or_default(),
+                            "slot_index": slot,
+                        })
+                    })
+                    .collect()
+            }
+        };
+
+        let height = 30.0 + 22.0 * (inputs.len().max(outputs.len()) as f32) + 26.0 * (widgets.len() as f32) + extra_height;
+        sizes.push((id.to_string(), (NODE_WIDTH, height)));
+        let title = node["_meta"]["title"].as_str().unwrap_or(class_type);
+        ui_nodes.push(
+            json!({
+                "id": ui_ids[id.as_str()],
+                "type": class_type,
+                "title": title,
+                "size": [NODE_WIDTH, height],
+                "flags": {},
+                "mode": 0,
+                "inputs": inputs,
+                "outputs": outputs,
+                "properties": { "Node name for S&R": class_type },
+                "widgets_values": widgets,
+            })
+        );
+    }
+
+    let edges: Vec<(String, String)> = links
+        .iter()
+        .map(|(from, _, _, to)| (from.clone(), to.clone()))
+        .collect();
+    let positions = auto_layout(&sizes, &edges);
+    let mut order: Vec<(f32, f32, usize)> = Vec::new();
+    for (index, (id, _)) in sizes.iter().enumerate() {
+        let (x, y) = positions[id];
+        ui_nodes[index]["pos"] = json!([x, y]);
+        order.push((x, y, index));
+    }
+    order.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+    for (position, (_, _, index)) in order.into_iter().enumerate() {
+        ui_nodes[index]["order"] = json!(position);
+    }
+
+    let ui_links: Vec<Value> = ui_links
+        .into_iter()
+        .map(|(link_id, from, slot, to, _)| {
+            json!([
+                link_id,
+                ui_ids.get(from.as_str()).copied().unwrap_or(0),
+                slot,
+                ui_ids[to.as_str()],
+                target_slots.get(&link_id).copied().unwrap_or(0),
+                output_type(from, slot),
+            ])
+        })
+        .collect();
+
+    Ok(
+        json!({
+            "last_node_id": ui_ids.values().max().copied().unwrap_or(0),
+            "last_link_id": ui_links.len(),
+            "nodes": ui_nodes,
+            "links": ui_links,
+            "groups": [],
+            "config": {},
+            "extra": {},
+            "version": 0.4,
+        })
+    )
+}
+
+#[tauri::command]
+pub async fn comfyui_ui_to_api(comfy_url: String, workflow_json: String) -> Result<ConvertedWorkflow, String> {
+    let workflow: Value = serde_json
+        ::from_str(&workflow_json)
+        .map_err(|e| format!("Failed to parse workflow JSON: {}", e))?;
+    if !is_ui_workflow(&workflow) {
+        return Err("Workflow is not in the ComfyUI editor format".to_string());
+    }
+    let schemas = comfyui_schema::object_info(&comfy_url, false).await?;
+    ui_to_api(&workflow, &schemas)
+}
+
+#[tauri::command]
+pub async fn comfyui_api_to_ui(comfy_url: String, workflow_json: String) -> Result<Value, String> {
+    let prompt: Map<String, Value> = serde_json
+        ::from_str(&workflow_json)
+        .map_err(|e| format!("Failed to parse workflow JSON: {}", e))?;
+    let schemas = comfyui_schema::object_info(&comfy_url, false).await?;
+    api_to_ui(&prompt, &schemas)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    fn input(name: &str, input_type: &str, link: Option<u64>) -> Value {
+        json!({ "name": name, "type": input_type, "link": link })
+    }
+
+    /// A text-to-image graph whose model goes through a reroute, whose CLIP goes through a bypassed LoRA, whose
+    /// seed comes from a primitive and whose latent comes from a muted node
+    fn editor_workflow() -> Value {
+        json!({
+            "nodes": [
+                { "id": 1, "type": "CheckpointLoaderSimple", "mode": 0, "pos": [0, 0], "widgets_values": ["sd_xl_base_1.0.safetensors"] },
+                {
+                    "id": 2, "type": "CLIPTextEncode", "mode": 0, "pos": [400, 0], "title": "Positive",
+                    "inputs": [input("clip", "CLIP", Some(3))], "widgets_values": ["a denim jacket"],
+                },
+                {
+                    "id": 3, "type": "CLIPTextEncode", "mode": 0, "pos": [400, 200],
+                    "inputs": [input("clip", "CLIP", Some(4))], "widgets_values": ["blurry"],
+                },
+                { "id": 4, "type": "EmptyLatentImage", "mode": MODE_MUTED, "pos": [400, 400], "widgets_values": [1024, 1024, 1] },
+                {
+                    "id": 5, "type": "KSampler", "mode": 0, "pos": [800, 0],
+                    "inputs": [
+                        input("model", "MODEL", Some(1)),
+                        input("positive", "CONDITIONING", Some(5)),
+                        input("negative", "CONDITIONING", Some(6)),
+                        input("latent_image", "LATENT", Some(7)),
+                        { "name": "seed", "type": "INT", "link": 8, "widget": { "name": "seed" } },
+                    ],
+                    "widgets_values": [0, "randomize", 30, 6.5, "dpmpp_2m", "karras", 1.0],
+                },
+                {
+                    "id": 6, "type": "VAEDecode", "mode": 0, "pos": [1200, 0],
+                    "inputs": [input("samples", "LATENT", Some(11)), input("vae", "VAE", Some(12))],
+                },
+                { "id": 7, "type": "Note", "mode": 0, "pos": [0, 400], "widgets_values": ["remember the hem"] },
+                { "id": 10, "type": "Reroute", "mode": 0, "pos": [600, 0], "inputs": [input("", "*", Some(2))] },
+                { "id": 11, "type": "PrimitiveNode", "mode": 0, "pos": [600, 100], "widgets_values": [123456, "fixed"] },
+                {
+                    "id": 12, "type": "LoraLoader", "mode": MODE_BYPASS, "pos": [200, 0],
+                    "inputs": [input("model", "MODEL", Some(9)), input("clip", "CLIP", Some(10))],
+                    "outputs": [{ "name": "MODEL", "type": "MODEL" }, { "name": "CLIP", "type": "CLIP" }],
+                    "widgets_values": ["garment_folds.safetensors", 1.0, 1.0],
+                },
+            ],
+            "links": [
+                [1, 10, 0, 5, 0, "MODEL"],
+                [2, 1, 0, 10, 0, "MODEL"],
+                [3, 12, 1, 2, 0, "CLIP"],
+                [4, 12, 1, 3, 0, "CLIP"],
+                [5, 2, 0, 5, 1, "CONDITIONING"],
+                [6, 3, 0, 5, 2, "CONDITIONING"],
+                [7, 4, 0, 5, 3, "LATENT"],
+                [8, 11, 0, 5, 4, "INT"],
+                [9, 1, 0, 12, 0, "MODEL"],
+                [10, 1, 1, 12, 1, "CLIP"],
+                [11, 5, 0, 6, 0, "LATENT"],
+                [12, 1, 2, 6, 1, "VAE"],
+            ],
+        })
+    }
+
+    fn api_prompt() -> Map<String, Value> {
+        let prompt = json!({
+            "3": {
+                "class_type": "KSampler",
+                "inputs": {
+                    "model": ["4", 0], "seed": 156680208700286u64, "steps": 20, "cfg": 8, "sampler_name": "euler",
+                    "scheduler": "normal", "positive": ["6", 0], "negative": ["7", 0], "latent_image": ["5", 0], "denoise": 1,
+                },
+                "_meta": { "title": "KSampler" },
+            },
+            "4": {
+                "class_type": "CheckpointLoaderSimple",
+                "inputs": { "ckpt_name": "v1-5-pruned-emaonly.safetensors" },
+                "_meta": { "title": "Load Checkpoint" },
+            },
+            "5": {
+                "class_type": "EmptyLatentImage",
+                "inputs": { "width": 512, "height": 512, "batch_size": 1 },
+                "_meta": { "title": "Empty Latent Image" },
+            },
+            "6": {
+                "class_type": "CLIPTextEncode",
+                "inputs": { "text": "a tailored wool coat", "clip": ["4", 1] },
+                "_meta": { "title": "Positive" },
+            },
+            "7": {
+                "class_type": "CLIPTextEncode",
+                "inputs": { "text": "text, watermark", "clip": ["4", 1] },
+                "_meta": { "title": "Negative" },
+            },
+            "8": {
+                "class_type": "VAEDecode",
+                "inputs": { "samples": ["3", 0], "vae": ["4", 2] },
+                "_meta": { "title": "VAE Decode" },
+            },
+            "9": {
+                "class_type": "SaveImage",
+                "inputs": { "filename_prefix": "ComfyUI", "images": ["8", 0] },
+                "_meta": { "title": "Save Image" },
+            },
+        });
+        prompt.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn widget_values_are_mapped_to_named_inputs() {
+        let schemas = test_support::schemas();
+        let sampler = &schemas["KSampler"];
+
+        let with_control = widget_inputs(sampler, &json!([7, "randomize", 30, 6.5, "dpmpp_2m", "karras", 0.75]));
+        let without_control = widget_inputs(sampler, &json!([7, 30, 6.5, "dpmpp_2m", "karras", 0.75]));
+        let expected = json!({
+            "seed": 7, "steps": 30, "cfg": 6.5, "sampler_name": "dpmpp_2m", "scheduler": "karras", "denoise": 0.75,
+        });
+        assert_eq!(Value::Object(with_control), expected);
+        assert_eq!(Value::Object(without_control), expected);
+
+        let named = widget_inputs(&schemas["EmptyLatentImage"], &json!({ "width": 768, "batch_size": 2, "model": "x" }));
+        assert_eq!(Value::Object(named), json!({ "width": 768, "batch_size": 2 }));
+        let short = widget_inputs(&schemas["EmptyLatentImage"], &json!([640]));
+        assert_eq!(Value::Object(short), json!({ "width": 640 }));
+    }
+
+    #[test]
+    fn reroutes_primitives_and_bypassed_nodes_are_resolved() {
+        let converted = ui_to_api(&editor_workflow(), &test_support::schemas()).unwrap();
+        let prompt = &converted.prompt;
+
+        let mut ids: Vec<&str> = prompt.keys().map(String::as_str).collect();
+        ids.sort();
+        assert_eq!(ids, ["1", "2", "3", "5", "6"], "virtual, muted and bypassed nodes are not sent");
+        assert_eq!(
+            prompt["5"]["inputs"],
+            json!({
+                "model": ["1", 0], "positive": ["2", 0], "negative": ["3", 0], "seed": 123456, "steps": 30, "cfg": 6.5,
+                "sampler_name": "dpmpp_2m", "scheduler": "karras", "denoise": 1.0,
+            })
+        );
+        assert_eq!(prompt["2"]["inputs"], json!({ "text": "a denim jacket", "clip": ["1", 1] }));
+        assert_eq!(prompt["3"]["inputs"]["clip"], json!(["1", 1]));
+        assert_eq!(prompt["6"]["inputs"], json!({ "samples": ["5", 0], "vae": ["1", 2] }));
+        assert_eq!(prompt["1"]["inputs"], json!({ "ckpt_name": "sd_xl_base_1.0.safetensors" }));
+        assert_eq!(prompt["2"]["_meta"]["title"], "Positive");
+        assert_eq!(prompt["3"]["_meta"]["title"], "CLIP Text Encode (Prompt)");
+        assert_eq!(converted.positions["5"], (800.0, 0.0));
+    }
+
+    #[test]
+    fn inputs_from_muted_nodes_are_dropped_with_a_warning() {
+        let mut workflow = editor_workflow();
+        // mute the checkpoint behind the bypassed LoRA as well, so both text encoders lose their CLIP
+        workflow["nodes"][0]["mode"] = json!(MODE_MUTED);
+        let converted = ui_to_api(&workflow, &test_support::schemas()).unwrap();
+
+        assert!(converted.prompt["5"]["inputs"].get("latent_image").is_none());
+        assert!(converted.prompt["2"]["inputs"].get("clip").is_none());
+        assert!(!converted.prompt.contains_key("1"));
+        assert_eq!(converted.warnings, [
+            "Input clip of node 2 is not linked to an active node",
+            "Input clip of node 3 is not linked to an active node",
+            "Input model of node 5 is not linked to an active node",
+            "Input latent_image of node 5 is not linked to an active node",
+            "Input vae of node 6 is not linked to an active node",
+        ]);
+    }
+
+    #[test]
+    fn api_prompts_survive_a_round_trip_through_the_editor_format() {
+        let schemas = test_support::schemas();
+        let prompt = api_prompt();
+
+        let ui = api_to_ui(&prompt, &schemas).unwrap();
+        assert!(is_ui_workflow(&ui));
+        assert_eq!(ui["links"].as_array().unwrap().len(), 9);
+        let sampler = ui["nodes"].as_array().unwrap().iter().find(|n| n["id"] == 3).unwrap();
+        assert_eq!(sampler["widgets_values"], json!([156680208700286u64, "fixed", 20, 8, "euler", "normal", 1]));
+
+        let converted = ui_to_api(&ui, &schemas).unwrap();
+        assert!(converted.warnings.is_empty(), "{:?}", converted.warnings);
+        assert_eq!(converted.prompt, prompt);
+    }
+
+    #[test]
+    fn auto_layout_gives_every_node_its_own_place() {
+        let ui = api_to_ui(&api_prompt(), &test_support::schemas()).unwrap();
+        let nodes = ui["nodes"].as_array().unwrap();
+        let place = |id: u64| -> (f32, f32, f32) {
+            let node = nodes.iter().find(|n| n["id"] == id).unwrap();
+            let coordinate = |value: &Value| value.as_f64().unwrap() as f32;
+            (coordinate(&node["pos"][0]), coordinate(&node["pos"][1]), coordinate(&node["size"][1]))
+        };
+
+        let mut positions: Vec<(f32, f32)> = nodes
+            .iter()
+            .map(|n| place(n["id"].as_u64().unwrap()))
+            .map(|(x, y, _)| (x, y))
+            .collect();
+        positions.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+        positions.dedup();
+        assert_eq!(positions.len(), nodes.len());
+
+        // checkpoint → text encoders → sampler → decode → save, one column each
+        let columns: Vec<f32> = [4, 6, 3, 8, 9].iter().map(|id| place(*id).0).collect();
+        assert!(columns.windows(2).all(|w| w[0] < w[1]), "{:?}", columns);
+        let (x4, y4, h4) = place(4);
+        let (x5, y5, _) = place(5);
+        assert_eq!((x4, y4), (LAYOUT_ORIGIN, LAYOUT_ORIGIN));
+        assert_eq!((x5, y5), (LAYOUT_ORIGIN, LAYOUT_ORIGIN + h4 + ROW_GAP));
+        let (x6, y6, h6) = place(6);
+        assert_eq!(place(7), (x6, y6 + h6 + ROW_GAP, place(7).2));
+
+        let orders: Vec<u64> = nodes.iter().map(|n| n["order"].as_u64().unwrap()).collect();
+        assert_eq!(orders.iter().copied().collect::<std::collections::BTreeSet<_>>().len(), nodes.len());
+    }
+}
@@ -0,0 +1,726 @@
+use crate::comfyui_connection;
+use crate::generation::{ image_mime_type, GeneratedImage, GenerationProvider, GenerationRequest, JobState, JobStatus };
+use base64::{ engine::general_purpose, Engine as _ };
+use futures_util::future::BoxFuture;
+use futures_util::StreamExt;
+use once_cell::sync::Lazy;
+use serde::{ Deserialize, Serialize };
+use serde_json::Value;
+use std::collections::{ HashMap, HashSet };
+use std::sync::{ Arc, Mutex };
+use tauri::Emitter;
+use tokio::net::TcpStream;
+use tokio::sync::Notify;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{ MaybeTlsStream, WebSocketStream };
+
+pub const COMFYUI_JOB_EVENT: &str = "comfyui-job";
+
+const PREVIEW_IMAGE: u32 = 1;
+const PREVIEW_IMAGE_WITH_METADATA: u32 = 4;
+
+/// Cancellation handles of running job listeners, by prompt id
+type JobRegistry = Arc<Mutex<HashMap<String, Arc<Notify>>>>;
+
+static COMFYUI_JOBS: Lazy<JobRegistry> = Lazy::new(||
+    Arc::new(Mutex::new(HashMap::new()))
+);
+
+/// Progress of the running node of each listened-to prompt, between 0 and 1
+static COMFYUI_PROGRESS: Lazy<Mutex<HashMap<String, f32>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+type JobSocket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Where a listener's events go; the app forwards them to the webview as `comfyui-job` events.
+type EventSink = Arc<dyn Fn(ComfyJobEvent) + Send + Sync>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComfyJob {
+    #[serde(rename = "promptId")]
+    pub prompt_id: String,
+    #[serde(rename = "clientId")]
+    pub client_id: String,
+    pub number: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", rename_all_fields = "camelCase")]
+pub enum ComfyJobEvent {
+    Queued {
+        prompt_id: String,
+        position: Option<usize>,
+        queue_remaining: Option<u64>,
+    },
+    ExecutionStart {
+        prompt_id: String,
+    },
+    Cached {
+        prompt_id: String,
+        nodes: Vec<String>,
+    },
+    Executing {
+        prompt_id: String,
+        node: String,
+    },
+    Progress {
+        prompt_id: String,
+        node: Option<String>,
+        value: u64,
+        max: u64,
+    },
+    Preview {
+        prompt_id: String,
+        node: Option<String>,
+        mime: String,
+        data: String,
+    },
+    Executed {
+        prompt_id: String,
+        node: String,
+        output: Value,
+    },
+    ExecutionError {
+        prompt_id: String,
+        node: Option<String>,
+        node_type: Option<String>,
+        message: String,
+        details: Value,
+    },
+    Interrupted {
+        prompt_id: String,
+    },
+    Cancelled {
+        prompt_id: String,
+    },
+    Completed {
+        prompt_id: String,
+    },
+    Disconnected {
+        prompt_id: String,
+        reason: String,
+    },
+}
+
+fn websocket_url(comfy_url: &str, client_id: &str) -> Result<String, String> {
+    let base = comfy_url.trim_end_matches('/');
+    let base = if let Some(rest) = base.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = base.strip_prefix("http://") {
+        format!("ws://{}", rest)
+    } else {
+        return Err(format!("Unsupported ComfyUI URL: {}", comfy_url));
+    };
+    Ok(format!("{}/ws?clientId={}", base, client_id))
+}
+
+async fn post_json(comfy_url: &str, path: &str, body: &Value) -> Result<Value, String> {
+    let url = format!("{}{}", comfy_url.trim_end_matches('/'), path);
+    let response = comfyui_connection::http_client(comfy_url).await?
+        .post(&url)
+        .json(body)
+        .send().await
+        .map_err(|e| format!("Failed to reach ComfyUI: {}", e))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("HTTP {}: {}", status, error_text));
+    }
+    let text = response.text().await.map_err(|e| format!("Failed to read response: {}", e))?;
+    Ok(serde_json::from_str(&text).unwrap_or(Value::Null))
+}
+
+async fn get_queue(comfy_url: &str) -> Result<Value, String> {
+    let url = format!("{}/queue", comfy_url.trim_end_matches('/'));
+    comfyui_connection::http_client(comfy_url).await?
+        .get(&url)
+        .send().await
+        .map_err(|e| format!("Failed to get queue: {}", e))?
+        .json().await
+        .map_err(|e| format!("Failed to parse queue: {}", e))
+}
+
+fn queue_contains(entries: &Value, prompt_id: &str) -> bool {
+    entries
+        .as_array()
+        .is_some_and(|entries| entries.iter().any(|e| e.get(1).and_then(Value::as_str) == Some(prompt_id)))
+}
+
+/// Position of a prompt in the ComfyUI queue, 0 meaning it is running.
+fn queue_position(queue: &Value, prompt_id: &str) -> Option<usize> {
+    if queue_contains(&queue["queue_running"], prompt_id) {
+        return Some(0);
+    }
+    let mut pending: Vec<(f64, &str)> = queue["queue_pending"]
+        .as_array()?
+        .iter()
+        .filter_map(|e| Some((e.get(0)?.as_f64()?, e.get(1)?.as_str()?)))
+        .collect();
+    pending.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let running = queue["queue_running"].as_array().map_or(0, |r| r.len());
+    pending
+        .iter()
+        .position(|(_, id)| *id == prompt_id)
+        .map(|p| p + running.max(1))
+}
+
+struct JobListener {
+    sink: EventSink,
+    comfy_url: String,
+    prompt_id: String,
+    executing: bool,
+    /// `queue_remaining` from the last status message
+    queue_remaining: Option<u64>,
+    /// Position as of the last queue lookup
+    position: Option<usize>,
+}
+
+impl JobListener {
+    fn new(sink: EventSink, comfy_url: String, prompt_id: String) -> Self {
+        JobListener { sink, comfy_url, prompt_id, executing: false, queue_remaining: None, position: None }
+    }
+
+    fn emit(&self, event: ComfyJobEvent) {
+        (self.sink)(event);
+    }
+
+    async fn refresh_queue_position(&mut self) {
+        self.position = match get_queue(&self.comfy_url).await {
+            Ok(queue) => queue_position(&queue, &self.prompt_id),
+            Err(_) => None,
+        };
+    }
+
+    /// Reports the queue position, looking it up again only when the queue has shrunk since the last status: new
+    /// prompts join behind this one, so nothing else can move it forward.
+    async fn emit_queue_position(&mut self, queue_remaining: Option<u64>) {
+        let shrunk = matches!((self.queue_remaining, queue_remaining), (Some(last), Some(now)) if now < last);
+        if shrunk && self.position != Some(0) {
+            self.refresh_queue_position().await;
+        }
+        if queue_remaining.is_some() {
+            self.queue_remaining = queue_remaining;
+        }
+        self.emit(ComfyJobEvent::Queued { prompt_id: self.prompt_id.clone(), position: self.position, queue_remaining });
+    }
+
+    /// Handles a JSON message from the socket, returning `true` once the job has finished.
+    async fn handle_text(&mut self, text: &str) -> bool {
+        let Ok(message) = serde_json::from_str::<Value>(text) else {
+            return false;
+        };
+        let data = &message["data"];
+        let message_prompt = data["prompt_id"].as_str();
+        let prompt_id = self.prompt_id.clone();
+        let ours = message_prompt == Some(prompt_id.as_str()) || (message_prompt.is_none() && self.executing);
+        let node = data["node"].as_str().map(str::to_string);
+
+        match message["type"].as_str().unwrap_or("") {
+            "status" if !self.executing => {
+                let remaining = data["status"]["exec_info"]["queue_remaining"].as_u64();
+                self.emit_queue_position(remaining).await;
+            }
+            "execution_start" if ours => {
+                self.executing = true;
+                self.emit(ComfyJobEvent::ExecutionStart { prompt_id });
+            }
+            "execution_cached" if ours => {
+                let nodes = data["nodes"]
+                    .as_array()
+                    .map(|n| n.iter().filter_map(|v| v.as_str().map(str::to_string)).collect())
+                    .unwrap_or_default();
+                self.emit(ComfyJobEvent::Cached { prompt_id, nodes });
+            }
+            "executing" if ours => {
+                match node {
+                    Some(node) => {
+                        self.executing = true;
+                        self.emit(ComfyJobEvent::Executing { prompt_id, node });
+                    }
+                    None => {
+                        self.emit(ComfyJobEvent::Completed { prompt_id });
+                        return true;
+                    }
+                }
+            }
+            "progress" if ours => {
+                let value = data["value"].as_u64().unwrap_or(0);
+                let max = data["max"].as_u64().unwrap_or(0);
+                if max > 0 {
+                    COMFYUI_PROGRESS.lock().unwrap().insert(prompt_id.clone(), ((value as f32) / (max as f32)).min(1.0));
+                }
+                self.emit(ComfyJobEvent::Progress { prompt_id, node, value, max });
+            }
+            "executed" if ours => {
+                self.emit(ComfyJobEvent::Executed {
+                    prompt_id,
+                    node: node.unwrap_or_default(),
+                    output: data["output"].clone(),
+                });
+            }
+            "execution_success" if ours => {
+                self.emit(ComfyJobEvent::Completed { prompt_id });
+                return true;
+            }
+            "execution_error" if ours => {
+                self.emit(ComfyJobEvent::ExecutionError {
+                    prompt_id,
+                    node: data["node_id"].as_str().map(str::to_string),
+                    node_type: data["node_type"].as_str().map(str::to_string),
+                    message: data["exception_message"].as_str().unwrap_or("Execution failed").trim().to_string(),
+                    details: data.clone(),
+                });
+                return true;
+            }
+            "execution_interrupted" if ours => {
+                self.emit(ComfyJobEvent::Interrupted { prompt_id });
+                return true;
+            }
+            _ => {}
+        }
+        false
+    }
+
+    fn handle_binary(&self, bytes: &[u8]) {
+        if bytes.len() < 8 {
+            return;
+        }
+        let event = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        let (node, prompt, image, mime) = match event {
+            PREVIEW_IMAGE if self.executing => {
+                let format = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+                let mime = if format == 2 { "image/png" } else { "image/jpeg" };
+                (None, None, &bytes[8..], mime.to_string())
+            }
+            PREVIEW_IMAGE_WITH_METADATA => {
+                let length = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]) as usize;
+                let Some(metadata) = bytes.get(8..8 + length) else {
+                    return;
+                };
+                let metadata: Value = serde_json::from_slice(metadata).unwrap_or(Value::Null);
+                (
+                    metadata["node_id"].as_str().map(str::to_string),
+                    metadata["prompt_id"].as_str().map(str::to_string),
+                    &bytes[8 + length..],
+                    metadata["image_type"].as_str().unwrap_or("image/png").to_string(),
+                )
+            }
+            _ => {
+                return;
+            }
+        };
+        if prompt.as_ref().is_some_and(|p| *p != self.prompt_id) || (prompt.is_none() && !self.executing) {
+            return;
+        }
+        self.emit(ComfyJobEvent::Preview {
+            prompt_id: self.prompt_id.clone(),
+            node,
+            mime,
+            data: general_purpose::STANDARD.encode(image),
+        });
+    }
+}
+
+async fn get_json(comfy_url: &str, path: &str) -> Result<Value, String> {
+    let url = format!("{}{}", comfy_url.trim_end_matches('/'), path);
+    let response = comfyui_connection::http_client(comfy_url).await?
+        .get(&url)
+        .send().await
+        .map_err(|e| format!("Failed to reach ComfyUI: {}", e))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("HTTP {}: {}", status, error_text));
+    }
+    response.json().await.map_err(|e| format!("Failed to parse response: {}", e))
+}
+
+/// A ComfyUI server as a generation provider; the request input is an API-format prompt and jobs are prompt ids.
+pub struct ComfyUIProvider {
+    comfy_url: String,
+    canceled: Mutex<HashSet<String>>,
+}
+
+impl ComfyUIProvider {
+    pub fn new(comfy_url: String) -> Self {
+        ComfyUIProvider { comfy_url, canceled: Mutex::new(HashSet::new()) }
+    }
+}
+
+impl GenerationProvider for ComfyUIProvider {
+    fn submit(&self, request: GenerationRequest) -> BoxFuture<'_, Result<String, String>> {
+        Box::pin(async move {
+            let client_id = uuid::Uuid::new_v4().to_string();
+            let socket = connect(&self.comfy_url, &client_id).await?;
+            let job = queue_prompt(&self.comfy_url, &request.input, client_id).await?;
+            spawn_listener(socket, self.comfy_url.clone(), job.prompt_id.clone(), Arc::new(|_| {}));
+            Ok(job.prompt_id)
+        })
+    }
+
+    fn status<'a>(&'a self, job_id: &'a str) -> BoxFuture<'a, Result<JobStatus, String>> {
+        Box::pin(async move {
+            let history = get_json(&self.comfy_url, &format!("/history/{}", job_id)).await?;
+            let entry = &history[job_id];
+            if entry.is_object() {
+                let state = match entry["status"]["status_str"].as_str() {
+                    Some("error") => JobState::Failed,
+                    _ if self.canceled.lock().unwrap().contains(job_id) => JobState::Canceled,
+                    _ => JobState::Succeeded,
+                };
+                let mut status = JobStatus::new(job_id, state);
+                if state == JobState::Succeeded {
+                    status.progress = Some(1.0);
+                }
+                status.error = entry["status"]["messages"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .find(|m| m[0].as_str() == Some("execution_error"))
+                    .and_then(|m| m[1]["exception_message"].as_str())
+                    .map(|m| m.trim().to_string());
+                return Ok(status);
+            }
+
+            let queue = get_queue(&self.comfy_url).await?;
+            match queue_position(&queue, job_id) {
+                Some(0) => {
+                    let mut status = JobStatus::new(job_id, JobState::Running);
+                    status.progress = COMFYUI_PROGRESS.lock().unwrap().get(job_id).copied();
+                    Ok(status)
+                }
+                Some(position) => {
+                    let mut status = JobStatus::new(job_id, JobState::Queued);
+                    status.message = Some(format!("Position {} in queue", position));
+                    Ok(status)
+                }
+                None if self.canceled.lock().unwrap().contains(job_id) => Ok(JobStatus::new(job_id, JobState::Canceled)),
+                None => Err(format!("Unknown prompt {}", job_id)),
+            }
+        })
+    }
+
+    fn cancel<'a>(&'a self, job_id: &'a str) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(async move {
+            self.canceled.lock().unwrap().insert(job_id.to_string());
+            comfyui_cancel_job(self.comfy_url.clone(), job_id.to_string()).await
+        })
+    }
+
+    fn outputs<'a>(&'a self, job_id: &'a str) -> BoxFuture<'a, Result<Vec<GeneratedImage>, String>> {
+        Box::pin(async move {
+            let history = get_json(&self.comfy_url, &format!("/history/{}", job_id)).await?;
+            let outputs = history[job_id]["outputs"].as_object().ok_or(format!("Prompt {} has no outputs yet", job_id))?;
+            let client = comfyui_connection::http_client(&self.comfy_url).await?;
+            let mut images = Vec::new();
+            for (node_id, output) in outputs {
+                for image in output["images"].as_array().into_iter().flatten() {
+                    let filename = image["filename"].as_str().unwrap_or("");
+                    let subfolder = image["subfolder"].as_str().unwrap_or("");
+                    let kind = image["type"].as_str().unwrap_or("output");
+                    let response = client
+                        .get(format!("{}/view", self.comfy_url.trim_end_matches('/')))
+                        .query(&[("filename", filename), ("subfolder", subfolder), ("type", kind)])
+                        .send().await
+                        .and_then(|r| r.error_for_status())
+                        .map_err(|e| format!("Failed to download {}: {}", filename, e))?;
+                    let data = response
+                        .bytes().await
+                        .map_err(|e| format!("Failed to read {}: {}", filename, e))?
+                        .to_vec();
+                    images.push(GeneratedImage {
+                        mime_type: image_mime_type(&data).to_string(),
+                        data,
+                        metadata: serde_json::json!({
+                            "provider": "comfyui",
+                            "promptId": job_id,
+                            "node": node_id,
+                            "filename": filename,
+                            "subfolder": subfolder,
+                            "type": kind,
+                        }),
+                    });
+                }
+            }
+            Ok(images)
+        })
+    }
+}
+
+async fn connect(comfy_url: &str, client_id: &str) -> Result<JobSocket, String> {
+    let ws_url = websocket_url(comfy_url, client_id)?;
+    let (request, connector) = comfyui_connection::websocket_request(comfy_url, &ws_url).await?;
+    let (socket, _) = tokio_tungstenite
+        ::connect_async_tls_with_config(request, None, false, connector).await
+        .map_err(|e| format!("Failed to connect to ComfyUI websocket: {}", e))?;
+    Ok(socket)
+}
+
+async fn queue_prompt(comfy_url: &str, workflow_json: &Value, client_id: String) -> Result<ComfyJob, String> {
+    let result = post_json(
+        comfy_url,
+        "/prompt",
+        &serde_json::json!({ "prompt": workflow_json, "client_id": client_id })
+    ).await?;
+    let prompt_id = result["prompt_id"].as_str().ok_or("No prompt_id in response")?.to_string();
+    Ok(ComfyJob { prompt_id, client_id, number: result["number"].as_i64() })
+}
+
+/// Follows a prompt over the socket until it finishes, fails, is cancelled or the socket drops.
+async fn listen(mut socket: JobSocket, mut listener: JobListener, cancel: Arc<Notify>) {
+    listener.refresh_queue_position().await;
+    listener.emit_queue_position(None).await;
+    loop {
+        tokio::select! {
+            _ = cancel.notified() => {
+                listener.emit(ComfyJobEvent::Cancelled { prompt_id: listener.prompt_id.clone() });
+                break;
+            }
+            message = socket.next() => {
+                match message {
+                    Some(Ok(Message::Text(text))) => {
+                        if listener.handle_text(&text).await {
+                            break;
+                        }
+                    }
+                    Some(Ok(Message::Binary(bytes))) => listener.handle_binary(&bytes),
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => {
+                        listener.emit(ComfyJobEvent::Disconnected { prompt_id: listener.prompt_id.clone(), reason: e.to_string() });
+                        break;
+                    }
+                    None => {
+                        listener.emit(ComfyJobEvent::Disconnected { prompt_id: listener.prompt_id.clone(), reason: "Socket closed".to_string() });
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    COMFYUI_JOBS.lock().unwrap().remove(&listener.prompt_id);
+    COMFYUI_PROGRESS.lock().unwrap().remove(&listener.prompt_id);
+    let _ = socket.close(None).await;
+}
+
+/// Listens for a queued prompt in the background, making it cancellable through `comfyui_cancel_job`.
+fn spawn_listener(socket: JobSocket, comfy_url: String, prompt_id: String, sink: EventSink) {
+    let cancel = Arc::new(Notify::new());
+    COMFYUI_JOBS.lock().unwrap().insert(prompt_id.clone(), cancel.clone());
+    tauri::async_runtime::spawn(listen(socket, JobListener::new(sink, comfy_url, prompt_id), cancel));
+}
+
+#[tauri::command]
+pub async fn comfyui_start_job(
+    app: tauri::AppHandle,
+    comfy_url: String,
+    workflow_json: Value,
+    client_id: Option<String>
+) -> Result<ComfyJob, String> {
+    let client_id = client_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let socket = connect(&comfy_url, &client_id).await?;
+    let job = queue_prompt(&comfy_url, &workflow_json, client_id).await?;
+
+    let sink: EventSink = Arc::new(move |event| {
+        if let Err(e) = app.emit(COMFYUI_JOB_EVENT, &event) {
+            log::warn!("Failed to emit ComfyUI job event: {}", e);
+        }
+    });
+    spawn_listener(socket, comfy_url, job.prompt_id.clone(), sink);
+
+    Ok(job)
+}
+
+#[tauri::command]
+pub async fn comfyui_cancel_job(comfy_url: String, prompt_id: String) -> Result<(), String> {
+    let queue = get_queue(&comfy_url).await?;
+    if queue_contains(&queue["queue_running"], &prompt_id) {
+        post_json(&comfy_url, "/interrupt", &serde_json::json!({ "prompt_id": prompt_id })).await?;
+        return Ok(());
+    }
+
+    if queue_contains(&queue["queue_pending"], &prompt_id) {
+        post_json(&comfy_url, "/queue", &serde_json::json!({ "delete": [prompt_id] })).await?;
+    }
+    if let Some(cancel) = COMFYUI_JOBS.lock().unwrap().get(&prompt_id) {
+        cancel.notify_one();
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn comfyui_get_queue_position(comfy_url: String, prompt_id: String) -> Result<Option<usize>, String> {
+    let queue = get_queue(&comfy_url).await?;
+    Ok(queue_position(&queue, &prompt_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{ StubResponse, StubServer };
+    use serde_json::json;
+    use std::sync::atomic::{ AtomicUsize, Ordering };
+
+    fn frame(value: Value) -> Message {
+        Message::Text(value.to_string())
+    }
+
+    fn status(queue_remaining: u64) -> Message {
+        frame(json!({ "type": "status", "data": { "status": { "exec_info": { "queue_remaining": queue_remaining } } } }))
+    }
+
+    fn recorder() -> (EventSink, Arc<Mutex<Vec<Value>>>) {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let log = events.clone();
+        let sink: EventSink = Arc::new(move |event| log.lock().unwrap().push(serde_json::to_value(event).unwrap()));
+        (sink, events)
+    }
+
+    #[tokio::test]
+    async fn follows_a_prompt_from_the_queue_to_completion() {
+        let lookups = Arc::new(AtomicUsize::new(0));
+        let counter = lookups.clone();
+        let server = StubServer::with_socket(
+            move |request| {
+                match (request.method.as_str(), request.path.as_str()) {
+                    ("POST", "/prompt") => StubResponse::json(200, json!({ "prompt_id": "p1", "number": 3 })),
+                    ("GET", "/queue") if counter.fetch_add(1, Ordering::SeqCst) == 0 =>
+                        StubResponse::json(
+                            200,
+                            json!({ "queue_running": [[2, "other"]], "queue_pending": [[3, "p1"]] })
+                        ),
+                    ("GET", "/queue") => StubResponse::json(200, json!({ "queue_running": [[3, "p1"]], "queue_pending": [] })),
+                    _ => StubResponse::json(404, Value::Null),
+                }
+            },
+            vec![
+                status(2),
+                status(2),
+                frame(json!({ "type": "progress", "data": { "prompt_id": "other", "value": 1, "max": 2 } })),
+                status(1),
+                frame(json!({ "type": "execution_start", "data": { "prompt_id": "p1" } })),
+                frame(json!({ "type": "executing", "data": { "prompt_id": "p1", "node": "3" } })),
+                frame(json!({ "type": "progress", "data": { "prompt_id": "p1", "node": "3", "value": 4, "max": 20 } })),
+                frame(json!({ "type": "executed", "data": { "prompt_id": "p1", "node": "9", "output": { "images": [] } } })),
+                frame(json!({ "type": "execution_success", "data": { "prompt_id": "p1" } }))
+            ]
+        ).await;
+
+        let socket = connect(&server.url, "c1").await.unwrap();
+        let job = queue_prompt(&server.url, &json!({}), "c1".to_string()).await.unwrap();
+        assert_eq!(job.prompt_id, "p1");
+        assert_eq!(job.number, Some(3));
+
+        let (sink, events) = recorder();
+        listen(socket, JobListener::new(sink, server.url.clone(), job.prompt_id), Arc::new(Notify::new())).await;
+
+        let events = events.lock().unwrap().clone();
+        let queued: Vec<(Value, Value)> = events
+            .iter()
+            .filter(|e| e["type"] == "queued")
+            .map(|e| (e["position"].clone(), e["queueRemaining"].clone()))
+            .collect();
+        assert_eq!(queued, vec![(json!(1), Value::Null), (json!(1), json!(2)), (json!(1), json!(2)), (json!(0), json!(1))]);
+        let rest: Vec<&Value> = events
+            .iter()
+            .filter(|e| e["type"] != "queued")
+            .collect();
+        assert_eq!(rest.len(), 5);
+        assert_eq!(rest[0]["type"], "execution_start");
+        assert_eq!(rest[1]["type"], "executing");
+        assert_eq!(*rest[2], json!({ "type": "progress", "promptId": "p1", "node": "3", "value": 4, "max": 20 }));
+        assert_eq!(rest[3]["type"], "executed");
+        assert_eq!(rest[3]["output"], json!({ "images": [] }));
+        assert_eq!(rest[4]["type"], "completed");
+
+        // One lookup when listening starts and one when the queue shrank, not one per status message
+        assert_eq!(server.requests_to("GET", "/queue").len(), 2);
+    }
+
+    #[tokio::test]
+    async fn provider_status_reports_progress_from_the_socket() {
+        let server = StubServer::with_socket(
+            |request| {
+                match request.path.as_str() {
+                    "/prompt" => StubResponse::json(200, json!({ "prompt_id": "p-steps", "number": 1 })),
+                    "/history/p-steps" => StubResponse::json(200, json!({})),
+                    "/queue" => StubResponse::json(200, json!({ "queue_running": [[1, "p-steps"]], "queue_pending": [] })),
+                    _ => StubResponse::json(404, Value::Null),
+                }
+            },
+            vec![
+                frame(json!({ "type": "execution_start", "data": { "prompt_id": "p-steps" } })),
+                frame(json!({ "type": "progress", "data": { "prompt_id": "p-steps", "node": "3", "value": 5, "max": 20 } }))
+            ]
+        ).await;
+        let provider = ComfyUIProvider::new(server.url.clone());
+        assert_eq!(provider.status("p-steps").await.unwrap().progress, None);
+
+        let socket = connect(&server.url, "c3").await.unwrap();
+        let job = queue_prompt(&server.url, &json!({}), "c3".to_string()).await.unwrap();
+        let cancel = Arc::new(Notify::new());
+        let (sink, events) = recorder();
+        let listening = tokio::spawn(listen(socket, JobListener::new(sink, server.url.clone(), job.prompt_id), cancel.clone()));
+        while !events.lock().unwrap().iter().any(|e| e["type"] == "progress") {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        let status = provider.status("p-steps").await.unwrap();
+        assert_eq!(status.state, JobState::Running);
+        assert_eq!(status.progress, Some(0.25));
+
+        cancel.notify_one();
+        listening.await.unwrap();
+        assert_eq!(provider.status("p-steps").await.unwrap().progress, None);
+    }
+
+    #[tokio::test]
+    async fn cancelling_a_running_prompt_interrupts_it() {
+        let server = StubServer::start(|request| {
+            match request.path.as_str() {
+                "/queue" => StubResponse::json(200, json!({ "queue_running": [[1, "p-run"]], "queue_pending": [] })),
+                _ => StubResponse::json(200, json!({})),
+            }
+        }).await;
+
+        comfyui_cancel_job(server.url.clone(), "p-run".to_string()).await.unwrap();
+
+        let interrupts = server.requests_to("POST", "/interrupt");
+        assert_eq!(interrupts.len(), 1);
+        assert_eq!(serde_json::from_slice::<Value>(&interrupts[0].body).unwrap(), json!({ "prompt_id": "p-run" }));
+        assert!(server.requests_to("POST", "/queue").is_empty());
+    }
+
+    #[tokio::test]
+    async fn cancelling_a_pending_prompt_deletes_it_and_stops_the_listener() {
+        let server = StubServer::with_socket(
+            |request| {
+                match (request.method.as_str(), request.path.as_str()) {
+                    ("POST", "/prompt") => StubResponse::json(200, json!({ "prompt_id": "p-wait", "number": 7 })),
+                    ("GET", "/queue") =>
+                        StubResponse::json(
+                            200,
+                            json!({ "queue_running": [[1, "other"]], "queue_pending": [[7, "p-wait"]] })
+                        ),
+                    _ => StubResponse::json(200, json!({})),
+                }
+            },
+            vec![status(2)]
+        ).await;
+
+        let socket = connect(&server.url, "c2").await.unwrap();
+        let job = queue_prompt(&server.url, &json!({}), "c2".to_string()).await.unwrap();
+        let cancel = Arc::new(Notify::new());
+        COMFYUI_JOBS.lock().unwrap().insert(job.prompt_id.clone(), cancel.clone());
+        let (sink, events) = recorder();
+        let listening = tokio::spawn(listen(socket, JobListener::new(sink, server.url.clone(), job.prompt_id), cancel));
+
+        comfyui_cancel_job(server.url.clone(), "p-wait".to_string()).await.unwrap();
+        tokio::time::timeout(std::time::Duration::from_secs(5), listening).await.unwrap().unwrap();
+
+        let deletes = server.requests_to("POST", "/queue");
+        assert_eq!(deletes.len(), 1);
+        assert_eq!(serde_json::from_slice::<Value>(&deletes[0].body).unwrap(), json!({ "delete": ["p-wait"] }));
+        assert!(server.requests_to("POST", "/interrupt").is_empty());
+        assert_eq!(events.lock().unwrap().last().unwrap()["type"], "cancelled");
+        assert!(!COMFYUI_JOBS.lock().unwrap().contains_key("p-wait"));
+    }
+}
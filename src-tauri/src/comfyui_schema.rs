@@ -0,0 +1,381 @@
+use once_cell::sync::Lazy;
+use serde::{ Deserialize, Serialize };
+use serde_json::Value;
+use std::collections::{ BTreeMap, HashMap };
+use std::sync::{ Arc, Mutex };
+use std::time::{ Duration, Instant };
+
+const OBJECT_INFO_TTL: Duration = Duration::from_secs(300);
+
+type SchemaMap = Arc<BTreeMap<String, NodeSchema>>;
+
+/// Parsed `/object_info` responses with the time they were fetched, by server URL
+type SchemaCache = Arc<Mutex<HashMap<String, (Instant, SchemaMap)>>>;
+
+static OBJECT_INFO_CACHE: Lazy<SchemaCache> = Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InputSpec {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub input_type: String,
+    pub required: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub step: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multiline: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub choices: Option<Vec<Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tooltip: Option<String>,
+    #[serde(rename = "forceInput", default, skip_serializing_if = "std::ops::Not::not")]
+    pub force_input: bool,
+    #[serde(rename = "controlAfterGenerate", default, skip_serializing_if = "std::ops::Not::not")]
+    pub control_after_generate: bool,
+}
+
+impl InputSpec {
+    /// Inputs the editor shows as widgets, whose values are stored positionally in `widgets_values`.
+    pub fn is_widget(&self) -> bool {
+        !self.force_input &&
+            (self.choices.is_some() || matches!(self.input_type.as_str(), "INT" | "FLOAT" | "STRING" | "BOOLEAN"))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutputSpec {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub output_type: String,
+    #[serde(rename = "isList")]
+    pub is_list: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeSchema {
+    #[serde(rename = "classType")]
+    pub class_type: String,
+    #[serde(rename = "displayName")]
+    pub display_name: String,
+    pub description: String,
+    pub category: String,
+    pub inputs: Vec<InputSpec>,
+    pub outputs: Vec<OutputSpec>,
+    #[serde(rename = "outputNode")]
+    pub output_node: bool,
+}
+
+impl NodeSchema {
+    pub fn input(&self, name: &str) -> Option<&InputSpec> {
+        self.inputs.iter().find(|i| i.name == name)
+    }
+}
+
+fn parse_input(name: &str, spec: &Value, required: bool) -> InputSpec {
+    let options = spec.get(1).cloned().unwrap_or(Value::Null);
+    let (input_type, choices) = match spec.get(0) {
+        Some(Value::Array(choices)) => ("COMBO".to_string(), Some(choices.clone())),
+        Some(Value::String(t)) if t == "COMBO" =>
+            ("COMBO".to_string(), options.get("options").and_then(Value::as_array).cloned()),
+        Some(Value::String(t)) => (t.clone(), None),
+        _ => ("*".to_string(), None),
+    };
+    InputSpec {
+        name: name.to_string(),
+        required,
+        default: options.get("default").cloned(),
+        min: options.get("min").and_then(Value::as_f64),
+        max: options.get("max").and_then(Value::as_f64),
+        step: options.get("step").and_then(Value::as_f64),
+        multiline: options.get("multiline").and_then(Value::as_bool),
+        choices,
+        tooltip: options.get("tooltip").and_then(Value::as_str).map(str::to_string),
+        force_input: options.get("forceInput").and_then(Value::as_bool).unwrap_or(false),
+        control_after_generate: options
+            .get("control_after_generate")
+            .and_then(Value::as_bool)
+            .unwrap_or(input_type == "INT" && matches!(name, "seed" | "noise_seed")),
+        input_type,
+    }
+}
+
+pub fn parse_node_schema(class_type: &str, info: &Value) -> NodeSchema {
+    let mut inputs = Vec::new();
+    for (group, required) in [("required", true), ("optional", false)] {
+        let Some(specs) = info["input"][group].as_object() else {
+            continue;
+        };
+        let order: Vec<String> = match info["input_order"][group].as_array() {
+            Some(order) => order.iter().filter_map(|n| n.as_str().map(str::to_string)).collect(),
+            None => specs.keys().cloned().collect(),
+        };
+        for name in order {
+            if let Some(spec) = specs.get(&name) {
+                inputs.push(parse_input(&name, spec, required));
+            }
+        }
+    }
+
+    let strings = |v: &Value| -> Vec<String> {
+        v.as_array()
+            .map(|a| a.iter().map(|s| s.as_str().unwrap_or("*").to_string()).collect())
+            .unwrap_or_default()
+    };
+    let output_types = strings(&info["output"]);
+    let output_names = strings(&info["output_name"]);
+    let output_is_list = info["output_is_list"].as_array().cloned().unwrap_or_default();
+    let outputs = output_types
+        .iter()
+        .enumerate()
+        .map(|(i, output_type)| OutputSpec {
+            name: output_names.get(i).cloned().unwrap_or_else(|| output_type.clone()),
+            output_type: output_type.clone(),
+            is_list: output_is_list.get(i).and_then(Value::as_bool).unwrap_or(false),
+        })
+        .collect();
+
+    NodeSchema {
+        class_type: class_type.to_string(),
+        display_name: info["display_name"].as_str().unwrap_or(class_type).to_string(),
+        description: info["description"].as_str().unwrap_or("").to_string(),
+        category: info["category"].as_str().unwrap_or("").to_string(),
+        inputs,
+        outputs,
+        output_node: info["output_node"].as_bool().unwrap_or(false),
+    }
+}
+
+/// Returns the node schemas of a ComfyUI server, fetching `/object_info` when the cached copy is missing or stale.
+pub async fn object_info(comfy_url: &str, refresh: bool) -> Result<SchemaMap, String> {
+    let key = comfy_url.trim_end_matches('/').to_string();
+    if !refresh {
+        if let Some((fetched, schemas)) = OBJECT_INFO_CACHE.lock().unwrap().get(&key) {
+            if fetched.elapsed() < OBJECT_INFO_TTL {
+                return Ok(schemas.clone());
+            }
+        }
+    }
+
+    let client = crate::comfyui_connection::http_client(&key).await?;
+    let response = client
+        .get(format!("{}/object_info", key))
+        .timeout(Duration::from_secs(30))
+        .send().await
+        .map_err(|e| format!("Failed to get object info: {}", e))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("HTTP {}: {}", status, error_text));
+    }
+    let raw: serde_json::Map<String, Value> = response
+        .json().await
+        .map_err(|e| format!("Failed to parse object info: {}", e))?;
+
+    let schemas: SchemaMap = Arc::new(
+        raw
+            .iter()
+            .map(|(class_type, info)| (class_type.clone(), parse_node_schema(class_type, info)))
+            .collect()
+    );
+    OBJECT_INFO_CACHE.lock().unwrap().insert(key, (Instant::now(), schemas.clone()));
+    Ok(schemas)
+}
+
+pub async fn node_schema(comfy_url: &str, class_type: &str) -> Result<Option<NodeSchema>, String> {
+    Ok(object_info(comfy_url, false).await?.get(class_type).cloned())
+}
+
+/// Lists the values offered by a loader's choice input, e.g. the checkpoint names of `CheckpointLoaderSimple`.
+pub fn model_choices(schema: &NodeSchema, input_name: Option<&str>) -> Result<Vec<String>, String> {
+    let input = match input_name {
+        Some(name) => schema.input(name),
+        None =>
+            schema.inputs
+                .iter()
+                .find(|i| i.required && i.choices.is_some())
+                .or_else(|| schema.inputs.iter().find(|i| i.choices.is_some())),
+    }.ok_or_else(|| format!("{} has no model list input", schema.class_type))?;
+
+    let choices = input.choices
+        .as_ref()
+        .ok_or_else(|| format!("Input {} of {} has no choices", input.name, schema.class_type))?;
+    Ok(
+        choices
+            .iter()
+            .map(|c| c.as_str().map(str::to_string).unwrap_or_else(|| c.to_string()))
+            .collect()
+    )
+}
+
+pub fn category_color(category: &str) -> &'static str {
+    match category.split('/').next().unwrap_or("") {
+        "loaders" => "#16A085",
+        "sampling" => "#E74C3C",
+        "conditioning" => "#4A90E2",
+        "latent" => "#9B59B6",
+        "image" => "#F39C12",
+        "mask" => "#7F8C8D",
+        _ => "#95A5A6",
+    }
+}
+
+/// The node description in the shape of `get_node_info`, extended with the full input and output specs.
+pub fn node_info_json(schema: &NodeSchema) -> Value {
+    let input_types: serde_json::Map<String, Value> = schema.inputs
+        .iter()
+        .map(|i| (i.name.clone(), Value::String(i.input_type.clone())))
+        .collect();
+    serde_json::json!({
+        "category": schema.category,
+        "description": if schema.description.is_empty() { &schema.display_name } else { &schema.description },
+        "input_types": input_types,
+        "output_types": schema.outputs.iter().map(|o| o.output_type.clone()).collect::<Vec<_>>(),
+        "color": category_color(&schema.category),
+        "display_name": schema.display_name,
+        "inputs": schema.inputs,
+        "outputs": schema.outputs,
+        "output_node": schema.output_node,
+    })
+}
+
+#[tauri::command]
+pub async fn comfyui_get_object_info(
+    comfy_url: String,
+    refresh: Option<bool>
+) -> Result<BTreeMap<String, NodeSchema>, String> {
+    let schemas = object_info(&comfy_url, refresh.unwrap_or(false)).await?;
+    Ok((*schemas).clone())
+}
+
+#[tauri::command]
+pub async fn comfyui_get_node_schema(comfy_url: String, class_type: String) -> Result<Option<NodeSchema>, String> {
+    node_schema(&comfy_url, &class_type).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{ self, StubResponse, StubServer };
+
+    #[test]
+    fn object_info_entries_are_parsed_in_input_order() {
+        let schemas = test_support::schemas();
+
+        let sampler = &schemas["KSampler"];
+        let names: Vec<&str> = sampler.inputs.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, [
+            "model", "seed", "steps", "cfg", "sampler_name", "scheduler", "positive", "negative", "latent_image", "denoise",
+        ]);
+        assert!(sampler.inputs.iter().all(|i| i.required));
+        let seed = sampler.input("seed").unwrap();
+        assert_eq!((seed.input_type.as_str(), seed.min, seed.default.clone()), ("INT", Some(0.0), Some(0.into())));
+        assert_eq!(seed.max, Some(u64::MAX as f64));
+        assert!(seed.control_after_generate && seed.is_widget());
+        let cfg = sampler.input("cfg").unwrap();
+        assert_eq!((cfg.min, cfg.max, cfg.step), (Some(0.0), Some(100.0), Some(0.1)));
+        assert_eq!(cfg.default, Some(serde_json::json!(8.0)));
+        let sampler_name = sampler.input("sampler_name").unwrap();
+        assert_eq!(sampler_name.input_type, "COMBO");
+        assert_eq!(sampler_name.choices, Some(vec!["euler".into(), "euler_ancestral".into(), "dpmpp_2m".into()]));
+        assert!(!sampler.input("model").unwrap().is_widget());
+        assert_eq!(sampler.outputs.len(), 1);
+        assert_eq!((sampler.outputs[0].name.as_str(), sampler.outputs[0].output_type.as_str()), ("LATENT", "LATENT"));
+
+        let text = schemas["CLIPTextEncode"].input("text").unwrap();
+        assert_eq!((text.multiline, text.tooltip.as_deref()), (Some(true), Some("The text to be encoded.")));
+        assert_eq!(schemas["CLIPTextEncode"].display_name, "CLIP Text Encode (Prompt)");
+        assert!(!schemas["EmptyLatentImage"].input("width").unwrap().control_after_generate);
+    }
+
+    #[test]
+    fn combo_inputs_optional_groups_and_outputs_are_read() {
+        let schemas = test_support::schemas();
+
+        let lora = schemas["LoraLoader"].input("lora_name").unwrap();
+        assert_eq!(lora.input_type, "COMBO");
+        assert_eq!(lora.choices, Some(vec!["garment_folds.safetensors".into(), "denim_texture.safetensors".into()]));
+
+        let load = &schemas["LoadImage"];
+        let groups: Vec<(&str, bool)> = load.inputs.iter().map(|i| (i.name.as_str(), i.required)).collect();
+        assert_eq!(groups, [("image", true), ("mask_channel", false), ("invert", false)]);
+        let mask_channel = load.input("mask_channel").unwrap();
+        assert_eq!(mask_channel.choices.as_ref().map(Vec::len), Some(4));
+        assert_eq!(mask_channel.default, Some("alpha".into()));
+        assert!(load.input("invert").unwrap().is_widget());
+        let outputs: Vec<(&str, &str)> = load.outputs.iter().map(|o| (o.name.as_str(), o.output_type.as_str())).collect();
+        assert_eq!(outputs, [("IMAGE", "IMAGE"), ("MASK", "MASK")]);
+
+        let split = &schemas["ImageBatchSplit"];
+        let index = split.input("index").unwrap();
+        assert!(index.force_input && !index.is_widget());
+        let outputs: Vec<(&str, &str, bool)> = split.outputs
+            .iter()
+            .map(|o| (o.name.as_str(), o.output_type.as_str(), o.is_list))
+            .collect();
+        assert_eq!(outputs, [("frames", "IMAGE", true), ("selected", "IMAGE", false)]);
+
+        let save = &schemas["SaveImage"];
+        assert!(save.output_node && save.outputs.is_empty());
+        assert_eq!(save.inputs.len(), 2, "hidden inputs are not part of the schema");
+
+        let bare = parse_node_schema("Custom", &serde_json::json!({ "input": { "required": { "x": [] } } }));
+        assert_eq!((bare.display_name.as_str(), bare.inputs[0].input_type.as_str()), ("Custom", "*"));
+    }
+
+    #[test]
+    fn model_lists_come_from_the_loader_choice_input() {
+        let schemas = test_support::schemas();
+
+        assert_eq!(model_choices(&schemas["CheckpointLoaderSimple"], None).unwrap(), [
+            "sd_xl_base_1.0.safetensors",
+            "v1-5-pruned-emaonly.safetensors",
+        ]);
+        assert_eq!(model_choices(&schemas["LoraLoader"], None).unwrap(), [
+            "garment_folds.safetensors",
+            "denim_texture.safetensors",
+        ]);
+        assert_eq!(model_choices(&schemas["LoadImage"], Some("mask_channel")).unwrap(), ["alpha", "red", "green", "blue"]);
+        assert_eq!(
+            model_choices(&schemas["LoraLoader"], Some("strength_model")).unwrap_err(),
+            "Input strength_model of LoraLoader has no choices"
+        );
+        assert_eq!(model_choices(&schemas["VAEDecode"], None).unwrap_err(), "VAEDecode has no model list input");
+    }
+
+    #[tokio::test]
+    async fn object_info_is_cached_per_server_until_refreshed() {
+        let first = StubServer::start(|_| StubResponse::json(200, test_support::object_info())).await;
+        let second = StubServer::start(|_| StubResponse::json(200, test_support::object_info())).await;
+
+        let schemas = object_info(&first.url, false).await.unwrap();
+        assert_eq!(schemas.len(), 10);
+        assert!(Arc::ptr_eq(&schemas, &object_info(&format!("{}/", first.url), false).await.unwrap()));
+        assert_eq!(node_schema(&first.url, "KSampler").await.unwrap().unwrap().class_type, "KSampler");
+        assert!(node_schema(&first.url, "Missing").await.unwrap().is_none());
+        assert_eq!(first.requests_to("GET", "/object_info").len(), 1);
+
+        object_info(&second.url, false).await.unwrap();
+        assert_eq!(second.requests_to("GET", "/object_info").len(), 1);
+
+        let refreshed = object_info(&first.url, true).await.unwrap();
+        assert!(!Arc::ptr_eq(&schemas, &refreshed));
+        assert_eq!(first.requests_to("GET", "/object_info").len(), 2);
+        assert_eq!(second.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn failed_fetches_are_reported_and_not_cached() {
+        let server = StubServer::start(|_| StubResponse::json(500, serde_json::json!({ "error": "busy" }))).await;
+
+        let error = object_info(&server.url, false).await.unwrap_err();
+        assert!(error.starts_with("HTTP 500"), "{}", error);
+        object_info(&server.url, false).await.unwrap_err();
+        assert_eq!(server.requests().len(), 2);
+    }
+}
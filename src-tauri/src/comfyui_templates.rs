@@ -0,0 +1,349 @@
+use crate::image_dpi;
+use base64::{ engine::general_purpose, Engine as _ };
+use serde::{ Deserialize, Serialize };
+use serde_json::Value;
+use std::collections::{ BTreeMap, HashMap };
+
+/// Largest seed handed out when randomising, kept within the integers JavaScript can represent exactly.
+const MAX_RANDOM_SEED: u64 = (1 << 53) - 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParameterType {
+    String,
+    Int,
+    Float,
+    Boolean,
+    Seed,
+    Image,
+    Choice,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateParameter {
+    /// `node_id.input` paths the value is written to
+    pub bind: Vec<String>,
+    #[serde(rename = "type")]
+    pub kind: ParameterType,
+    #[serde(default)]
+    pub default: Option<Value>,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub min: Option<f64>,
+    #[serde(default)]
+    pub max: Option<f64>,
+    #[serde(default)]
+    pub choices: Option<Vec<Value>>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowTemplate {
+    pub workflow: Value,
+    pub parameters: BTreeMap<String, TemplateParameter>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplateSubmission {
+    #[serde(rename = "promptId")]
+    pub prompt_id: String,
+    pub values: BTreeMap<String, Value>,
+}
+
+fn binding(path: &str) -> Option<(&str, &str)> {
+    path.split_once('.').filter(|(node, input)| !node.is_empty() && !input.is_empty())
+}
+
+fn check_bindings(template: &WorkflowTemplate) -> Vec<String> {
+    let mut errors = Vec::new();
+    for (name, parameter) in &template.parameters {
+        if parameter.bind.is_empty() {
+            errors.push(format!("{} is not bound to any input", name));
+        }
+        for path in &parameter.bind {
+            match binding(path) {
+                None => errors.push(format!("{}: binding {} is not of the form node_id.input", name, path)),
+                Some((node, _)) if !template.workflow[node]["inputs"].is_object() =>
+                    errors.push(format!("{}: node {} does not exist in the workflow", name, node)),
+                Some(_) => {}
+            }
+        }
+    }
+    errors
+}
+
+fn check_value(name: &str, parameter: &TemplateParameter, value: &Value) -> Result<(), String> {
+    let type_ok = match parameter.kind {
+        ParameterType::String | ParameterType::Image => value.is_string(),
+        ParameterType::Int | ParameterType::Seed => value.is_i64() || value.is_u64(),
+        ParameterType::Float => value.is_number(),
+        ParameterType::Boolean => value.is_boolean(),
+        ParameterType::Choice => true,
+    };
+    if !type_ok {
+        return Err(format!("{} expects a {:?} value, got {}", name, parameter.kind, value));
+    }
+    if let Some(n) = value.as_f64() {
+        if parameter.min.is_some_and(|min| n < min) || parameter.max.is_some_and(|max| n > max) {
+            return Err(
+                format!(
+                    "{} = {} is outside {}..{}",
+                    name,
+                    n,
+                    parameter.min.map(|v| v.to_string()).unwrap_or_default(),
+                    parameter.max.map(|v| v.to_string()).unwrap_or_default()
+                )
+            );
+        }
+    }
+    if let Some(choices) = &parameter.choices {
+        if !choices.contains(value) {
+            return Err(format!("{} = {} is not one of the allowed choices", name, value));
+        }
+    }
+    Ok(())
+}
+
+fn random_seed(parameter: &TemplateParameter) -> Value {
+    let min = parameter.min.map(|v| v.max(0.0) as u64).unwrap_or(0);
+    let max = parameter.max.map(|v| v as u64).unwrap_or(MAX_RANDOM_SEED).min(MAX_RANDOM_SEED).max(min);
+    Value::from(rand::random_range(min..=max))
+}
+
+/// Works out the value of every parameter from the caller's map and the template defaults.
+/// A seed given as `"random"`, or left unset when `randomize_seeds` is on, gets a fresh random value.
+/// Parameters with no value and no default are left out, keeping whatever the workflow already has.
+pub fn resolve_parameters(
+    template: &WorkflowTemplate,
+    params: &HashMap<String, Value>,
+    randomize_seeds: bool
+) -> Result<BTreeMap<String, Value>, String> {
+    let mut errors = check_bindings(template);
+    for name in params.keys() {
+        if !template.parameters.contains_key(name) {
+            errors.push(format!("Unknown parameter {}", name));
+        }
+    }
+
+    let mut values = BTreeMap::new();
+    for (name, parameter) in &template.parameters {
+        let given = params.get(name).filter(|v| !v.is_null());
+        let wants_random =
+            parameter.kind == ParameterType::Seed &&
+            (given.and_then(Value::as_str) == Some("random") || (given.is_none() && randomize_seeds));
+        let value = if wants_random {
+            random_seed(parameter)
+        } else {
+            match given.or(parameter.default.as_ref()) {
+                Some(value) => value.clone(),
+                None if parameter.required => {
+                    errors.push(format!("Missing required parameter {}", name));
+                    continue;
+                }
+                None => {
+                    continue;
+                }
+            }
+        };
+        match check_value(name, parameter, &value) {
+            Ok(()) => {
+                values.insert(name.clone(), value);
+            }
+            Err(e) => errors.push(e),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(values)
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+/// Writes resolved values into a copy of the template's workflow at each bound `node_id.input`.
+pub fn apply_parameters(template: &WorkflowTemplate, values: &BTreeMap<String, Value>) -> Value {
+    let mut workflow = template.workflow.clone();
+    for (name, value) in values {
+        let Some(parameter) = template.parameters.get(name) else {
+            continue;
+        };
+        for (node, input) in parameter.bind.iter().filter_map(|path| binding(path)) {
+            if let Some(inputs) = workflow[node]["inputs"].as_object_mut() {
+                inputs.insert(input.to_string(), value.clone());
+            }
+        }
+    }
+    workflow
+}
+
+/// Image values are uploaded when they hold base64 image data; anything else is taken as a file already on the server.
+fn is_image_data(value: &str) -> bool {
+    if value.starts_with("data:image") {
+        return true;
+    }
+    general_purpose::STANDARD
+        .decode(value)
+        .is_ok_and(|bytes| image_dpi::detect_format(&bytes).is_some())
+}
+
+#[tauri::command]
+pub async fn comfyui_execute_template(
+    comfy_url: String,
+    template: WorkflowTemplate,
+    params: HashMap<String, Value>,
+    client_id: String,
+    randomize_seeds: Option<bool>
+) -> Result<TemplateSubmission, String> {
+    let mut values = resolve_parameters(&template, &params, randomize_seeds.unwrap_or(false))?;
+
+    for (name, parameter) in &template.parameters {
+        if parameter.kind != ParameterType::Image {
+            continue;
+        }
+        let Some(data) = values.get(name).and_then(Value::as_str).filter(|v| is_image_data(v)) else {
+            continue;
+        };
+        let filename = crate::comfyui_upload_image(comfy_url.clone(), data.to_string()).await
+            .map_err(|e| format!("Failed to upload {}: {}", name, e))?;
+        values.insert(name.clone(), Value::String(filename));
+    }
+
+    let workflow = apply_parameters(&template, &values);
+    let prompt_id = crate::comfyui_execute_workflow(comfy_url, workflow, client_id).await?;
+    Ok(TemplateSubmission { prompt_id, values })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn template() -> WorkflowTemplate {
+        serde_json
+            ::from_value(
+                json!({
+                    "workflow": {
+                        "3": { "class_type": "KSampler", "inputs": { "seed": 0, "steps": 20, "cfg": 8, "model": ["4", 0] } },
+                        "6": { "class_type": "CLIPTextEncode", "inputs": { "text": "", "clip": ["4", 1] } },
+                        "10": { "class_type": "KSampler", "inputs": { "seed": 0, "steps": 10 } },
+                        "12": { "class_type": "LoadImage", "inputs": { "image": "sketch.png" } },
+                    },
+                    "parameters": {
+                        "prompt": { "bind": ["6.text"], "type": "string", "required": true },
+                        "seed": { "bind": ["3.seed", "10.seed"], "type": "seed", "min": 100, "max": 110 },
+                        "steps": { "bind": ["3.steps"], "type": "int", "default": 25, "min": 1, "max": 150 },
+                        "cfg": { "bind": ["3.cfg"], "type": "float" },
+                        "sampler": { "bind": ["3.sampler_name"], "type": "choice", "choices": ["euler", "dpmpp_2m"] },
+                        "sketch": { "bind": ["12.image"], "type": "image" },
+                    },
+                })
+            )
+            .unwrap()
+    }
+
+    fn params(values: Value) -> HashMap<String, Value> {
+        serde_json::from_value(values).unwrap()
+    }
+
+    #[test]
+    fn values_come_from_the_caller_then_the_defaults() {
+        let given = params(json!({ "prompt": "a linen shirt", "seed": 105 }));
+        let values = resolve_parameters(&template(), &given, false).unwrap();
+
+        assert_eq!(
+            json!(values),
+            json!({ "prompt": "a linen shirt", "seed": 105, "steps": 25 }),
+            "parameters with no value and no default are left out"
+        );
+    }
+
+    #[test]
+    fn missing_required_parameters_are_refused() {
+        let error = resolve_parameters(&template(), &params(json!({ "prompt": null })), false).unwrap_err();
+        assert_eq!(error, "Missing required parameter prompt");
+    }
+
+    #[test]
+    fn wrong_types_ranges_and_choices_are_refused() {
+        let cases = [
+            (json!({ "steps": "many" }), "steps expects a Int value, got \"many\""),
+            (json!({ "steps": 2.5 }), "steps expects a Int value, got 2.5"),
+            (json!({ "steps": 151 }), "steps = 151 is outside 1..150"),
+            (json!({ "seed": 99 }), "seed = 99 is outside 100..110"),
+            (json!({ "cfg": true }), "cfg expects a Float value, got true"),
+            (json!({ "sampler": "heun" }), "sampler = \"heun\" is not one of the allowed choices"),
+            (json!({ "sketch": 4 }), "sketch expects a Image value, got 4"),
+            (json!({ "style": "bold" }), "Unknown parameter style"),
+        ];
+        for (given, expected) in cases {
+            let mut given = params(given);
+            given.insert("prompt".to_string(), json!("a linen shirt"));
+            assert_eq!(resolve_parameters(&template(), &given, false).unwrap_err(), expected);
+        }
+    }
+
+    #[test]
+    fn bindings_must_name_an_input_of_an_existing_node() {
+        let mut template = template();
+        template.parameters.get_mut("cfg").unwrap().bind = vec!["3".to_string(), ".cfg".to_string(), "7.cfg".to_string()];
+        template.parameters.get_mut("sampler").unwrap().bind.clear();
+
+        let error = resolve_parameters(&template, &params(json!({ "prompt": "a linen shirt" })), false).unwrap_err();
+        assert_eq!(
+            error,
+            [
+                "cfg: binding 3 is not of the form node_id.input",
+                "cfg: binding .cfg is not of the form node_id.input",
+                "cfg: node 7 does not exist in the workflow",
+                "sampler is not bound to any input",
+            ].join("; ")
+        );
+    }
+
+    #[test]
+    fn random_seeds_stay_within_the_parameter_bounds() {
+        let template = template();
+        for _ in 0..200 {
+            let asked = resolve_parameters(&template, &params(json!({ "prompt": "x", "seed": "random" })), false).unwrap();
+            let unset = resolve_parameters(&template, &params(json!({ "prompt": "x" })), true).unwrap();
+            for seed in [&asked["seed"], &unset["seed"]] {
+                assert!((100..=110).contains(&seed.as_u64().unwrap()), "{}", seed);
+            }
+        }
+        let given = resolve_parameters(&template, &params(json!({ "prompt": "x", "seed": 107 })), true).unwrap();
+        assert_eq!(given["seed"], 107);
+
+        let unbounded: TemplateParameter = serde_json
+            ::from_value(json!({ "bind": ["3.seed"], "type": "seed", "min": -5, "max": 1e300 }))
+            .unwrap();
+        for _ in 0..200 {
+            assert!(random_seed(&unbounded).as_u64().unwrap() <= MAX_RANDOM_SEED);
+        }
+        let inverted: TemplateParameter = serde_json
+            ::from_value(json!({ "bind": ["3.seed"], "type": "seed", "min": 50, "max": 10 }))
+            .unwrap();
+        assert_eq!(random_seed(&inverted), 50);
+    }
+
+    #[test]
+    fn values_are_written_into_every_bound_node() {
+        let template = template();
+        let values = resolve_parameters(
+            &template,
+            &params(json!({ "prompt": "a linen shirt", "seed": 104, "sampler": "euler" })),
+            false
+        ).unwrap();
+        let workflow = apply_parameters(&template, &values);
+
+        assert_eq!(workflow["6"]["inputs"], json!({ "text": "a linen shirt", "clip": ["4", 1] }));
+        assert_eq!(
+            workflow["3"]["inputs"],
+            json!({ "seed": 104, "steps": 25, "cfg": 8, "model": ["4", 0], "sampler_name": "euler" })
+        );
+        assert_eq!(workflow["10"]["inputs"], json!({ "seed": 104, "steps": 10 }));
+        assert_eq!(workflow["12"], template.workflow["12"]);
+        assert_eq!(template.workflow["3"]["inputs"]["seed"], 0, "the template itself is left alone");
+    }
+}
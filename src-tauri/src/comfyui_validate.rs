@@ -0,0 +1,597 @@
+use crate::comfyui_schema::{ self, InputSpec, NodeSchema };
+use crate::{ ComfyUIValue, ComfyUIWorkflow };
+use serde::Serialize;
+use std::collections::{ BTreeMap, BTreeSet, HashMap };
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticKind {
+    UnknownNodeType,
+    MissingRequiredInput,
+    UnknownInput,
+    MissingSourceNode,
+    MissingOutput,
+    TypeMismatch,
+    InvalidValue,
+    OutOfRange,
+    InvalidChoice,
+    Cycle,
+    NoOutputNode,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub kind: DiagnosticKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NodeDiagnostics {
+    #[serde(rename = "nodeId")]
+    pub node_id: String,
+    #[serde(rename = "classType")]
+    pub class_type: String,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkflowValidation {
+    pub valid: bool,
+    #[serde(rename = "executionOrder")]
+    pub execution_order: Vec<String>,
+    pub cycles: Vec<Vec<String>>,
+    pub nodes: Vec<NodeDiagnostics>,
+    pub workflow: Vec<Diagnostic>,
+}
+
+fn error(kind: DiagnosticKind, input: Option<&str>, message: String) -> Diagnostic {
+    Diagnostic { severity: Severity::Error, kind, input: input.map(str::to_string), message }
+}
+
+fn warning(kind: DiagnosticKind, input: Option<&str>, message: String) -> Diagnostic {
+    Diagnostic { severity: Severity::Warning, kind, input: input.map(str::to_string), message }
+}
+
+/// Orders node ids numerically where possible, so "10" comes after "9".
+pub fn id_key(id: &str) -> (u64, String) {
+    (id.parse().unwrap_or(u64::MAX), id.to_string())
+}
+
+/// Whether an output of `from` can feed an input of `to`; `*` matches anything and inputs may list several types.
+pub fn types_compatible(from: &str, to: &str) -> bool {
+    if from == "*" || to == "*" || from == to {
+        return true;
+    }
+    let accepted: Vec<&str> = to.split(',').map(str::trim).collect();
+    from.split(',').map(str::trim).any(|t| accepted.contains(&t))
+}
+
+fn check_value(spec: &InputSpec, value: &ComfyUIValue) -> Option<Diagnostic> {
+    let name = Some(spec.name.as_str());
+    if let Some(choices) = &spec.choices {
+        let matches = choices.iter().any(|choice| {
+            match (choice, value) {
+                (serde_json::Value::String(c), ComfyUIValue::Text(v)) => c == v,
+                (serde_json::Value::Number(c), ComfyUIValue::Number(v)) => c.as_f64() == Some(*v),
+                (serde_json::Value::Bool(c), ComfyUIValue::Boolean(v)) => c == v,
+                _ => false,
+            }
+        });
+        return (!matches).then(|| {
+            error(
+                DiagnosticKind::InvalidChoice,
+                name,
+                format!("{} is not one of the {} options for {}", value_label(value), choices.len(), spec.name)
+            )
+        });
+    }
+
+    match (spec.input_type.as_str(), value) {
+        ("INT", ComfyUIValue::Number(n)) if n.fract() != 0.0 =>
+            Some(error(DiagnosticKind::InvalidValue, name, format!("{} must be a whole number, got {}", spec.name, n))),
+        ("INT" | "FLOAT", ComfyUIValue::Number(n)) => {
+            let below = spec.min.is_some_and(|min| *n < min);
+            let above = spec.max.is_some_and(|max| *n > max);
+            (below || above).then(|| {
+                error(
+                    DiagnosticKind::OutOfRange,
+                    name,
+                    format!(
+                        "{} = {} is outside {}..{}",
+                        spec.name,
+                        n,
+                        spec.min.map(|v| v.to_string()).unwrap_or_default(),
+                        spec.max.map(|v| v.to_string()).unwrap_or_default()
+                    )
+                )
+            })
+        }
+        ("INT" | "FLOAT", _) | ("BOOLEAN", ComfyUIValue::Text(_) | ComfyUIValue::Number(_)) | ("STRING", ComfyUIValue::Boolean(_)) =>
+            Some(
+                error(
+                    DiagnosticKind::InvalidValue,
+                    name,
+                    format!("{} expects {}, got {}", spec.name, spec.input_type, value_label(value))
+                )
+            ),
+        _ => None,
+    }
+}
+
+fn value_label(value: &ComfyUIValue) -> String {
+    match value {
+        ComfyUIValue::Text(s) => format!("\"{}\"", s),
+        ComfyUIValue::Number(n) => n.to_string(),
+        ComfyUIValue::Boolean(b) => b.to_string(),
+        ComfyUIValue::Connection(_) => "a link".to_string(),
+        ComfyUIValue::Null => "null".to_string(),
+    }
+}
+
+/// Tarjan's strongly connected components, keeping only those that form a cycle.
+fn find_cycles(ids: &[&str], edges: &HashMap<&str, Vec<&str>>) -> Vec<Vec<String>> {
+    struct State<'a> {
+        index: HashMap<&'a str, usize>,
+        low: HashMap<&'a str, usize>,
+        stack: Vec<&'a str>,
+        on_stack: BTreeSet<&'a str>,
+        cycles: Vec<Vec<String>>,
+    }
+
+    fn visit<'a>(node: &'a str, edges: &HashMap<&'a str, Vec<&'a str>>, state: &mut State<'a>) {
+        let index = state.index.len();
+        state.index.insert(node, index);
+        state.low.insert(node, index);
+        state.stack.push(node);
+        state.on_stack.insert(node);
+
+        for &next in edges.get(node).map(Vec::as_slice).unwrap_or_default() {
+            if !state.index.contains_key(next) {
+                visit(next, edges, state);
+                let low = state.low[node].min(state.low[next]);
+                state.low.insert(node, low);
+            } else if state.on_stack.contains(next) {
+                let low = state.low[node].min(state.index[next]);
+                state.low.insert(node, low);
+            }
+        }
+
+        if state.low[node] == state.index[node] {
+            let mut component = Vec::new();
+            while let Some(member) = state.stack.pop() {
+                state.on_stack.remove(member);
+                component.push(member.to_string());
+                if member == node {
+                    break;
+                }
+            }
+            let self_loop = edges.get(node).is_some_and(|e| e.contains(&node));
+            if component.len() > 1 || self_loop {
+                component.sort_by_key(|id| id_key(id));
+                state.cycles.push(component);
+            }
+        }
+    }
+
+    let mut state = State {
+        index: HashMap::new(),
+        low: HashMap::new(),
+        stack: Vec::new(),
+        on_stack: BTreeSet::new(),
+        cycles: Vec::new(),
+    };
+    for &id in ids {
+        if !state.index.contains_key(id) {
+            visit(id, edges, &mut state);
+        }
+    }
+    state.cycles
+}
+
+/// Checks a parsed workflow against node schemas and computes the order ComfyUI would execute it in.
+pub fn validate_workflow(workflow: &ComfyUIWorkflow, schemas: &BTreeMap<String, NodeSchema>) -> WorkflowValidation {
+    let mut nodes: Vec<_> = workflow.nodes.iter().collect();
+    nodes.sort_by_key(|n| id_key(&n.id));
+    let by_id: HashMap<&str, _> = nodes.iter().map(|n| (n.id.as_str(), *n)).collect();
+    let mut diagnostics: BTreeMap<&str, Vec<Diagnostic>> = BTreeMap::new();
+    let linked: BTreeSet<(&str, &str)> = workflow.connections
+        .iter()
+        .map(|c| (c.to_node.as_str(), c.to_input.as_str()))
+        .collect();
+
+    for node in &nodes {
+        let found = diagnostics.entry(node.id.as_str()).or_default();
+        let Some(schema) = schemas.get(&node.class_type) else {
+            found.push(
+                error(
+                    DiagnosticKind::UnknownNodeType,
+                    None,
+                    format!("{} is not installed on this server", node.class_type)
+                )
+            );
+            continue;
+        };
+
+        for spec in &schema.inputs {
+            if linked.contains(&(node.id.as_str(), spec.name.as_str())) {
+                continue;
+            }
+            match node.inputs.get(&spec.name) {
+                None | Some(ComfyUIValue::Null) if spec.required => {
+                    found.push(
+                        error(
+                            DiagnosticKind::MissingRequiredInput,
+                            Some(&spec.name),
+                            format!("Required input {} ({}) is not set", spec.name, spec.input_type)
+                        )
+                    );
+                }
+                Some(ComfyUIValue::Connection(_)) | None | Some(ComfyUIValue::Null) => {}
+                Some(value) => found.extend(check_value(spec, value)),
+            }
+        }
+
+        let mut names: Vec<&String> = node.inputs.keys().collect();
+        names.sort();
+        for name in names {
+            if schema.input(name).is_none() {
+                found.push(
+                    warning(
+                        DiagnosticKind::UnknownInput,
+                        Some(name),
+                        format!("{} has no input named {}", node.class_type, name)
+                    )
+                );
+            }
+        }
+    }
+
+    let mut edges: HashMap<&str, Vec<&str>> = HashMap::new();
+    let mut in_degree: HashMap<&str, usize> = nodes.iter().map(|n| (n.id.as_str(), 0)).collect();
+    for connection in &workflow.connections {
+        let found = diagnostics.entry(connection.to_node.as_str()).or_default();
+        let input = Some(connection.to_input.as_str());
+        let Some(source) = by_id.get(connection.from_node.as_str()) else {
+            found.push(
+                error(
+                    DiagnosticKind::MissingSourceNode,
+                    input,
+                    format!("{} is linked to node {}, which does not exist", connection.to_input, connection.from_node)
+                )
+            );
+            continue;
+        };
+        if by_id.contains_key(connection.to_node.as_str()) {
+            edges.entry(source.id.as_str()).or_default().push(connection.to_node.as_str());
+            *in_degree.entry(connection.to_node.as_str()).or_default() += 1;
+        }
+
+        let Some(source_schema) = schemas.get(&source.class_type) else {
+            continue;
+        };
+        let Some(output) = source_schema.outputs.get(connection.from_output) else {
+            found.push(
+                error(
+                    DiagnosticKind::MissingOutput,
+                    input,
+                    format!(
+                        "{} is linked to output {} of node {}, which has {} outputs",
+                        connection.to_input,
+                        connection.from_output,
+                        source.id,
+                        source_schema.outputs.len()
+                    )
+                )
+            );
+            continue;
+        };
+        let target = by_id
+            .get(connection.to_node.as_str())
+            .and_then(|n| schemas.get(&n.class_type))
+            .and_then(|s| s.input(&connection.to_input));
+        if let Some(target) = target {
+            let expected = if target.choices.is_some() { "COMBO" } else { target.input_type.as_str() };
+            if expected != "COMBO" && !types_compatible(&output.output_type, expected) {
+                found.push(
+                    error(
+                        DiagnosticKind::TypeMismatch,
+                        input,
+                        format!(
+                            "{} expects {} but node {} output {} is {}",
+                            connection.to_input,
+                            expected,
+                            source.id,
+                            connection.from_output,
+                            output.output_type
+                        )
+                    )
+                );
+            }
+        }
+    }
+
+    let mut ready: BTreeSet<(u64, String)> = in_degree
+        .iter()
+        .filter(|(_, degree)| **degree == 0)
+        .map(|(id, _)| id_key(id))
+        .collect();
+    let mut execution_order = Vec::new();
+    while let Some(next) = ready.pop_first() {
+        let id = next.1;
+        for &target in edges.get(id.as_str()).map(Vec::as_slice).unwrap_or_default() {
+            let degree = in_degree.get_mut(target).expect("edge targets are known nodes");
+            *degree -= 1;
+            if *degree == 0 {
+                ready.insert(id_key(target));
+            }
+        }
+        execution_order.push(id);
+    }
+
+    let ids: Vec<&str> = nodes.iter().map(|n| n.id.as_str()).collect();
+    let cycles = find_cycles(&ids, &edges);
+    for cycle in &cycles {
+        for id in cycle {
+            if let Some(found) = diagnostics.get_mut(id.as_str()) {
+                found.push(
+                    error(DiagnosticKind::Cycle, None, format!("Node is part of a cycle: {}", cycle.join(" → ")))
+                );
+            }
+        }
+    }
+
+    let mut workflow_diagnostics = Vec::new();
+    let has_output = nodes
+        .iter()
+        .any(|n| schemas.get(&n.class_type).is_some_and(|s| s.output_node));
+    if !has_output {
+        workflow_diagnostics.push(
+            error(DiagnosticKind::NoOutputNode, None, "Workflow has no output node, so nothing will run".to_string())
+        );
+    }
+
+    let mut nodes: Vec<NodeDiagnostics> = diagnostics
+        .into_iter()
+        .filter(|(_, found)| !found.is_empty())
+        .map(|(id, diagnostics)| NodeDiagnostics {
+            node_id: id.to_string(),
+            class_type: by_id.get(id).map(|n| n.class_type.clone()).unwrap_or_default(),
+            diagnostics,
+        })
+        .collect();
+    nodes.sort_by_key(|n| id_key(&n.node_id));
+    let valid = nodes
+        .iter()
+        .flat_map(|n| &n.diagnostics)
+        .chain(&workflow_diagnostics)
+        .all(|d| d.severity != Severity::Error);
+
+    WorkflowValidation { valid, execution_order, cycles, nodes, workflow: workflow_diagnostics }
+}
+
+#[tauri::command]
+pub async fn validate_comfyui_workflow(
+    comfy_url: String,
+    workflow: ComfyUIWorkflow
+) -> Result<WorkflowValidation, String> {
+    let schemas = comfyui_schema::object_info(&comfy_url, false).await?;
+    Ok(validate_workflow(&workflow, &schemas))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ test_support, ComfyUIConnection, ComfyUINode };
+    use serde_json::{ json, Value };
+
+    /// Builds a workflow from an API prompt, turning `[node, output]` inputs into connections.
+    fn workflow(prompt: Value) -> ComfyUIWorkflow {
+        let mut nodes = Vec::new();
+        let mut connections = Vec::new();
+        for (id, node) in prompt.as_object().unwrap() {
+            let mut inputs = HashMap::new();
+            for (name, value) in node["inputs"].as_object().unwrap() {
+                if let Some([from, output]) = value.as_array().map(Vec::as_slice) {
+                    connections.push(ComfyUIConnection {
+                        from_node: from.as_str().unwrap().to_string(),
+                        from_output: output.as_u64().unwrap() as usize,
+                        to_node: id.clone(),
+                        to_input: name.clone(),
+                    });
+                }
+                inputs.insert(name.clone(), serde_json::from_value(value.clone()).unwrap());
+            }
+            nodes.push(ComfyUINode {
+                id: id.clone(),
+                class_type: node["class_type"].as_str().unwrap().to_string(),
+                title: String::new(),
+                inputs,
+                outputs: Vec::new(),
+                position: None,
+            });
+        }
+        ComfyUIWorkflow { nodes, connections }
+    }
+
+    fn txt2img() -> Value {
+        json!({
+            "3": {
+                "class_type": "KSampler",
+                "inputs": {
+                    "model": ["4", 0], "seed": 42, "steps": 20, "cfg": 8, "sampler_name": "euler", "scheduler": "normal",
+                    "positive": ["6", 0], "negative": ["7", 0], "latent_image": ["5", 0], "denoise": 1,
+                },
+            },
+            "4": { "class_type": "CheckpointLoaderSimple", "inputs": { "ckpt_name": "sd_xl_base_1.0.safetensors" } },
+            "5": { "class_type": "EmptyLatentImage", "inputs": { "width": 1024, "height": 1024, "batch_size": 1 } },
+            "6": { "class_type": "CLIPTextEncode", "inputs": { "text": "a quilted vest", "clip": ["4", 1] } },
+            "7": { "class_type": "CLIPTextEncode", "inputs": { "text": "blurry", "clip": ["4", 1] } },
+            "8": { "class_type": "VAEDecode", "inputs": { "samples": ["3", 0], "vae": ["4", 2] } },
+            "9": { "class_type": "SaveImage", "inputs": { "images": ["8", 0], "filename_prefix": "coinop" } },
+        })
+    }
+
+    fn validate(prompt: Value) -> WorkflowValidation {
+        validate_workflow(&workflow(prompt), &test_support::schemas())
+    }
+
+    /// The diagnostics reported for one node, as `(kind, input)`
+    fn found(validation: &WorkflowValidation, node_id: &str) -> Vec<(DiagnosticKind, Option<String>)> {
+        validation.nodes
+            .iter()
+            .filter(|n| n.node_id == node_id)
+            .flat_map(|n| &n.diagnostics)
+            .map(|d| (d.kind, d.input.clone()))
+            .collect()
+    }
+
+    fn message(validation: &WorkflowValidation, node_id: &str) -> String {
+        validation.nodes.iter().find(|n| n.node_id == node_id).unwrap().diagnostics[0].message.clone()
+    }
+
+    #[test]
+    fn valid_graphs_execute_in_topological_order() {
+        let validation = validate(txt2img());
+
+        assert!(validation.valid, "{:?}", validation.nodes);
+        assert!(validation.nodes.is_empty() && validation.workflow.is_empty() && validation.cycles.is_empty());
+        assert_eq!(validation.execution_order, ["4", "5", "6", "7", "3", "8", "9"]);
+
+        let mut prompt = txt2img();
+        let decode = prompt.as_object_mut().unwrap().remove("8").unwrap();
+        prompt["10"] = decode;
+        prompt["9"]["inputs"]["images"] = json!(["10", 0]);
+        assert_eq!(validate(prompt).execution_order, ["4", "5", "6", "7", "3", "10", "9"]);
+    }
+
+    fn scale(image: Value) -> Value {
+        json!({
+            "class_type": "ImageScale",
+            "inputs": { "image": image, "upscale_method": "area", "width": 512, "height": 512, "crop": "center" },
+        })
+    }
+
+    #[test]
+    fn cycles_are_found_and_left_out_of_the_execution_order() {
+        let validation = validate(
+            json!({
+                "1": { "class_type": "LoadImage", "inputs": { "image": "sketch.png" } },
+                "2": scale(json!(["3", 0])),
+                "3": scale(json!(["2", 0])),
+                "4": scale(json!(["4", 0])),
+                "5": { "class_type": "SaveImage", "inputs": { "images": ["1", 0], "filename_prefix": "coinop" } },
+            })
+        );
+
+        assert!(!validation.valid);
+        assert_eq!(validation.cycles, [vec!["2".to_string(), "3".to_string()], vec!["4".to_string()]]);
+        assert_eq!(validation.execution_order, ["1", "5"]);
+        for id in ["2", "3", "4"] {
+            assert_eq!(found(&validation, id), [(DiagnosticKind::Cycle, None)]);
+        }
+        assert_eq!(message(&validation, "2"), "Node is part of a cycle: 2 → 3");
+    }
+
+    #[test]
+    fn links_to_missing_nodes_are_reported() {
+        let mut prompt = txt2img();
+        prompt["3"]["inputs"]["positive"] = json!(["99", 0]);
+        let validation = validate(prompt);
+
+        assert!(!validation.valid);
+        assert_eq!(found(&validation, "3"), [(DiagnosticKind::MissingSourceNode, Some("positive".to_string()))]);
+        assert_eq!(message(&validation, "3"), "positive is linked to node 99, which does not exist");
+        assert_eq!(validation.execution_order.len(), 7);
+    }
+
+    #[test]
+    fn links_to_missing_outputs_are_reported() {
+        let mut prompt = txt2img();
+        prompt["8"]["inputs"]["vae"] = json!(["4", 5]);
+        let validation = validate(prompt);
+
+        assert_eq!(found(&validation, "8"), [(DiagnosticKind::MissingOutput, Some("vae".to_string()))]);
+        assert_eq!(message(&validation, "8"), "vae is linked to output 5 of node 4, which has 3 outputs");
+    }
+
+    #[test]
+    fn mismatched_link_types_are_reported() {
+        let mut prompt = txt2img();
+        prompt["3"]["inputs"]["latent_image"] = json!(["6", 0]);
+        let validation = validate(prompt);
+
+        assert_eq!(found(&validation, "3"), [(DiagnosticKind::TypeMismatch, Some("latent_image".to_string()))]);
+        assert_eq!(message(&validation, "3"), "latent_image expects LATENT but node 6 output 0 is CONDITIONING");
+        assert!(types_compatible("*", "LATENT") && types_compatible("IMAGE", "MASK,IMAGE"));
+        assert!(!types_compatible("CONDITIONING", "LATENT"));
+    }
+
+    #[test]
+    fn values_outside_the_choices_are_reported() {
+        let mut prompt = txt2img();
+        prompt["3"]["inputs"]["sampler_name"] = json!("heun");
+        prompt["4"]["inputs"]["ckpt_name"] = json!(3);
+        let validation = validate(prompt);
+
+        assert_eq!(found(&validation, "3"), [(DiagnosticKind::InvalidChoice, Some("sampler_name".to_string()))]);
+        assert_eq!(message(&validation, "3"), "\"heun\" is not one of the 3 options for sampler_name");
+        assert_eq!(found(&validation, "4"), [(DiagnosticKind::InvalidChoice, Some("ckpt_name".to_string()))]);
+    }
+
+    #[test]
+    fn numbers_are_checked_against_type_and_range() {
+        let mut prompt = txt2img();
+        prompt["3"]["inputs"]["steps"] = json!(0);
+        prompt["3"]["inputs"]["denoise"] = json!(1.5);
+        prompt["5"]["inputs"]["width"] = json!(512.5);
+        prompt["5"]["inputs"]["height"] = json!("tall");
+        let validation = validate(prompt);
+
+        assert_eq!(found(&validation, "3"), [
+            (DiagnosticKind::OutOfRange, Some("steps".to_string())),
+            (DiagnosticKind::OutOfRange, Some("denoise".to_string())),
+        ]);
+        assert_eq!(message(&validation, "3"), "steps = 0 is outside 1..10000");
+        assert_eq!(found(&validation, "5"), [
+            (DiagnosticKind::InvalidValue, Some("width".to_string())),
+            (DiagnosticKind::InvalidValue, Some("height".to_string())),
+        ]);
+        assert_eq!(message(&validation, "5"), "width must be a whole number, got 512.5");
+    }
+
+    #[test]
+    fn missing_unknown_and_uninstalled_parts_are_reported() {
+        let mut prompt = txt2img();
+        prompt["3"]["inputs"].as_object_mut().unwrap().remove("steps");
+        prompt["3"]["inputs"]["cfg"] = Value::Null;
+        prompt["3"]["inputs"]["clip_skip"] = json!(2);
+        prompt["9"]["class_type"] = json!("SaveImageWebsocket");
+        let validation = validate(prompt);
+
+        assert_eq!(found(&validation, "3"), [
+            (DiagnosticKind::MissingRequiredInput, Some("steps".to_string())),
+            (DiagnosticKind::MissingRequiredInput, Some("cfg".to_string())),
+            (DiagnosticKind::UnknownInput, Some("clip_skip".to_string())),
+        ]);
+        let unknown = &validation.nodes.iter().find(|n| n.node_id == "3").unwrap().diagnostics[2];
+        assert_eq!(unknown.severity, Severity::Warning);
+        assert_eq!(found(&validation, "9"), [(DiagnosticKind::UnknownNodeType, None)]);
+        assert_eq!(validation.workflow.len(), 1);
+        assert_eq!(validation.workflow[0].kind, DiagnosticKind::NoOutputNode);
+        assert!(!validation.valid);
+
+        let mut warned_only = txt2img();
+        warned_only["3"]["inputs"]["clip_skip"] = json!(2);
+        assert!(validate(warned_only).valid);
+    }
+}
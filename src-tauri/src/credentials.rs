@@ -0,0 +1,543 @@
+use aes_gcm::aead::{ Aead, KeyInit, Payload };
+use aes_gcm::{ Aes256Gcm, Nonce };
+use base64::{ engine::general_purpose, Engine as _ };
+use chrono::{ DateTime, Utc };
+use once_cell::sync::Lazy;
+use serde::{ Deserialize, Serialize };
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use tauri::Manager;
+
+const CREDENTIALS_FILE: &str = "credentials.json";
+
+/// Sealed under the store key so a wrong passphrase or a different machine is caught before any secret is touched.
+const VERIFIER: &[u8] = b"coinop-credential-store";
+
+static CREDENTIAL_STORE: Lazy<Mutex<CredentialStore>> = Lazy::new(|| Mutex::new(CredentialStore::default()));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeySource {
+    /// Key derived from this machine's id, unlocked automatically at startup
+    #[default]
+    Machine,
+    /// Key derived from a passphrase the user enters each session
+    Passphrase,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Sealed {
+    nonce: String,
+    ciphertext: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredCredential {
+    #[serde(default)]
+    provider: Option<String>,
+    /// Origins (`scheme://host[:port]`) the secret may be sent to; unused for providers with a fixed API
+    #[serde(default)]
+    origins: Vec<String>,
+    #[serde(flatten)]
+    sealed: Sealed,
+    #[serde(rename = "createdAt")]
+    created_at: DateTime<Utc>,
+    #[serde(rename = "updatedAt")]
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct CredentialFile {
+    #[serde(rename = "keySource", default)]
+    key_source: KeySource,
+    #[serde(default)]
+    salt: String,
+    #[serde(default)]
+    verifier: Option<Sealed>,
+    #[serde(default)]
+    credentials: BTreeMap<String, StoredCredential>,
+}
+
+#[derive(Default)]
+struct CredentialStore {
+    file: CredentialFile,
+    key: Option<[u8; 32]>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CredentialInfo {
+    pub name: String,
+    pub provider: Option<String>,
+    pub origins: Vec<String>,
+    #[serde(rename = "createdAt")]
+    pub created_at: DateTime<Utc>,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CredentialStatus {
+    #[serde(rename = "keySource")]
+    pub key_source: KeySource,
+    pub locked: bool,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CredentialTest {
+    pub ok: bool,
+    pub message: String,
+}
+
+/// The fixed API origins of providers the app knows; their credentials are only ever sent there.
+fn provider_origins(provider: Option<&str>) -> &'static [&'static str] {
+    match provider {
+        Some("replicate") => &["https://api.replicate.com"],
+        Some("openai") => &["https://api.openai.com"],
+        _ => &[],
+    }
+}
+
+fn origin_of(url: &str) -> Result<String, String> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| format!("Invalid URL {}: {}", url, e))?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        return Err(format!("{} is not an http(s) URL", url));
+    }
+    Ok(parsed.origin().ascii_serialization())
+}
+
+/// The origins a credential is bound to: the provider's fixed API for known providers, otherwise the ones it was
+/// stored with.
+fn allowed_origins(stored: &StoredCredential) -> Vec<String> {
+    let fixed = provider_origins(stored.provider.as_deref());
+    if fixed.is_empty() {
+        stored.origins.clone()
+    } else {
+        fixed
+            .iter()
+            .map(|origin| origin.to_string())
+            .collect()
+    }
+}
+
+fn check_origin(name: &str, stored: &StoredCredential, url: &str) -> Result<(), String> {
+    let origin = origin_of(url)?;
+    if allowed_origins(stored).contains(&origin) {
+        Ok(())
+    } else {
+        Err(format!("Credential {} may not be sent to {}", name, origin))
+    }
+}
+
+fn new_salt() -> String {
+    general_purpose::STANDARD.encode(rand::random::<[u8; 16]>())
+}
+
+fn derive_key(secret: &[u8], salt: &str) -> Result<[u8; 32], String> {
+    let salt = general_purpose::STANDARD.decode(salt).map_err(|e| format!("Corrupt credential salt: {}", e))?;
+    let mut key = [0u8; 32];
+    argon2::Argon2
+        ::default()
+        .hash_password_into(secret, &salt, &mut key)
+        .map_err(|e| format!("Failed to derive key: {}", e))?;
+    Ok(key)
+}
+
+fn machine_secret() -> Result<Vec<u8>, String> {
+    let id = machine_uid::get().map_err(|e| format!("No machine id available, set a passphrase instead: {}", e))?;
+    Ok(format!("coinop:{}", id.trim()).into_bytes())
+}
+
+/// Encrypts with AES-256-GCM; the credential name is bound as associated data so entries cannot be swapped.
+fn seal(key: &[u8; 32], name: &str, plaintext: &[u8]) -> Result<Sealed, String> {
+    let cipher = Aes256Gcm::new(key.into());
+    let nonce = rand::random::<[u8; 12]>();
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), Payload { msg: plaintext, aad: name.as_bytes() })
+        .map_err(|_| format!("Failed to encrypt {}", name))?;
+    Ok(Sealed {
+        nonce: general_purpose::STANDARD.encode(nonce),
+        ciphertext: general_purpose::STANDARD.encode(ciphertext),
+    })
+}
+
+fn open(key: &[u8; 32], name: &str, sealed: &Sealed) -> Result<Vec<u8>, String> {
+    let nonce = general_purpose::STANDARD.decode(&sealed.nonce).map_err(|e| format!("Corrupt nonce for {}: {}", name, e))?;
+    let ciphertext = general_purpose::STANDARD
+        .decode(&sealed.ciphertext)
+        .map_err(|e| format!("Corrupt ciphertext for {}: {}", name, e))?;
+    if nonce.len() != 12 {
+        return Err(format!("Corrupt nonce for {}", name));
+    }
+    Aes256Gcm::new(key.into())
+        .decrypt(Nonce::from_slice(&nonce), Payload { msg: &ciphertext, aad: name.as_bytes() })
+        .map_err(|_| format!("Failed to decrypt {}", name))
+}
+
+/// Derives the key for the file's salt and checks it against the verifier, if one has been written.
+fn unlock_with(file: &CredentialFile, secret: &[u8]) -> Result<[u8; 32], String> {
+    let key = derive_key(secret, &file.salt)?;
+    if let Some(verifier) = &file.verifier {
+        if open(&key, "verifier", verifier).ok().as_deref() != Some(VERIFIER) {
+            return Err(
+                match file.key_source {
+                    KeySource::Passphrase => "Wrong passphrase".to_string(),
+                    KeySource::Machine => "Credential store was created on another machine".to_string(),
+                }
+            );
+        }
+    }
+    Ok(key)
+}
+
+fn credentials_path(app: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    Ok(app_data_dir.join(CREDENTIALS_FILE))
+}
+
+fn save_credentials(app: &tauri::AppHandle, file: &CredentialFile) -> Result<(), String> {
+    let path = credentials_path(app)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    let json = serde_json::to_string_pretty(file).map_err(|e| format!("Failed to serialize credentials: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write credentials: {}", e))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600));
+    }
+    Ok(())
+}
+
+/// Loads the store at startup and unlocks it when it is machine-bound; a passphrase store stays locked until unlocked.
+pub fn load_credentials(app: &tauri::AppHandle) {
+    let file = match credentials_path(app).and_then(|path| std::fs::read_to_string(path).map_err(|e| e.to_string())) {
+        Ok(json) =>
+            match serde_json::from_str::<CredentialFile>(&json) {
+                Ok(file) => file,
+                Err(e) => {
+                    log::warn!("Ignoring unreadable {}: {}", CREDENTIALS_FILE, e);
+                    return;
+                }
+            }
+        Err(_) => CredentialFile { salt: new_salt(), ..Default::default() },
+    };
+    let key = match file.key_source {
+        KeySource::Machine =>
+            machine_secret()
+                .and_then(|secret| unlock_with(&file, &secret))
+                .map_err(|e| log::warn!("Credential store locked: {}", e))
+                .ok(),
+        KeySource::Passphrase => None,
+    };
+    *CREDENTIAL_STORE.lock().unwrap() = CredentialStore { file, key };
+}
+
+fn decrypt(store: &CredentialStore, name: &str) -> Result<String, String> {
+    let stored = store.file.credentials.get(name).ok_or_else(|| format!("No credential named {}", name))?;
+    let key = store.key.as_ref().ok_or("Credential store is locked")?;
+    let plaintext = open(key, name, &stored.sealed)?;
+    String::from_utf8(plaintext).map_err(|_| format!("Credential {} is not valid text", name))
+}
+
+/// Fails unless `name` is a stored credential that may be sent to `url`.
+pub fn check_destination(name: &str, url: &str) -> Result<(), String> {
+    let store = CREDENTIAL_STORE.lock().unwrap();
+    let stored = store.file.credentials.get(name).ok_or_else(|| format!("No credential named {}", name))?;
+    check_origin(name, stored, url)
+}
+
+/// Decrypts a stored credential for a request to `url`, refusing when `url` is not on one of the origins the
+/// credential is bound to. Never return the result to the webview.
+pub fn secret_for(name: &str, url: &str) -> Result<String, String> {
+    let store = CREDENTIAL_STORE.lock().unwrap();
+    let stored = store.file.credentials.get(name).ok_or_else(|| format!("No credential named {}", name))?;
+    check_origin(name, stored, url)?;
+    decrypt(&store, name)
+}
+
+#[tauri::command]
+pub async fn credentials_status() -> Result<CredentialStatus, String> {
+    let store = CREDENTIAL_STORE.lock().unwrap();
+    Ok(CredentialStatus {
+        key_source: store.file.key_source,
+        locked: store.key.is_none(),
+        count: store.file.credentials.len(),
+    })
+}
+
+#[tauri::command]
+pub async fn credentials_unlock(passphrase: String) -> Result<(), String> {
+    let mut store = CREDENTIAL_STORE.lock().unwrap();
+    if store.file.key_source != KeySource::Passphrase {
+        return Err("Credential store is not passphrase protected".to_string());
+    }
+    let key = unlock_with(&store.file, passphrase.as_bytes())?;
+    store.key = Some(key);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn credentials_lock() -> Result<(), String> {
+    let mut store = CREDENTIAL_STORE.lock().unwrap();
+    if store.file.key_source == KeySource::Passphrase {
+        store.key = None;
+    }
+    Ok(())
+}
+
+/// Re-encrypts every credential under a new passphrase, or under the machine key when `passphrase` is empty.
+#[tauri::command]
+pub async fn credentials_set_passphrase(app: tauri::AppHandle, passphrase: Option<String>) -> Result<(), String> {
+    let mut store = CREDENTIAL_STORE.lock().unwrap();
+    if store.key.is_none() && !store.file.credentials.is_empty() {
+        return Err("Unlock the credential store first".to_string());
+    }
+    let mut secrets = BTreeMap::new();
+    if let Some(key) = &store.key {
+        for (name, stored) in &store.file.credentials {
+            secrets.insert(name.clone(), open(key, name, &stored.sealed)?);
+        }
+    }
+
+    let (key_source, secret) = match passphrase.filter(|p| !p.is_empty()) {
+        Some(passphrase) => (KeySource::Passphrase, passphrase.into_bytes()),
+        None => (KeySource::Machine, machine_secret()?),
+    };
+    let mut file = CredentialFile {
+        key_source,
+        salt: new_salt(),
+        verifier: None,
+        credentials: store.file.credentials.clone(),
+    };
+    let key = derive_key(&secret, &file.salt)?;
+    file.verifier = Some(seal(&key, "verifier", VERIFIER)?);
+    for (name, plaintext) in &secrets {
+        if let Some(stored) = file.credentials.get_mut(name) {
+            stored.sealed = seal(&key, name, plaintext)?;
+        }
+    }
+
+    save_credentials(&app, &file)?;
+    *store = CredentialStore { file, key: Some(key) };
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn credentials_set(
+    app: tauri::AppHandle,
+    name: String,
+    secret: String,
+    provider: Option<String>,
+    origins: Option<Vec<String>>
+) -> Result<CredentialInfo, String> {
+    if name.trim().is_empty() {
+        return Err("Credential name cannot be empty".to_string());
+    }
+    if secret.is_empty() {
+        return Err(format!("Secret for {} cannot be empty", name));
+    }
+    let origins = origins
+        .unwrap_or_default()
+        .iter()
+        .map(|url| origin_of(url))
+        .collect::<Result<Vec<String>, String>>()?;
+    if origins.is_empty() && provider_origins(provider.as_deref()).is_empty() {
+        return Err(format!("Credential {} needs at least one origin it may be sent to", name));
+    }
+    let mut store = CREDENTIAL_STORE.lock().unwrap();
+    let key = store.key.ok_or("Credential store is locked")?;
+    let mut file = store.file.clone();
+    if file.verifier.is_none() {
+        file.verifier = Some(seal(&key, "verifier", VERIFIER)?);
+    }
+    let now = Utc::now();
+    let created_at = file.credentials.get(&name).map(|c| c.created_at).unwrap_or(now);
+    let stored = StoredCredential {
+        provider,
+        origins,
+        sealed: seal(&key, &name, secret.as_bytes())?,
+        created_at,
+        updated_at: now,
+    };
+    file.credentials.insert(name.clone(), stored.clone());
+
+    save_credentials(&app, &file)?;
+    store.file = file;
+    Ok(CredentialInfo {
+        origins: allowed_origins(&stored),
+        name,
+        provider: stored.provider,
+        created_at,
+        updated_at: now,
+    })
+}
+
+#[tauri::command]
+pub async fn credentials_list() -> Result<Vec<CredentialInfo>, String> {
+    let store = CREDENTIAL_STORE.lock().unwrap();
+    Ok(
+        store.file.credentials
+            .iter()
+            .map(|(name, stored)| CredentialInfo {
+                name: name.clone(),
+                provider: stored.provider.clone(),
+                origins: allowed_origins(stored),
+                created_at: stored.created_at,
+                updated_at: stored.updated_at,
+            })
+            .collect()
+    )
+}
+
+#[tauri::command]
+pub async fn credentials_delete(app: tauri::AppHandle, name: String) -> Result<bool, String> {
+    let mut store = CREDENTIAL_STORE.lock().unwrap();
+    let mut file = store.file.clone();
+    if file.credentials.remove(&name).is_none() {
+        return Ok(false);
+    }
+    save_credentials(&app, &file)?;
+    store.file = file;
+    Ok(true)
+}
+
+/// Checks that a credential decrypts and, for known providers, that the service accepts it.
+#[tauri::command]
+pub async fn credentials_test(name: String) -> Result<CredentialTest, String> {
+    let provider = CREDENTIAL_STORE.lock()
+        .unwrap()
+        .file.credentials.get(&name)
+        .and_then(|c| c.provider.clone());
+    let url = match provider.as_deref() {
+        Some("replicate") => "https://api.replicate.com/v1/account",
+        Some("openai") => "https://api.openai.com/v1/models",
+        _ => {
+            decrypt(&CREDENTIAL_STORE.lock().unwrap(), &name)?;
+            return Ok(CredentialTest { ok: true, message: format!("{} decrypts", name) });
+        }
+    };
+    let api_key = secret_for(&name, url)?;
+    let response = reqwest::Client
+        ::new()
+        .get(url)
+        .bearer_auth(api_key)
+        .send().await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    let status = response.status();
+    Ok(CredentialTest {
+        ok: status.is_success(),
+        message: if status.is_success() {
+            format!("{} accepted by {}", name, provider.unwrap_or_default())
+        } else {
+            format!("HTTP {}", status)
+        },
+    })
+}
+
+/// Adds a credential bound to `origin` to the in-memory store only, unlocking it with a throwaway key if needed.
+#[cfg(test)]
+pub fn insert_for_tests(name: &str, origin: &str, secret: &str) {
+    let mut store = CREDENTIAL_STORE.lock().unwrap();
+    let key = *store.key.get_or_insert_with(rand::random);
+    let stored = StoredCredential {
+        provider: None,
+        origins: vec![origin_of(origin).unwrap()],
+        sealed: seal(&key, name, secret.as_bytes()).unwrap(),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    };
+    store.file.credentials.insert(name.to_string(), stored);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn passphrase_file(passphrase: &str) -> (CredentialFile, [u8; 32]) {
+        let mut file = CredentialFile { key_source: KeySource::Passphrase, salt: new_salt(), ..Default::default() };
+        let key = derive_key(passphrase.as_bytes(), &file.salt).unwrap();
+        file.verifier = Some(seal(&key, "verifier", VERIFIER).unwrap());
+        (file, key)
+    }
+
+    fn stored(provider: Option<&str>, origins: &[&str]) -> StoredCredential {
+        StoredCredential {
+            provider: provider.map(str::to_string),
+            origins: origins
+                .iter()
+                .map(|o| o.to_string())
+                .collect(),
+            sealed: Sealed { nonce: String::new(), ciphertext: String::new() },
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn sealed_secrets_round_trip_and_stay_bound_to_their_name() {
+        let key = rand::random::<[u8; 32]>();
+        let sealed = seal(&key, "replicate", b"r8_token").unwrap();
+        assert_eq!(open(&key, "replicate", &sealed).unwrap(), b"r8_token");
+        assert_ne!(seal(&key, "replicate", b"r8_token").unwrap().nonce, sealed.nonce);
+
+        assert!(open(&key, "openai", &sealed).is_err());
+        assert!(open(&rand::random::<[u8; 32]>(), "replicate", &sealed).is_err());
+        let mut tampered = sealed.clone();
+        let mut bytes = general_purpose::STANDARD.decode(&tampered.ciphertext).unwrap();
+        bytes[0] ^= 1;
+        tampered.ciphertext = general_purpose::STANDARD.encode(bytes);
+        assert!(open(&key, "replicate", &tampered).is_err());
+    }
+
+    #[test]
+    fn the_right_passphrase_unlocks_the_store() {
+        let (mut file, key) = passphrase_file("correct horse");
+        assert_eq!(unlock_with(&file, b"correct horse").unwrap(), key);
+
+        file.credentials.insert("subgraph".to_string(), StoredCredential {
+            sealed: seal(&key, "subgraph", b"graph-key").unwrap(),
+            ..stored(None, &["https://api.studio.thegraph.com"])
+        });
+        let unlocked = unlock_with(&file, b"correct horse").unwrap();
+        assert_eq!(open(&unlocked, "subgraph", &file.credentials["subgraph"].sealed).unwrap(), b"graph-key");
+    }
+
+    #[test]
+    fn a_wrong_passphrase_is_caught_by_the_verifier() {
+        let (file, _) = passphrase_file("correct horse");
+        assert_eq!(unlock_with(&file, b"battery staple").unwrap_err(), "Wrong passphrase");
+
+        let machine = CredentialFile { key_source: KeySource::Machine, ..file.clone() };
+        assert_eq!(
+            unlock_with(&machine, b"coinop:other-machine").unwrap_err(),
+            "Credential store was created on another machine"
+        );
+
+        let mut corrupt = file.clone();
+        corrupt.verifier = Some(seal(&rand::random::<[u8; 32]>(), "verifier", VERIFIER).unwrap());
+        assert!(unlock_with(&corrupt, b"correct horse").is_err());
+
+        let legacy = CredentialFile { verifier: None, ..file };
+        assert!(unlock_with(&legacy, b"anything").is_ok());
+    }
+
+    #[test]
+    fn secrets_only_go_to_their_bound_origins() {
+        let replicate = stored(Some("replicate"), &["https://evil.example"]);
+        assert!(check_origin("replicate", &replicate, "https://api.replicate.com/v1/predictions").is_ok());
+        assert!(check_origin("replicate", &replicate, "https://evil.example/v1").is_err());
+        assert!(check_origin("replicate", &replicate, "http://api.replicate.com/v1").is_err());
+        assert!(check_origin("replicate", &replicate, "https://api.replicate.com.evil.example/v1").is_err());
+
+        let subgraph = stored(None, &["https://gateway.thegraph.com"]);
+        assert!(check_origin("subgraph", &subgraph, "https://gateway.thegraph.com/api/subgraphs/id/x").is_ok());
+        assert!(check_origin("subgraph", &subgraph, "https://gateway.thegraph.com:8443/api").is_err());
+        assert!(check_origin("subgraph", &stored(None, &[]), "https://gateway.thegraph.com/api").is_err());
+        assert!(check_origin("subgraph", &subgraph, "file:///etc/passwd").is_err());
+    }
+}
@@ -0,0 +1,721 @@
+use serde::{ Deserialize, Serialize };
+use std::fmt::Write as _;
+use crate::fs_sandbox;
+
+const HPGL_UNITS_PER_MM: f64 = 40.0;
+const ARC_STEP_DEGREES: f64 = 10.0;
+const MAX_MITER_RATIO: f64 = 4.0;
+const LABEL_SCANLINES: usize = 32;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CutLayout {
+    pub pieces: Vec<CutPiece>,
+    #[serde(rename = "mmPerUnit")]
+    pub mm_per_unit: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CutPiece {
+    pub id: String,
+    pub label: Option<String>,
+    pub outline: Vec<[f64; 2]>,
+    #[serde(default)]
+    pub holes: Vec<Vec<[f64; 2]>>,
+    #[serde(default, rename = "internalLines")]
+    pub internal_lines: Vec<Vec<[f64; 2]>>,
+    pub x: f64,
+    pub y: f64,
+    pub rotation: f64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CutUnits {
+    Mm,
+    Cm,
+    Inch,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum CutOrigin {
+    BottomLeft,
+    TopLeft,
+    BottomRight,
+    TopRight,
+    Center,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ToolCompensation {
+    None,
+    Kerf {
+        width: f64,
+    },
+    BladeOffset {
+        offset: f64,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CutterSettings {
+    pub units: CutUnits,
+    pub origin: CutOrigin,
+    #[serde(rename = "originOffsetX", default)]
+    pub origin_offset_x: f64,
+    #[serde(rename = "originOffsetY", default)]
+    pub origin_offset_y: f64,
+    pub compensation: ToolCompensation,
+    #[serde(rename = "penTool", default = "default_pen_tool")]
+    pub pen_tool: u32,
+    #[serde(rename = "cutTool", default = "default_cut_tool")]
+    pub cut_tool: u32,
+    #[serde(rename = "labelHeightMm", default = "default_label_height")]
+    pub label_height_mm: f64,
+}
+
+fn default_pen_tool() -> u32 {
+    1
+}
+
+fn default_cut_tool() -> u32 {
+    2
+}
+
+fn default_label_height() -> f64 {
+    5.0
+}
+
+#[derive(Debug, Clone)]
+pub struct PlacedPiece {
+    pub id: String,
+    pub label: Option<String>,
+    pub internal_lines: Vec<Vec<[f64; 2]>>,
+    pub cut_outline: Vec<[f64; 2]>,
+    pub cut_holes: Vec<Vec<[f64; 2]>>,
+    pub label_anchor: [f64; 2],
+}
+
+impl CutUnits {
+    fn per_mm(&self) -> f64 {
+        match self {
+            CutUnits::Mm => 1.0,
+            CutUnits::Cm => 0.1,
+            CutUnits::Inch => 1.0 / 25.4,
+        }
+    }
+}
+
+fn transform_point(p: [f64; 2], piece: &CutPiece, mm_per_unit: f64) -> [f64; 2] {
+    let (sin, cos) = piece.rotation.to_radians().sin_cos();
+    let x = p[0] * cos - p[1] * sin + piece.x;
+    let y = p[0] * sin + p[1] * cos + piece.y;
+    [x * mm_per_unit, y * mm_per_unit]
+}
+
+fn signed_area(poly: &[[f64; 2]]) -> f64 {
+    let n = poly.len();
+    let mut area = 0.0;
+    for i in 0..n {
+        let a = poly[i];
+        let b = poly[(i + 1) % n];
+        area += a[0] * b[1] - b[0] * a[1];
+    }
+    area / 2.0
+}
+
+fn open_ring(poly: &[[f64; 2]]) -> Vec<[f64; 2]> {
+    let mut ring = poly.to_vec();
+    while ring.len() > 1 && ring.first() == ring.last() {
+        ring.pop();
+    }
+    ring.dedup();
+    ring
+}
+
+fn normalize(v: [f64; 2]) -> Option<[f64; 2]> {
+    let len = (v[0] * v[0] + v[1] * v[1]).sqrt();
+    if len < 1e-9 {
+        None
+    } else {
+        Some([v[0] / len, v[1] / len])
+    }
+}
+
+pub fn offset_polygon(poly: &[[f64; 2]], distance: f64) -> Vec<[f64; 2]> {
+    let ring = open_ring(poly);
+    let n = ring.len();
+    if n < 3 || distance == 0.0 {
+        return ring;
+    }
+    let orientation = if signed_area(&ring) >= 0.0 { 1.0 } else { -1.0 };
+    let mut result = Vec::with_capacity(n);
+    for i in 0..n {
+        let prev = ring[(i + n - 1) % n];
+        let curr = ring[i];
+        let next = ring[(i + 1) % n];
+        let d_in = normalize([curr[0] - prev[0], curr[1] - prev[1]]);
+        let d_out = normalize([next[0] - curr[0], next[1] - curr[1]]);
+        let (d_in, d_out) = match (d_in, d_out) {
+            (Some(a), Some(b)) => (a, b),
+            _ => {
+                result.push(curr);
+                continue;
+            }
+        };
+        let n_in = [d_in[1] * orientation, -d_in[0] * orientation];
+        let n_out = [d_out[1] * orientation, -d_out[0] * orientation];
+        let bisector = match normalize([n_in[0] + n_out[0], n_in[1] + n_out[1]]) {
+            Some(b) => b,
+            None => n_in,
+        };
+        let cos_half = bisector[0] * n_in[0] + bisector[1] * n_in[1];
+        let miter = if cos_half.abs() < 1.0 / MAX_MITER_RATIO {
+            MAX_MITER_RATIO * cos_half.signum()
+        } else {
+            1.0 / cos_half
+        };
+        result.push([curr[0] + bisector[0] * distance * miter, curr[1] + bisector[1] * distance * miter]);
+    }
+    result
+}
+
+pub fn blade_offset_path(poly: &[[f64; 2]], offset: f64) -> Vec<[f64; 2]> {
+    let ring = open_ring(poly);
+    let n = ring.len();
+    if n < 2 || offset <= 0.0 {
+        let mut closed = ring.clone();
+        if let Some(first) = ring.first() {
+            closed.push(*first);
+        }
+        return closed;
+    }
+    let dirs: Vec<[f64; 2]> = (0..n)
+        .map(|i| {
+            let a = ring[i];
+            let b = ring[(i + 1) % n];
+            normalize([b[0] - a[0], b[1] - a[1]]).unwrap_or([1.0, 0.0])
+        })
+        .collect();
+    let mut path = Vec::new();
+    path.push([ring[0][0] + dirs[0][0] * offset, ring[0][1] + dirs[0][1] * offset]);
+    for i in 1..=n {
+        let corner = ring[i % n];
+        let d_in = dirs[i - 1];
+        let d_out = dirs[i % n];
+        path.push([corner[0] + d_in[0] * offset, corner[1] + d_in[1] * offset]);
+        let start_angle = d_in[1].atan2(d_in[0]);
+        let mut sweep = d_out[1].atan2(d_out[0]) - start_angle;
+        while sweep > std::f64::consts::PI {
+            sweep -= 2.0 * std::f64::consts::PI;
+        }
+        while sweep < -std::f64::consts::PI {
+            sweep += 2.0 * std::f64::consts::PI;
+        }
+        let steps = (sweep.abs().to_degrees() / ARC_STEP_DEGREES).ceil() as usize;
+        for s in 1..=steps {
+            let angle = start_angle + sweep * (s as f64) / (steps as f64);
+            path.push([corner[0] + angle.cos() * offset, corner[1] + angle.sin() * offset]);
+        }
+    }
+    path
+}
+
+/// Sorted coordinates along `axis` (0 = x, 1 = y) where the line `other axis = at` crosses the rings' edges.
+fn crossings(rings: &[Vec<[f64; 2]>], axis: usize, at: f64) -> Vec<f64> {
+    let other = 1 - axis;
+    let mut hits = Vec::new();
+    for ring in rings {
+        let n = ring.len();
+        for i in 0..n {
+            let a = ring[i];
+            let b = ring[(i + 1) % n];
+            if (a[other] > at) != (b[other] > at) {
+                hits.push(a[axis] + ((at - a[other]) * (b[axis] - a[axis])) / (b[other] - a[other]));
+            }
+        }
+    }
+    hits.sort_by(f64::total_cmp);
+    hits
+}
+
+/// A point well inside the piece for its label. The vertex average can land outside concave pieces or in a hole,
+/// so this scans horizontal lines for inside spans and keeps the span midpoint with the most room both ways.
+fn label_anchor(outline: &[[f64; 2]], holes: &[Vec<[f64; 2]>]) -> [f64; 2] {
+    let rings: Vec<Vec<[f64; 2]>> = std::iter::once(open_ring(outline))
+        .chain(holes.iter().map(|h| open_ring(h)))
+        .collect();
+    let (min_y, max_y) = rings[0]
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| (lo.min(p[1]), hi.max(p[1])));
+    let mut best: Option<(f64, [f64; 2])> = None;
+    for i in 0..LABEL_SCANLINES {
+        let y = min_y + ((max_y - min_y) * (i as f64 + 0.5)) / (LABEL_SCANLINES as f64);
+        for span in crossings(&rings, 0, y).chunks_exact(2) {
+            let x = (span[0] + span[1]) / 2.0;
+            let Some(column) = crossings(&rings, 1, x)
+                .chunks_exact(2)
+                .find(|c| c[0] <= y && y <= c[1])
+                .map(|c| [c[0], c[1]]) else {
+                continue;
+            };
+            let room = (span[1] - span[0]).min(column[1] - column[0]);
+            if best.is_none_or(|(most, _)| room > most) {
+                best = Some((room, [x, (column[0] + column[1]) / 2.0]));
+            }
+        }
+    }
+    best.map_or(rings[0][0], |(_, anchor)| anchor)
+}
+
+fn close_ring(ring: Vec<[f64; 2]>) -> Vec<[f64; 2]> {
+    let mut ring = ring;
+    if let Some(first) = ring.first().copied() {
+        if ring.last() != Some(&first) {
+            ring.push(first);
+        }
+    }
+    ring
+}
+
+fn compensate(ring: &[[f64; 2]], compensation: &ToolCompensation, outward: bool) -> Vec<[f64; 2]> {
+    match compensation {
+        ToolCompensation::None => close_ring(open_ring(ring)),
+        ToolCompensation::Kerf { width } => {
+            let distance = if outward { width / 2.0 } else { -width / 2.0 };
+            close_ring(offset_polygon(ring, distance))
+        }
+        ToolCompensation::BladeOffset { offset } => blade_offset_path(ring, *offset),
+    }
+}
+
+pub fn place_layout(layout: &CutLayout, settings: &CutterSettings) -> Result<Vec<PlacedPiece>, String> {
+    if layout.mm_per_unit <= 0.0 {
+        return Err("mmPerUnit must be positive".to_string());
+    }
+    let mut transformed = Vec::with_capacity(layout.pieces.len());
+    let mut min = [f64::INFINITY, f64::INFINITY];
+    let mut max = [f64::NEG_INFINITY, f64::NEG_INFINITY];
+    for piece in &layout.pieces {
+        if piece.outline.len() < 3 {
+            return Err(format!("Piece {} has fewer than 3 outline points", piece.id));
+        }
+        let map = |ring: &Vec<[f64; 2]>| -> Vec<[f64; 2]> {
+            ring.iter().map(|p| transform_point(*p, piece, layout.mm_per_unit)).collect()
+        };
+        let outline = map(&piece.outline);
+        for p in &outline {
+            min[0] = min[0].min(p[0]);
+            min[1] = min[1].min(p[1]);
+            max[0] = max[0].max(p[0]);
+            max[1] = max[1].max(p[1]);
+        }
+        let holes = piece.holes.iter().map(map).collect::<Vec<_>>();
+        let internal_lines = piece.internal_lines.iter().map(map).collect::<Vec<_>>();
+        transformed.push((piece, outline, holes, internal_lines));
+    }
+    if transformed.is_empty() {
+        return Err("Layout has no pieces".to_string());
+    }
+
+    let (origin_x, origin_y) = match settings.origin {
+        CutOrigin::BottomLeft => (min[0], max[1]),
+        CutOrigin::TopLeft => (min[0], min[1]),
+        CutOrigin::BottomRight => (max[0], max[1]),
+        CutOrigin::TopRight => (max[0], min[1]),
+        CutOrigin::Center => ((min[0] + max[0]) / 2.0, (min[1] + max[1]) / 2.0),
+    };
+    let to_machine = |p: &[f64; 2]| -> [f64; 2] {
+        [p[0] - origin_x + settings.origin_offset_x, origin_y - p[1] + settings.origin_offset_y]
+    };
+    let to_machine_ring = |ring: &Vec<[f64; 2]>| -> Vec<[f64; 2]> {
+        ring.iter().map(to_machine).collect()
+    };
+
+    let mut placed = Vec::with_capacity(transformed.len());
+    for (piece, outline, holes, internal_lines) in transformed {
+        let outline = to_machine_ring(&outline);
+        let holes: Vec<Vec<[f64; 2]>> = holes.iter().map(to_machine_ring).collect();
+        let internal_lines = internal_lines.iter().map(to_machine_ring).collect();
+        let cut_outline = compensate(&outline, &settings.compensation, true);
+        let cut_holes = holes
+            .iter()
+            .map(|h| compensate(h, &settings.compensation, false))
+            .collect();
+        let label_anchor = label_anchor(&outline, &holes);
+        placed.push(PlacedPiece {
+            id: piece.id.clone(),
+            label: piece.label.clone(),
+            internal_lines,
+            cut_outline,
+            cut_holes,
+            label_anchor,
+        });
+    }
+    Ok(placed)
+}
+
+fn hpgl_polyline(out: &mut String, points: &[[f64; 2]]) {
+    let plu = |v: f64| (v * HPGL_UNITS_PER_MM).round() as i64;
+    if points.len() < 2 {
+        return;
+    }
+    let _ = write!(out, "PU{},{};PD", plu(points[0][0]), plu(points[0][1]));
+    let coords: Vec<String> = points[1..]
+        .iter()
+        .map(|p| format!("{},{}", plu(p[0]), plu(p[1])))
+        .collect();
+    let _ = writeln!(out, "{};", coords.join(","));
+}
+
+fn hpgl_label_text(label: &str) -> String {
+    label
+        .chars()
+        .filter(|c| !c.is_control() && *c != ';')
+        .collect()
+}
+
+pub fn layout_to_hpgl(layout: &CutLayout, settings: &CutterSettings) -> Result<String, String> {
+    let placed = place_layout(layout, settings)?;
+    let mut out = String::new();
+    out.push_str("IN;\n");
+
+    let _ = writeln!(out, "SP{};", settings.pen_tool);
+    let char_height_cm = settings.label_height_mm / 10.0;
+    let _ = writeln!(out, "SI{:.3},{:.3};", char_height_cm * 0.6, char_height_cm);
+    for piece in &placed {
+        for line in &piece.internal_lines {
+            hpgl_polyline(&mut out, line);
+        }
+        if let Some(label) = &piece.label {
+            let anchor = piece.label_anchor;
+            let _ = writeln!(
+                out,
+                "PU{},{};LB{}\x03",
+                (anchor[0] * HPGL_UNITS_PER_MM).round() as i64,
+                (anchor[1] * HPGL_UNITS_PER_MM).round() as i64,
+                hpgl_label_text(label)
+            );
+        }
+    }
+
+    let _ = writeln!(out, "SP{};", settings.cut_tool);
+    for piece in &placed {
+        for hole in &piece.cut_holes {
+            hpgl_polyline(&mut out, hole);
+        }
+        hpgl_polyline(&mut out, &piece.cut_outline);
+    }
+    out.push_str("PU;SP0;\n");
+    Ok(out)
+}
+
+fn dxf_pair(out: &mut String, code: i32, value: impl std::fmt::Display) {
+    let _ = writeln!(out, "{}\n{}", code, value);
+}
+
+fn dxf_polyline(out: &mut String, layer: &str, points: &[[f64; 2]], closed: bool, scale: f64) {
+    let mut points = points.to_vec();
+    if closed && points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    dxf_pair(out, 0, "POLYLINE");
+    dxf_pair(out, 8, layer);
+    dxf_pair(out, 66, 1);
+    dxf_pair(out, 10, "0.0");
+    dxf_pair(out, 20, "0.0");
+    dxf_pair(out, 30, "0.0");
+    dxf_pair(out, 70, if closed { 1 } else { 0 });
+    for p in &points {
+        dxf_pair(out, 0, "VERTEX");
+        dxf_pair(out, 8, layer);
+        dxf_pair(out, 10, format!("{:.4}", p[0] * scale));
+        dxf_pair(out, 20, format!("{:.4}", p[1] * scale));
+        dxf_pair(out, 30, "0.0");
+    }
+    dxf_pair(out, 0, "SEQEND");
+    dxf_pair(out, 8, layer);
+}
+
+/// A line break inside a group value would start a new group code, so control characters become word breaks.
+fn dxf_text_value(text: &str) -> String {
+    text.split(char::is_control)
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn dxf_text(out: &mut String, layer: &str, text: &str, at: [f64; 2], height: f64, scale: f64) {
+    dxf_pair(out, 0, "TEXT");
+    dxf_pair(out, 8, layer);
+    dxf_pair(out, 10, format!("{:.4}", at[0] * scale));
+    dxf_pair(out, 20, format!("{:.4}", at[1] * scale));
+    dxf_pair(out, 30, "0.0");
+    dxf_pair(out, 40, format!("{:.4}", height * scale));
+    dxf_pair(out, 1, dxf_text_value(text));
+}
+
+fn dxf_block_name(id: &str) -> String {
+    id.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == '-' { c } else { '_' })
+        .collect()
+}
+
+pub fn layout_to_dxf(layout: &CutLayout, settings: &CutterSettings) -> Result<String, String> {
+    let placed = place_layout(layout, settings)?;
+    let scale = settings.units.per_mm();
+    let mut out = String::new();
+
+    dxf_pair(&mut out, 0, "SECTION");
+    dxf_pair(&mut out, 2, "HEADER");
+    dxf_pair(&mut out, 9, "$ACADVER");
+    dxf_pair(&mut out, 1, "AC1009");
+    dxf_pair(&mut out, 0, "ENDSEC");
+
+    dxf_pair(&mut out, 0, "SECTION");
+    dxf_pair(&mut out, 2, "BLOCKS");
+    for (index, piece) in placed.iter().enumerate() {
+        let name = format!("{}_{}", dxf_block_name(&piece.id), index);
+        dxf_pair(&mut out, 0, "BLOCK");
+        dxf_pair(&mut out, 8, "0");
+        dxf_pair(&mut out, 2, &name);
+        dxf_pair(&mut out, 70, 0);
+        dxf_pair(&mut out, 10, "0.0");
+        dxf_pair(&mut out, 20, "0.0");
+        dxf_pair(&mut out, 30, "0.0");
+        dxf_pair(&mut out, 3, &name);
+        dxf_polyline(&mut out, "1", &piece.cut_outline, true, scale);
+        for hole in &piece.cut_holes {
+            dxf_polyline(&mut out, "11", hole, true, scale);
+        }
+        for line in &piece.internal_lines {
+            dxf_polyline(&mut out, "8", line, false, scale);
+        }
+        let text_height = settings.label_height_mm;
+        let mut text_at = piece.label_anchor;
+        let piece_name = piece.label.clone().unwrap_or_else(|| piece.id.clone());
+        dxf_text(&mut out, "15", &format!("Piece Name: {}", piece_name), text_at, text_height, scale);
+        text_at[1] -= text_height * 1.5;
+        let units_text = match settings.units {
+            CutUnits::Inch => "Units: ENGLISH",
+            _ => "Units: METRIC",
+        };
+        dxf_text(&mut out, "15", units_text, text_at, text_height, scale);
+        dxf_pair(&mut out, 0, "ENDBLK");
+        dxf_pair(&mut out, 8, "0");
+    }
+    dxf_pair(&mut out, 0, "ENDSEC");
+
+    dxf_pair(&mut out, 0, "SECTION");
+    dxf_pair(&mut out, 2, "ENTITIES");
+    for (index, piece) in placed.iter().enumerate() {
+        dxf_pair(&mut out, 0, "INSERT");
+        dxf_pair(&mut out, 8, "1");
+        dxf_pair(&mut out, 2, format!("{}_{}", dxf_block_name(&piece.id), index));
+        dxf_pair(&mut out, 10, "0.0");
+        dxf_pair(&mut out, 20, "0.0");
+        dxf_pair(&mut out, 30, "0.0");
+    }
+    dxf_pair(&mut out, 0, "ENDSEC");
+    dxf_pair(&mut out, 0, "EOF");
+    Ok(out)
+}
+
+#[tauri::command]
+pub async fn export_layout_to_hpgl(
+    app: tauri::AppHandle,
+    layout: CutLayout,
+    settings: CutterSettings,
+    out_path: Option<String>
+) -> Result<String, String> {
+    let output_path = fs_sandbox::export_path(
+        &app,
+        out_path.as_deref(),
+        &format!("pattern_cut_{}.plt", chrono::Utc::now().format("%Y%m%d_%H%M%S"))
+    )?;
+    let hpgl = layout_to_hpgl(&layout, &settings)?;
+    std::fs::write(&output_path, hpgl).map_err(|e| format!("write plt: {e}"))?;
+    Ok(output_path.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+pub async fn export_layout_to_dxf(
+    app: tauri::AppHandle,
+    layout: CutLayout,
+    settings: CutterSettings,
+    out_path: Option<String>
+) -> Result<String, String> {
+    let output_path = fs_sandbox::export_path(
+        &app,
+        out_path.as_deref(),
+        &format!("pattern_cut_{}.dxf", chrono::Utc::now().format("%Y%m%d_%H%M%S"))
+    )?;
+    let dxf = layout_to_dxf(&layout, &settings)?;
+    std::fs::write(&output_path, dxf).map_err(|e| format!("write dxf: {e}"))?;
+    Ok(output_path.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(units: CutUnits, compensation: ToolCompensation) -> CutterSettings {
+        CutterSettings {
+            units,
+            origin: CutOrigin::BottomLeft,
+            origin_offset_x: 0.0,
+            origin_offset_y: 0.0,
+            compensation,
+            pen_tool: 1,
+            cut_tool: 2,
+            label_height_mm: 5.0,
+        }
+    }
+
+    fn piece(id: &str, label: Option<&str>, outline: &[[f64; 2]]) -> CutPiece {
+        CutPiece {
+            id: id.to_string(),
+            label: label.map(str::to_string),
+            outline: outline.to_vec(),
+            holes: Vec::new(),
+            internal_lines: Vec::new(),
+            x: 0.0,
+            y: 0.0,
+            rotation: 0.0,
+        }
+    }
+
+    fn rectangle() -> CutLayout {
+        let mut front = piece("front", Some("Front;\u{7}"), &[[0.0, 0.0], [40.0, 0.0], [40.0, 20.0], [0.0, 20.0]]);
+        front.internal_lines = vec![vec![[10.0, 5.0], [30.0, 5.0]]];
+        CutLayout { pieces: vec![front], mm_per_unit: 1.0 }
+    }
+
+    fn inside(rings: &[Vec<[f64; 2]>], p: [f64; 2]) -> bool {
+        crossings(rings, 0, p[1])
+            .chunks_exact(2)
+            .any(|span| span[0] < p[0] && p[0] < span[1])
+    }
+
+    /// Group code/value pairs of a DXF file
+    fn dxf_pairs(dxf: &str) -> Vec<(i32, String)> {
+        let lines: Vec<&str> = dxf.lines().collect();
+        lines
+            .chunks_exact(2)
+            .map(|pair| (pair[0].parse().unwrap(), pair[1].to_string()))
+            .collect()
+    }
+
+    fn vertices(pairs: &[(i32, String)], layer: &str) -> Vec<[f64; 2]> {
+        let mut points = Vec::new();
+        for (i, (code, value)) in pairs.iter().enumerate() {
+            if *code == 0 && value == "VERTEX" && pairs[i + 1].1 == layer {
+                points.push([pairs[i + 2].1.parse().unwrap(), pairs[i + 3].1.parse().unwrap()]);
+            }
+        }
+        points
+    }
+
+    #[test]
+    fn hpgl_plots_pen_work_then_cuts_in_plotter_units() {
+        let hpgl = layout_to_hpgl(&rectangle(), &settings(CutUnits::Mm, ToolCompensation::None)).unwrap();
+        assert_eq!(
+            hpgl,
+            "IN;\n\
+             SP1;\n\
+             SI0.300,0.500;\n\
+             PU400,600;PD1200,600;\n\
+             PU800,400;LBFront\x03\n\
+             SP2;\n\
+             PU0,800;PD1600,800,1600,0,0,0,0,800;\n\
+             PU;SP0;\n"
+        );
+    }
+
+    #[test]
+    fn hpgl_kerf_moves_outlines_out_and_holes_in() {
+        let mut layout = rectangle();
+        layout.pieces[0].label = None;
+        layout.pieces[0].internal_lines.clear();
+        layout.pieces[0].holes = vec![vec![[15.0, 5.0], [25.0, 5.0], [25.0, 15.0], [15.0, 15.0]]];
+        let hpgl = layout_to_hpgl(&layout, &settings(CutUnits::Mm, ToolCompensation::Kerf { width: 1.0 })).unwrap();
+        assert_eq!(
+            hpgl,
+            "IN;\n\
+             SP1;\n\
+             SI0.300,0.500;\n\
+             SP2;\n\
+             PU620,580;PD980,580,980,220,620,220,620,580;\n\
+             PU-20,820;PD1620,820,1620,-20,-20,-20,-20,820;\n\
+             PU;SP0;\n"
+        );
+    }
+
+    #[test]
+    fn blade_offset_overshoots_corners_and_sweeps_arcs_around_them() {
+        let square = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]];
+        let path = blade_offset_path(&square, 0.5);
+
+        // Start point, then per corner an overshoot point and a 90 degree arc in 10 degree steps
+        assert_eq!(path.len(), 1 + 4 * (1 + 9));
+        assert_eq!(path[0], [0.5, 0.0]);
+        assert_eq!(path[1], [10.5, 0.0]);
+        for (step, point) in path[2..11].iter().enumerate() {
+            let distance = ((point[0] - 10.0).powi(2) + point[1].powi(2)).sqrt();
+            assert!((distance - 0.5).abs() < 1e-9, "arc point {} is {} from the corner", step, distance);
+        }
+        let end = path[10];
+        assert!((end[0] - 10.0).abs() < 1e-9 && (end[1] - 0.5).abs() < 1e-9);
+        let last = path.last().unwrap();
+        assert!((last[0] - 0.5).abs() < 1e-9 && last[1].abs() < 1e-9);
+    }
+
+    #[test]
+    fn dxf_is_r12_with_aama_blocks_scaled_to_the_chosen_units() {
+        let dxf = layout_to_dxf(&rectangle(), &settings(CutUnits::Inch, ToolCompensation::None)).unwrap();
+        let pairs = dxf_pairs(&dxf);
+
+        let header: Vec<(i32, &str)> = pairs[..5]
+            .iter()
+            .map(|(code, value)| (*code, value.as_str()))
+            .collect();
+        assert_eq!(header, [(0, "SECTION"), (2, "HEADER"), (9, "$ACADVER"), (1, "AC1009"), (0, "ENDSEC")]);
+        assert!(!dxf.contains("$INSUNITS"));
+        assert_eq!(pairs.last().unwrap(), &(0, "EOF".to_string()));
+
+        assert_eq!(vertices(&pairs, "1"), [[0.0, 0.7874], [1.5748, 0.7874], [1.5748, 0.0], [0.0, 0.0]]);
+        assert_eq!(vertices(&pairs, "8"), [[0.3937, 0.5906], [1.1811, 0.5906]]);
+
+        let texts: Vec<(&str, &str, &str)> = pairs
+            .iter()
+            .enumerate()
+            .filter(|(_, (code, value))| *code == 0 && value == "TEXT")
+            .map(|(i, _)| (pairs[i + 2].1.as_str(), pairs[i + 3].1.as_str(), pairs[i + 6].1.as_str()))
+            .collect();
+        assert_eq!(texts, [("0.7874", "0.3937", "Piece Name: Front;"), ("0.7874", "0.0984", "Units: ENGLISH")]);
+        assert_eq!(dxf_text_value("Front\r\nleft\tpanel\u{7}"), "Front left panel");
+
+        let metric = layout_to_dxf(&rectangle(), &settings(CutUnits::Mm, ToolCompensation::None)).unwrap();
+        let pairs = dxf_pairs(&metric);
+        assert_eq!(vertices(&pairs, "1"), [[0.0, 20.0], [40.0, 20.0], [40.0, 0.0], [0.0, 0.0]]);
+        assert!(metric.contains("\nUnits: METRIC\n"));
+    }
+
+    #[test]
+    fn labels_stay_inside_concave_pieces_and_out_of_holes() {
+        // The vertex average of a U falls in its notch
+        let u = [[0.0, 0.0], [30.0, 0.0], [30.0, 100.0], [20.0, 100.0], [20.0, 10.0], [10.0, 10.0], [10.0, 100.0], [0.0, 100.0]];
+        let anchor = label_anchor(&u, &[]);
+        assert!(inside(&[u.to_vec()], anchor), "{:?} is outside the U", anchor);
+
+        // ...and that of a frame falls in its hole
+        let frame = [[0.0, 0.0], [60.0, 0.0], [60.0, 60.0], [0.0, 60.0]];
+        let hole = vec![[10.0, 10.0], [50.0, 10.0], [50.0, 50.0], [10.0, 50.0]];
+        let anchor = label_anchor(&frame, std::slice::from_ref(&hole));
+        assert!(inside(&[frame.to_vec(), hole], anchor), "{:?} is not on the frame", anchor);
+
+        let square = [[0.0, 0.0], [40.0, 0.0], [40.0, 20.0], [0.0, 20.0]];
+        assert_eq!(label_anchor(&square, &[]), [20.0, 10.0]);
+    }
+}
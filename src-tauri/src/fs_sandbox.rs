@@ -0,0 +1,222 @@
+use std::path::{ Component, Path, PathBuf };
+use tauri::Manager;
+use tauri_plugin_fs::FsExt;
+
+/// Directory under app data that exports land in when no destination was chosen
+const EXPORTS_DIR: &str = "exports";
+
+/// Resolves `path` relative to `root`, which is created if missing. Absolute paths, drive prefixes and `..`
+/// are refused outright; the deepest part of the result that already exists is canonicalised and must still
+/// be inside `root`, so a symlink planted anywhere along the way cannot lead outside it. `root` itself is
+/// never returned, since no command should write over or delete the whole directory.
+pub fn resolve_within(root: &Path, path: &str) -> Result<PathBuf, String> {
+    if path.contains('\0') {
+        return Err("Path contains a NUL byte".to_string());
+    }
+    let relative = Path::new(path);
+    for component in relative.components() {
+        match component {
+            Component::Normal(_) | Component::CurDir => {}
+            Component::ParentDir => {
+                return Err(format!("Path {} must not contain ..", path));
+            }
+            Component::RootDir | Component::Prefix(_) => {
+                return Err(format!("Path {} must be relative to the app data directory", path));
+            }
+        }
+    }
+
+    std::fs::create_dir_all(root).map_err(|e| format!("Failed to create {}: {}", root.display(), e))?;
+    let root = root.canonicalize().map_err(|e| format!("Failed to resolve {}: {}", root.display(), e))?;
+
+    let mut existing = root.join(relative);
+    let mut missing = Vec::new();
+    while existing.symlink_metadata().is_err() {
+        let Some(name) = existing.file_name().map(|n| n.to_os_string()) else {
+            break;
+        };
+        missing.push(name);
+        existing.pop();
+    }
+    let resolved = existing
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve {}: {}", path, e))?;
+    if !resolved.starts_with(&root) {
+        return Err(format!("Path {} leads outside the app data directory", path));
+    }
+
+    let resolved = missing
+        .into_iter()
+        .rev()
+        .fold(resolved, |p, name| p.join(name));
+    if resolved == root {
+        return Err(format!("Path {} refers to the app data directory itself", path));
+    }
+    Ok(resolved)
+}
+
+pub fn app_data_root(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))
+}
+
+/// A path the frontend gave relative to the app data directory.
+pub fn app_data_path(app: &tauri::AppHandle, path: &str) -> Result<PathBuf, String> {
+    resolve_within(&app_data_root(app)?, path)
+}
+
+/// An absolute path outside app data that the frontend wants written. It must have been picked through the
+/// dialog plugin this session, which adds each chosen file or folder to the fs plugin's runtime scope.
+/// Absolute paths that land inside app data go through the same checks as relative ones.
+pub fn user_path(app: &tauri::AppHandle, path: &str) -> Result<PathBuf, String> {
+    if path.contains('\0') {
+        return Err("Path contains a NUL byte".to_string());
+    }
+    let candidate = Path::new(path);
+    if !candidate.is_absolute() {
+        return Err(format!("Path {} must be absolute", path));
+    }
+    if candidate.components().any(|c| c == Component::ParentDir) {
+        return Err(format!("Path {} must not contain ..", path));
+    }
+
+    let root = app_data_root(app)?;
+    let root = root.canonicalize().unwrap_or(root);
+    let parent = candidate.parent().and_then(|p| p.canonicalize().ok());
+    if let Some(relative) = parent.as_ref().and_then(|p| p.strip_prefix(&root).ok()) {
+        let name = candidate.file_name().ok_or_else(|| format!("Path {} has no file name", path))?;
+        return resolve_within(&root, &relative.join(name).to_string_lossy());
+    }
+
+    let scope = app.fs_scope();
+    if scope.is_allowed(candidate) && !scope.is_forbidden(candidate) {
+        Ok(candidate.to_path_buf())
+    } else {
+        Err(format!("{} was not chosen through a file dialog in this session", path))
+    }
+}
+
+/// A path the frontend wants read: relative ones resolve under app data, absolute ones go through `user_path`.
+pub fn frontend_path(app: &tauri::AppHandle, path: &str) -> Result<PathBuf, String> {
+    if Path::new(path).is_absolute() {
+        user_path(app, path)
+    } else {
+        app_data_path(app, path)
+    }
+}
+
+/// Where an export is written: `out_path` when the user picked one, otherwise `default_name` in the app data exports
+/// directory.
+pub fn export_path(app: &tauri::AppHandle, out_path: Option<&str>, default_name: &str) -> Result<PathBuf, String> {
+    if let Some(path) = out_path {
+        return user_path(app, path);
+    }
+    let path = app_data_path(app, &format!("{}/{}", EXPORTS_DIR, default_name))?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn root() -> (tempfile::TempDir, PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("app");
+        std::fs::create_dir_all(root.join("designs")).unwrap();
+        std::fs::create_dir_all(dir.path().join("outside")).unwrap();
+        let root = root.canonicalize().unwrap();
+        (dir, root)
+    }
+
+    #[test]
+    fn plain_relative_paths_resolve_inside_the_root() {
+        let (_dir, root) = root();
+        assert_eq!(resolve_within(&root, "designs/a.json").unwrap(), root.join("designs/a.json"));
+        assert_eq!(resolve_within(&root, "./designs").unwrap(), root.join("designs"));
+        assert_eq!(resolve_within(&root, "new/deep/file.txt").unwrap(), root.join("new/deep/file.txt"));
+        assert!(!root.join("new").exists());
+    }
+
+    #[test]
+    fn parent_components_are_refused_even_when_they_stay_inside() {
+        let (_dir, root) = root();
+        assert!(resolve_within(&root, "../outside/x").is_err());
+        assert!(resolve_within(&root, "designs/../designs/a.json").is_err());
+        assert!(resolve_within(&root, "..").is_err());
+    }
+
+    #[test]
+    fn absolute_paths_and_nul_bytes_are_refused() {
+        let (dir, root) = root();
+        assert!(resolve_within(&root, "/etc/passwd").is_err());
+        assert!(resolve_within(&root, &root.join("designs/a.json").to_string_lossy()).is_err());
+        assert!(resolve_within(&root, &dir.path().join("outside").to_string_lossy()).is_err());
+        assert!(resolve_within(&root, "designs/a\0.json").is_err());
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn drive_prefixes_are_refused() {
+        let (_dir, root) = root();
+        assert!(resolve_within(&root, "C:\\Windows\\win.ini").is_err());
+        assert!(resolve_within(&root, "C:designs").is_err());
+        assert!(resolve_within(&root, "\\\\server\\share\\file").is_err());
+    }
+
+    #[test]
+    fn the_root_itself_is_never_returned() {
+        let (_dir, root) = root();
+        assert!(resolve_within(&root, "").is_err());
+        assert!(resolve_within(&root, ".").is_err());
+        assert!(resolve_within(&root, "./.").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_out_of_the_root_are_refused_at_any_depth() {
+        let (dir, root) = root();
+        let outside = dir.path().join("outside");
+        std::fs::write(outside.join("secret.txt"), b"secret").unwrap();
+        std::os::unix::fs::symlink(&outside, root.join("designs/escape")).unwrap();
+        std::os::unix::fs::symlink(outside.join("secret.txt"), root.join("leaf.txt")).unwrap();
+
+        assert!(resolve_within(&root, "designs/escape/secret.txt").is_err());
+        assert!(resolve_within(&root, "designs/escape/missing/new.txt").is_err());
+        assert!(resolve_within(&root, "designs/escape").is_err());
+        assert!(resolve_within(&root, "leaf.txt").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_that_stay_inside_the_root_resolve_to_their_target() {
+        let (_dir, root) = root();
+        std::os::unix::fs::symlink(root.join("designs"), root.join("current")).unwrap();
+        assert_eq!(resolve_within(&root, "current/a.json").unwrap(), root.join("designs/a.json"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn dangling_symlinks_are_refused() {
+        let (dir, root) = root();
+        std::os::unix::fs::symlink(dir.path().join("outside/not-yet"), root.join("dangling")).unwrap();
+        std::os::unix::fs::symlink(root.join("missing"), root.join("dangling-inside")).unwrap();
+
+        assert!(resolve_within(&root, "dangling").is_err());
+        assert!(resolve_within(&root, "dangling/file.txt").is_err());
+        assert!(resolve_within(&root, "dangling-inside").is_err());
+        assert!(!dir.path().join("outside/not-yet").exists());
+    }
+
+    #[test]
+    fn a_missing_root_is_created() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("fresh");
+        let resolved = resolve_within(&root, "a.txt").unwrap();
+        assert!(root.is_dir());
+        assert_eq!(resolved, root.canonicalize().unwrap().join("a.txt"));
+    }
+}
@@ -0,0 +1,334 @@
+use crate::comfyui_jobs::ComfyUIProvider;
+use crate::replicate::ReplicateProvider;
+use base64::{ engine::general_purpose, Engine as _ };
+use futures_util::future::BoxFuture;
+use once_cell::sync::Lazy;
+use serde::{ Deserialize, Serialize };
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{ Arc, Mutex, RwLock };
+use tauri::Manager;
+
+/// Configured providers by id; job ids are `<provider id>:<provider job id>`
+type ProviderRegistry = RwLock<HashMap<String, Arc<dyn GenerationProvider>>>;
+
+static GENERATION_PROVIDERS: Lazy<ProviderRegistry> = Lazy::new(|| RwLock::new(HashMap::new()));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobState {
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+    Canceled,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JobStatus {
+    pub id: String,
+    pub state: JobState,
+    /// Fraction complete between 0 and 1, when the provider reports it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl JobStatus {
+    pub fn new(id: &str, state: JobState) -> Self {
+        JobStatus { id: id.to_string(), state, progress: None, message: None, error: None }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GenerationRequest {
+    /// Model name for hosted providers
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub prompt: Option<String>,
+    /// Provider-specific input: the model input for Replicate, the API prompt for ComfyUI
+    #[serde(default)]
+    pub input: Value,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GeneratedImage {
+    #[serde(serialize_with = "as_base64")]
+    pub data: Vec<u8>,
+    #[serde(rename = "mimeType")]
+    pub mime_type: String,
+    pub metadata: Value,
+}
+
+fn as_base64<S: serde::Serializer>(data: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&general_purpose::STANDARD.encode(data))
+}
+
+pub fn image_mime_type(data: &[u8]) -> &'static str {
+    match crate::image_dpi::detect_format(data) {
+        Some(crate::image_dpi::ImageFormat::Png) => "image/png",
+        Some(crate::image_dpi::ImageFormat::Jpeg) => "image/jpeg",
+        Some(crate::image_dpi::ImageFormat::Tiff) => "image/tiff",
+        None if data.starts_with(b"RIFF") && data.get(8..12) == Some(b"WEBP".as_slice()) => "image/webp",
+        None => "application/octet-stream",
+    }
+}
+
+/// A backend that turns a request into images. Job ids are the provider's own; the registry adds the provider prefix.
+pub trait GenerationProvider: Send + Sync {
+    fn submit(&self, request: GenerationRequest) -> BoxFuture<'_, Result<String, String>>;
+    fn status<'a>(&'a self, job_id: &'a str) -> BoxFuture<'a, Result<JobStatus, String>>;
+    fn cancel<'a>(&'a self, job_id: &'a str) -> BoxFuture<'a, Result<(), String>>;
+    fn outputs<'a>(&'a self, job_id: &'a str) -> BoxFuture<'a, Result<Vec<GeneratedImage>, String>>;
+}
+
+/// Poll count, state and prompt of each fake job
+type FakeJobs = Mutex<HashMap<String, (u32, JobState, Option<String>)>>;
+
+/// Provider that finishes every job after a fixed number of status polls, producing a solid-colour PNG.
+pub struct FakeProvider {
+    steps: u32,
+    fail: bool,
+    jobs: FakeJobs,
+}
+
+impl FakeProvider {
+    pub fn new(steps: u32, fail: bool) -> Self {
+        FakeProvider { steps: steps.max(1), fail, jobs: Mutex::new(HashMap::new()) }
+    }
+}
+
+impl GenerationProvider for FakeProvider {
+    fn submit(&self, request: GenerationRequest) -> BoxFuture<'_, Result<String, String>> {
+        Box::pin(async move {
+            let id = uuid::Uuid::new_v4().to_string();
+            self.jobs.lock().unwrap().insert(id.clone(), (0, JobState::Queued, request.prompt));
+            Ok(id)
+        })
+    }
+
+    fn status<'a>(&'a self, job_id: &'a str) -> BoxFuture<'a, Result<JobStatus, String>> {
+        Box::pin(async move {
+            let mut jobs = self.jobs.lock().unwrap();
+            let (polls, state, _) = jobs.get_mut(job_id).ok_or(format!("Unknown job {}", job_id))?;
+            if matches!(state, JobState::Queued | JobState::Running) {
+                *polls += 1;
+                *state = match (*polls >= self.steps, self.fail) {
+                    (false, _) => JobState::Running,
+                    (true, false) => JobState::Succeeded,
+                    (true, true) => JobState::Failed,
+                };
+            }
+            let mut status = JobStatus::new(job_id, *state);
+            status.progress = Some(((*polls as f32) / (self.steps as f32)).min(1.0));
+            if *state == JobState::Failed {
+                status.error = Some("Fake provider configured to fail".to_string());
+            }
+            Ok(status)
+        })
+    }
+
+    fn cancel<'a>(&'a self, job_id: &'a str) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(async move {
+            let mut jobs = self.jobs.lock().unwrap();
+            let (_, state, _) = jobs.get_mut(job_id).ok_or(format!("Unknown job {}", job_id))?;
+            if matches!(state, JobState::Queued | JobState::Running) {
+                *state = JobState::Canceled;
+            }
+            Ok(())
+        })
+    }
+
+    fn outputs<'a>(&'a self, job_id: &'a str) -> BoxFuture<'a, Result<Vec<GeneratedImage>, String>> {
+        Box::pin(async move {
+            let prompt = {
+                let jobs = self.jobs.lock().unwrap();
+                let (_, state, prompt) = jobs.get(job_id).ok_or(format!("Unknown job {}", job_id))?;
+                if *state != JobState::Succeeded {
+                    return Err(format!("Job {} has not succeeded", job_id));
+                }
+                prompt.clone().unwrap_or_default()
+            };
+            let shade = prompt.bytes().fold(0u8, |acc, b| acc.wrapping_mul(31).wrapping_add(b));
+            let image = image::RgbImage::from_pixel(8, 8, image::Rgb([shade, 255 - shade, 128]));
+            let mut data = Vec::new();
+            image::DynamicImage
+                ::ImageRgb8(image)
+                .write_to(&mut std::io::Cursor::new(&mut data), image::ImageOutputFormat::Png)
+                .map_err(|e| format!("Failed to encode image: {}", e))?;
+            Ok(vec![GeneratedImage {
+                data,
+                mime_type: "image/png".to_string(),
+                metadata: serde_json::json!({ "provider": "fake", "prompt": prompt }),
+            }])
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum ProviderConfig {
+    #[serde(rename = "comfyui")]
+    ComfyUI {
+        url: String,
+    },
+    Replicate {
+        /// Name of the stored credential holding the API token
+        credential: String,
+        #[serde(default)]
+        base_url: Option<String>,
+    },
+    Fake {
+        #[serde(default)]
+        steps: Option<u32>,
+        #[serde(default)]
+        fail: bool,
+    },
+}
+
+impl ProviderConfig {
+    fn build(self, app_data_dir: PathBuf) -> Result<Arc<dyn GenerationProvider>, String> {
+        Ok(match self {
+            ProviderConfig::ComfyUI { url } => Arc::new(ComfyUIProvider::new(url)),
+            ProviderConfig::Replicate { credential, base_url } =>
+                Arc::new(ReplicateProvider::new(&credential, base_url, app_data_dir)?),
+            ProviderConfig::Fake { steps, fail } => Arc::new(FakeProvider::new(steps.unwrap_or(3), fail)),
+        })
+    }
+}
+
+pub fn register_provider(id: &str, provider: Arc<dyn GenerationProvider>) {
+    GENERATION_PROVIDERS.write().unwrap().insert(id.to_string(), provider);
+}
+
+fn provider(id: &str) -> Result<Arc<dyn GenerationProvider>, String> {
+    GENERATION_PROVIDERS.read()
+        .unwrap()
+        .get(id)
+        .cloned()
+        .ok_or_else(|| format!("No generation provider named {}", id))
+}
+
+fn split_job_id(job_id: &str) -> Result<(Arc<dyn GenerationProvider>, &str), String> {
+    let (provider_id, id) = job_id.split_once(':').ok_or(format!("Invalid job id {}", job_id))?;
+    Ok((provider(provider_id)?, id))
+}
+
+#[tauri::command]
+pub async fn generation_configure_provider(
+    app: tauri::AppHandle,
+    provider_id: String,
+    config: ProviderConfig
+) -> Result<(), String> {
+    if provider_id.is_empty() || provider_id.contains(':') {
+        return Err(format!("Invalid provider id {}", provider_id));
+    }
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    register_provider(&provider_id, config.build(app_data_dir)?);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn generation_list_providers() -> Result<Vec<String>, String> {
+    let mut ids: Vec<String> = GENERATION_PROVIDERS.read().unwrap().keys().cloned().collect();
+    ids.sort();
+    Ok(ids)
+}
+
+#[tauri::command]
+pub async fn generation_submit(provider_id: String, request: GenerationRequest) -> Result<String, String> {
+    let id = provider(&provider_id)?.submit(request).await?;
+    Ok(format!("{}:{}", provider_id, id))
+}
+
+#[tauri::command]
+pub async fn generation_status(job_id: String) -> Result<JobStatus, String> {
+    let (provider, id) = split_job_id(&job_id)?;
+    let mut status = provider.status(id).await?;
+    status.id = job_id.clone();
+    Ok(status)
+}
+
+#[tauri::command]
+pub async fn generation_cancel(job_id: String) -> Result<(), String> {
+    let (provider, id) = split_job_id(&job_id)?;
+    provider.cancel(id).await
+}
+
+#[tauri::command]
+pub async fn generation_outputs(job_id: String) -> Result<Vec<GeneratedImage>, String> {
+    let (provider, id) = split_job_id(&job_id)?;
+    provider.outputs(id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn request(prompt: &str) -> GenerationRequest {
+        GenerationRequest { model: None, prompt: Some(prompt.to_string()), input: Value::Null }
+    }
+
+    #[tokio::test]
+    async fn fake_jobs_run_through_the_registry_to_an_image() {
+        register_provider("fake-ok", Arc::new(FakeProvider::new(2, false)));
+        let job_id = generation_submit("fake-ok".to_string(), request("a red shirt")).await.unwrap();
+        assert!(job_id.starts_with("fake-ok:"));
+        assert!(generation_outputs(job_id.clone()).await.is_err());
+
+        let running = generation_status(job_id.clone()).await.unwrap();
+        assert_eq!(running.id, job_id);
+        assert_eq!(running.state, JobState::Running);
+        assert_eq!(running.progress, Some(0.5));
+        let done = generation_status(job_id.clone()).await.unwrap();
+        assert_eq!(done.state, JobState::Succeeded);
+        assert_eq!(done.progress, Some(1.0));
+
+        let images = generation_outputs(job_id.clone()).await.unwrap();
+        assert_eq!(images.len(), 1);
+        assert_eq!(images[0].mime_type, "image/png");
+        assert_eq!(image_mime_type(&images[0].data), "image/png");
+        assert_eq!(images[0].metadata, json!({ "provider": "fake", "prompt": "a red shirt" }));
+        let decoded = image::load_from_memory(&images[0].data).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (8, 8));
+
+        let serialized = serde_json::to_value(&images[0]).unwrap();
+        assert_eq!(general_purpose::STANDARD.decode(serialized["data"].as_str().unwrap()).unwrap(), images[0].data);
+        assert_eq!(serialized["mimeType"], "image/png");
+    }
+
+    #[tokio::test]
+    async fn fake_jobs_fail_and_cancel() {
+        register_provider("fake-fail", Arc::new(FakeProvider::new(1, true)));
+        let failing = generation_submit("fake-fail".to_string(), request("x")).await.unwrap();
+        let status = generation_status(failing.clone()).await.unwrap();
+        assert_eq!(status.state, JobState::Failed);
+        assert!(status.error.is_some());
+        assert!(generation_outputs(failing).await.is_err());
+
+        register_provider("fake-slow", Arc::new(FakeProvider::new(5, false)));
+        let slow = generation_submit("fake-slow".to_string(), request("y")).await.unwrap();
+        generation_status(slow.clone()).await.unwrap();
+        generation_cancel(slow.clone()).await.unwrap();
+        assert_eq!(generation_status(slow.clone()).await.unwrap().state, JobState::Canceled);
+        assert!(generation_outputs(slow).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn job_ids_name_a_registered_provider() {
+        assert!(generation_status("no-separator".to_string()).await.is_err());
+        assert!(generation_status("missing:123".to_string()).await.is_err());
+        assert!(generation_submit("missing".to_string(), request("z")).await.is_err());
+        register_provider("fake-ids", Arc::new(FakeProvider::new(1, false)));
+        assert!(generation_status("fake-ids:unknown".to_string()).await.is_err());
+    }
+}
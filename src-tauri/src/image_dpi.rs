@@ -0,0 +1,724 @@
+use serde::{ Deserialize, Serialize };
+
+pub const PNG_SIGNATURE: [u8; 8] = [137, 80, 78, 71, 13, 10, 26, 10];
+const TAG_X_RESOLUTION: u16 = 282;
+const TAG_Y_RESOLUTION: u16 = 283;
+const TAG_RESOLUTION_UNIT: u16 = 296;
+const TIFF_SHORT: u16 = 3;
+const TIFF_RATIONAL: u16 = 5;
+
+/// Chunk type and the raw chunk including length and CRC
+pub type PngChunk<'a> = (&'a [u8; 4], &'a [u8]);
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+    Tiff,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageDpi {
+    pub format: ImageFormat,
+    #[serde(rename = "dpiX")]
+    pub dpi_x: f64,
+    #[serde(rename = "dpiY")]
+    pub dpi_y: f64,
+    pub source: String,
+}
+
+pub fn detect_format(data: &[u8]) -> Option<ImageFormat> {
+    if data.starts_with(&PNG_SIGNATURE) {
+        Some(ImageFormat::Png)
+    } else if data.starts_with(&[0xff, 0xd8]) {
+        Some(ImageFormat::Jpeg)
+    } else if data.starts_with(b"II*\0") || data.starts_with(b"MM\0*") {
+        Some(ImageFormat::Tiff)
+    } else {
+        None
+    }
+}
+
+/// Writes resolution metadata into a PNG, JPEG or TIFF file without touching the image data.
+pub fn set_dpi(data: &[u8], dpi_x: f64, dpi_y: f64) -> Result<Vec<u8>, String> {
+    if !(dpi_x > 0.0 && dpi_y > 0.0) {
+        return Err("DPI must be positive".to_string());
+    }
+    match detect_format(data) {
+        Some(ImageFormat::Png) => png_set_dpi(data, dpi_x, dpi_y),
+        Some(ImageFormat::Jpeg) => jpeg_set_dpi(data, dpi_x, dpi_y),
+        Some(ImageFormat::Tiff) => tiff_set_dpi(data, dpi_x, dpi_y),
+        None => Err("Unsupported image format for DPI metadata".to_string()),
+    }
+}
+
+pub fn read_dpi(data: &[u8]) -> Result<Option<ImageDpi>, String> {
+    match detect_format(data) {
+        Some(ImageFormat::Png) => png_read_dpi(data),
+        Some(ImageFormat::Jpeg) => jpeg_read_dpi(data),
+        Some(ImageFormat::Tiff) =>
+            Ok(
+                tiff_read_dpi(data)?.map(|(dpi_x, dpi_y)| ImageDpi {
+                    format: ImageFormat::Tiff,
+                    dpi_x,
+                    dpi_y,
+                    source: "TIFF".to_string(),
+                })
+            ),
+        None => Err("Unsupported image format for DPI metadata".to_string()),
+    }
+}
+
+pub fn png_chunks(data: &[u8]) -> Result<Vec<PngChunk<'_>>, String> {
+    let mut chunks = Vec::new();
+    let mut cursor = PNG_SIGNATURE.len();
+    while cursor + 12 <= data.len() {
+        let length = u32::from_be_bytes([data[cursor], data[cursor + 1], data[cursor + 2], data[cursor + 3]]) as usize;
+        let end = cursor + 12 + length;
+        if end > data.len() {
+            return Err("Truncated PNG chunk".to_string());
+        }
+        let chunk_type: &[u8; 4] = data[cursor + 4..cursor + 8].try_into().unwrap();
+        chunks.push((chunk_type, &data[cursor..end]));
+        cursor = end;
+        if chunk_type == b"IEND" {
+            break;
+        }
+    }
+    Ok(chunks)
+}
+
+pub fn png_chunk(chunk_type: &[u8; 4], payload: &[u8]) -> Vec<u8> {
+    let mut chunk = Vec::with_capacity(payload.len() + 12);
+    chunk.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+    chunk.extend_from_slice(chunk_type);
+    chunk.extend_from_slice(payload);
+    let mut hasher = crc32fast::Hasher::new();
+    hasher.update(chunk_type);
+    hasher.update(payload);
+    chunk.extend_from_slice(&hasher.finalize().to_be_bytes());
+    chunk
+}
+
+fn png_set_dpi(data: &[u8], dpi_x: f64, dpi_y: f64) -> Result<Vec<u8>, String> {
+    let ppm_x = (dpi_x / 0.0254).round() as u32;
+    let ppm_y = (dpi_y / 0.0254).round() as u32;
+    let mut payload = Vec::with_capacity(9);
+    payload.extend_from_slice(&ppm_x.to_be_bytes());
+    payload.extend_from_slice(&ppm_y.to_be_bytes());
+    payload.push(1);
+    let phys = png_chunk(b"pHYs", &payload);
+
+    let chunks = png_chunks(data)?;
+    if chunks.first().map(|(t, _)| *t) != Some(b"IHDR") {
+        return Err("PNG does not start with IHDR".to_string());
+    }
+    let mut output = Vec::with_capacity(data.len() + phys.len());
+    output.extend_from_slice(&PNG_SIGNATURE);
+    for (chunk_type, bytes) in chunks {
+        if chunk_type == b"pHYs" {
+            continue;
+        }
+        output.extend_from_slice(bytes);
+        if chunk_type == b"IHDR" {
+            output.extend_from_slice(&phys);
+        }
+    }
+    Ok(output)
+}
+
+fn png_read_dpi(data: &[u8]) -> Result<Option<ImageDpi>, String> {
+    for (chunk_type, bytes) in png_chunks(data)? {
+        if chunk_type != b"pHYs" || bytes.len() < 21 {
+            continue;
+        }
+        let payload = &bytes[8..17];
+        if payload[8] != 1 {
+            return Ok(None);
+        }
+        let ppm_x = u32::from_be_bytes([payload[0], payload[1], payload[2], payload[3]]) as f64;
+        let ppm_y = u32::from_be_bytes([payload[4], payload[5], payload[6], payload[7]]) as f64;
+        return Ok(
+            Some(ImageDpi {
+                format: ImageFormat::Png,
+                dpi_x: (ppm_x * 2.54).round() / 100.0,
+                dpi_y: (ppm_y * 2.54).round() / 100.0,
+                source: "pHYs".to_string(),
+            })
+        );
+    }
+    Ok(None)
+}
+
+/// JPEG marker segments before the start of scan, as (marker, offset of the marker, total length).
+pub fn jpeg_segments(data: &[u8]) -> Result<Vec<(u8, usize, usize)>, String> {
+    let mut segments = Vec::new();
+    let mut cursor = 2;
+    while cursor + 4 <= data.len() {
+        if data[cursor] != 0xff {
+            return Err(format!("Invalid JPEG marker at byte {}", cursor));
+        }
+        let marker = data[cursor + 1];
+        if marker == 0xff {
+            cursor += 1;
+            continue;
+        }
+        if marker == 0xda || marker == 0xd9 {
+            break;
+        }
+        let length = u16::from_be_bytes([data[cursor + 2], data[cursor + 3]]) as usize;
+        if length < 2 {
+            return Err(format!("Invalid JPEG segment length at byte {}", cursor));
+        }
+        if cursor + 2 + length > data.len() {
+            return Err("Truncated JPEG segment".to_string());
+        }
+        segments.push((marker, cursor, length + 2));
+        cursor += length + 2;
+    }
+    Ok(segments)
+}
+
+fn jfif_segment<'a>(data: &'a [u8], segments: &[(u8, usize, usize)]) -> Option<(usize, &'a [u8])> {
+    segments
+        .iter()
+        .find(|(marker, offset, len)| *marker == 0xe0 && *len >= 16 && &data[offset + 4..offset + 9] == b"JFIF\0")
+        .map(|(_, offset, len)| (*offset, &data[*offset..offset + len]))
+}
+
+fn exif_segment(data: &[u8], segments: &[(u8, usize, usize)]) -> Option<(usize, usize)> {
+    segments
+        .iter()
+        .find(|(marker, offset, len)| *marker == 0xe1 && *len >= 10 && &data[offset + 4..offset + 10] == b"Exif\0\0")
+        .map(|(_, offset, len)| (offset + 10, offset + len))
+}
+
+fn jpeg_set_dpi(data: &[u8], dpi_x: f64, dpi_y: f64) -> Result<Vec<u8>, String> {
+    let density_x = dpi_x.round().clamp(1.0, u16::MAX as f64) as u16;
+    let density_y = dpi_y.round().clamp(1.0, u16::MAX as f64) as u16;
+    let segments = jpeg_segments(data)?;
+    let mut output = data.to_vec();
+
+    match jfif_segment(data, &segments) {
+        Some((offset, _)) => {
+            output[offset + 11] = 1;
+            output[offset + 12..offset + 14].copy_from_slice(&density_x.to_be_bytes());
+            output[offset + 14..offset + 16].copy_from_slice(&density_y.to_be_bytes());
+        }
+        None => {
+            let mut app0 = vec![0xff, 0xe0, 0x00, 0x10];
+            app0.extend_from_slice(b"JFIF\0");
+            app0.extend_from_slice(&[1, 1, 1]);
+            app0.extend_from_slice(&density_x.to_be_bytes());
+            app0.extend_from_slice(&density_y.to_be_bytes());
+            app0.extend_from_slice(&[0, 0]);
+            output.splice(2..2, app0);
+        }
+    }
+
+    if let Some((start, end)) = exif_segment(&output, &jpeg_segments(&output)?) {
+        tiff_update_in_place(&mut output[start..end], dpi_x, dpi_y).ok();
+    }
+    Ok(output)
+}
+
+fn jpeg_read_dpi(data: &[u8]) -> Result<Option<ImageDpi>, String> {
+    let segments = jpeg_segments(data)?;
+    if let Some((_, segment)) = jfif_segment(data, &segments) {
+        let units = segment[11];
+        let density_x = u16::from_be_bytes([segment[12], segment[13]]) as f64;
+        let density_y = u16::from_be_bytes([segment[14], segment[15]]) as f64;
+        let scale = match units {
+            1 => Some(1.0),
+            2 => Some(2.54),
+            _ => None,
+        };
+        if let Some(scale) = scale {
+            return Ok(
+                Some(ImageDpi {
+                    format: ImageFormat::Jpeg,
+                    dpi_x: density_x * scale,
+                    dpi_y: density_y * scale,
+                    source: "JFIF".to_string(),
+                })
+            );
+        }
+    }
+    if let Some((start, end)) = exif_segment(data, &segments) {
+        if let Some((dpi_x, dpi_y)) = tiff_read_dpi(&data[start..end])? {
+            return Ok(
+                Some(ImageDpi {
+                    format: ImageFormat::Jpeg,
+                    dpi_x,
+                    dpi_y,
+                    source: "EXIF".to_string(),
+                })
+            );
+        }
+    }
+    Ok(None)
+}
+
+struct TiffEntry {
+    tag: u16,
+    field_type: u16,
+    count: u32,
+    /// Offset of the 4-byte value/offset field of the entry
+    value_pos: usize,
+}
+
+struct Tiff {
+    little_endian: bool,
+    ifd_offset: usize,
+    entries: Vec<TiffEntry>,
+}
+
+impl Tiff {
+    fn parse(data: &[u8]) -> Result<Self, String> {
+        if data.len() < 8 {
+            return Err("Truncated TIFF header".to_string());
+        }
+        let little_endian = match &data[0..2] {
+            b"II" => true,
+            b"MM" => false,
+            _ => {
+                return Err("Invalid TIFF byte order".to_string());
+            }
+        };
+        let mut tiff = Tiff { little_endian, ifd_offset: 0, entries: Vec::new() };
+        tiff.ifd_offset = tiff.u32_at(data, 4)? as usize;
+        let count = tiff.u16_at(data, tiff.ifd_offset)? as usize;
+        for i in 0..count {
+            let pos = tiff.ifd_offset + 2 + i * 12;
+            tiff.entries.push(TiffEntry {
+                tag: tiff.u16_at(data, pos)?,
+                field_type: tiff.u16_at(data, pos + 2)?,
+                count: tiff.u32_at(data, pos + 4)?,
+                value_pos: pos + 8,
+            });
+        }
+        Ok(tiff)
+    }
+
+    fn u16_at(&self, data: &[u8], pos: usize) -> Result<u16, String> {
+        let bytes: [u8; 2] = data
+            .get(pos..pos + 2)
+            .ok_or("Truncated TIFF data")?
+            .try_into()
+            .unwrap();
+        Ok(if self.little_endian { u16::from_le_bytes(bytes) } else { u16::from_be_bytes(bytes) })
+    }
+
+    fn u32_at(&self, data: &[u8], pos: usize) -> Result<u32, String> {
+        let bytes: [u8; 4] = data
+            .get(pos..pos + 4)
+            .ok_or("Truncated TIFF data")?
+            .try_into()
+            .unwrap();
+        Ok(if self.little_endian { u32::from_le_bytes(bytes) } else { u32::from_be_bytes(bytes) })
+    }
+
+    fn u16_bytes(&self, value: u16) -> [u8; 2] {
+        if self.little_endian { value.to_le_bytes() } else { value.to_be_bytes() }
+    }
+
+    fn u32_bytes(&self, value: u32) -> [u8; 4] {
+        if self.little_endian { value.to_le_bytes() } else { value.to_be_bytes() }
+    }
+
+    fn entry(&self, tag: u16) -> Option<&TiffEntry> {
+        self.entries.iter().find(|e| e.tag == tag)
+    }
+
+    fn rational(&self, data: &[u8], entry: &TiffEntry) -> Result<f64, String> {
+        let offset = self.u32_at(data, entry.value_pos)? as usize;
+        let numerator = self.u32_at(data, offset)? as f64;
+        let denominator = self.u32_at(data, offset + 4)? as f64;
+        match denominator == 0.0 {
+            true => Err("Invalid TIFF resolution".to_string()),
+            false => Ok(numerator / denominator),
+        }
+    }
+
+    fn rational_bytes(&self, value: f64) -> [u8; 8] {
+        let (numerator, denominator) = match value.fract() == 0.0 {
+            true => (value as u32, 1),
+            false => ((value * 1000.0).round() as u32, 1000),
+        };
+        let mut bytes = [0u8; 8];
+        bytes[..4].copy_from_slice(&self.u32_bytes(numerator));
+        bytes[4..].copy_from_slice(&self.u32_bytes(denominator));
+        bytes
+    }
+}
+
+fn tiff_read_dpi(data: &[u8]) -> Result<Option<(f64, f64)>, String> {
+    let tiff = Tiff::parse(data)?;
+    let (Some(x), Some(y)) = (tiff.entry(TAG_X_RESOLUTION), tiff.entry(TAG_Y_RESOLUTION)) else {
+        return Ok(None);
+    };
+    let unit = match tiff.entry(TAG_RESOLUTION_UNIT) {
+        Some(entry) => tiff.u16_at(data, entry.value_pos)?,
+        None => 2,
+    };
+    let scale = match unit {
+        2 => 1.0,
+        3 => 2.54,
+        _ => {
+            return Ok(None);
+        }
+    };
+    Ok(Some((tiff.rational(data, x)? * scale, tiff.rational(data, y)? * scale)))
+}
+
+/// Overwrites existing resolution tags, returning `false` if one of them is missing.
+fn tiff_update_in_place(data: &mut [u8], dpi_x: f64, dpi_y: f64) -> Result<bool, String> {
+    let tiff = Tiff::parse(data)?;
+    let (Some(x), Some(y), Some(unit)) = (
+        tiff.entry(TAG_X_RESOLUTION),
+        tiff.entry(TAG_Y_RESOLUTION),
+        tiff.entry(TAG_RESOLUTION_UNIT),
+    ) else {
+        return Ok(false);
+    };
+    if x.field_type != TIFF_RATIONAL || y.field_type != TIFF_RATIONAL || unit.field_type != TIFF_SHORT || x.count != 1 || y.count != 1 {
+        return Ok(false);
+    }
+    for (entry, dpi) in [(x, dpi_x), (y, dpi_y)] {
+        let offset = tiff.u32_at(data, entry.value_pos)? as usize;
+        data.get_mut(offset..offset + 8)
+            .ok_or("Truncated TIFF data")?
+            .copy_from_slice(&tiff.rational_bytes(dpi));
+    }
+    data[unit.value_pos..unit.value_pos + 2].copy_from_slice(&tiff.u16_bytes(2));
+    Ok(true)
+}
+
+fn tiff_set_dpi(data: &[u8], dpi_x: f64, dpi_y: f64) -> Result<Vec<u8>, String> {
+    let mut output = data.to_vec();
+    if tiff_update_in_place(&mut output, dpi_x, dpi_y)? {
+        return Ok(output);
+    }
+
+    // Missing tags need a larger IFD, so append a rewritten copy of IFD0 and point the header at it.
+    let tiff = Tiff::parse(data)?;
+    let next_ifd = tiff.u32_at(data, tiff.ifd_offset + 2 + tiff.entries.len() * 12)?;
+    if output.len() % 2 == 1 {
+        output.push(0);
+    }
+    let rationals_offset = output.len();
+    output.extend_from_slice(&tiff.rational_bytes(dpi_x));
+    output.extend_from_slice(&tiff.rational_bytes(dpi_y));
+    let ifd_offset = output.len();
+
+    let mut entries: Vec<(u16, [u8; 12])> = tiff.entries
+        .iter()
+        .filter(|e| ![TAG_X_RESOLUTION, TAG_Y_RESOLUTION, TAG_RESOLUTION_UNIT].contains(&e.tag))
+        .map(|e| {
+            let start = e.value_pos - 8;
+            (e.tag, data[start..start + 12].try_into().unwrap())
+        })
+        .collect();
+    let mut new_entry = |tag: u16, field_type: u16, value: [u8; 4]| {
+        let mut bytes = [0u8; 12];
+        bytes[0..2].copy_from_slice(&tiff.u16_bytes(tag));
+        bytes[2..4].copy_from_slice(&tiff.u16_bytes(field_type));
+        bytes[4..8].copy_from_slice(&tiff.u32_bytes(1));
+        bytes[8..12].copy_from_slice(&value);
+        entries.push((tag, bytes));
+    };
+    new_entry(TAG_X_RESOLUTION, TIFF_RATIONAL, tiff.u32_bytes(rationals_offset as u32));
+    new_entry(TAG_Y_RESOLUTION, TIFF_RATIONAL, tiff.u32_bytes((rationals_offset + 8) as u32));
+    let mut unit = [0u8; 4];
+    unit[..2].copy_from_slice(&tiff.u16_bytes(2));
+    new_entry(TAG_RESOLUTION_UNIT, TIFF_SHORT, unit);
+    entries.sort_by_key(|(tag, _)| *tag);
+
+    output.extend_from_slice(&tiff.u16_bytes(entries.len() as u16));
+    for (_, bytes) in &entries {
+        output.extend_from_slice(bytes);
+    }
+    output.extend_from_slice(&tiff.u32_bytes(next_ifd));
+    let header = tiff.u32_bytes(ifd_offset as u32);
+    output[4..8].copy_from_slice(&header);
+    Ok(output)
+}
+
+#[tauri::command]
+pub fn read_image_dpi(data: Vec<u8>) -> Result<Option<ImageDpi>, String> {
+    read_dpi(&data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encoded(format: image::ImageOutputFormat) -> Vec<u8> {
+        let pixels = image::RgbImage::from_fn(8, 8, |x, y| image::Rgb([x as u8 * 30, y as u8 * 30, 90]));
+        let mut out = std::io::Cursor::new(Vec::new());
+        image::DynamicImage::ImageRgb8(pixels).write_to(&mut out, format).unwrap();
+        out.into_inner()
+    }
+
+    fn pixels(data: &[u8]) -> Vec<u8> {
+        image::load_from_memory(data).unwrap().into_bytes()
+    }
+
+    fn chunk_types(png: &[u8]) -> Vec<String> {
+        png_chunks(png)
+            .unwrap()
+            .iter()
+            .map(|(chunk_type, _)| String::from_utf8_lossy(*chunk_type).to_string())
+            .collect()
+    }
+
+    fn chunks_except_phys(png: &[u8]) -> Vec<&[u8]> {
+        png_chunks(png)
+            .unwrap()
+            .into_iter()
+            .filter(|(chunk_type, _)| *chunk_type != b"pHYs")
+            .map(|(_, bytes)| bytes)
+            .collect()
+    }
+
+    /// A PNG with a pHYs of `ppm` pixels per `unit` and a tEXt chunk, both ahead of the image data
+    fn png_with_phys(ppm: u32, unit: u8) -> Vec<u8> {
+        let png = encoded(image::ImageOutputFormat::Png);
+        let mut payload = ppm.to_be_bytes().to_vec();
+        payload.extend_from_slice(&ppm.to_be_bytes());
+        payload.push(unit);
+        let mut output = PNG_SIGNATURE.to_vec();
+        for (chunk_type, bytes) in png_chunks(&png).unwrap() {
+            if chunk_type == b"IDAT" && !output.windows(4).any(|w| w == b"pHYs") {
+                output.extend_from_slice(&png_chunk(b"tEXt", b"Software\0Coin-Op"));
+                output.extend_from_slice(&png_chunk(b"pHYs", &payload));
+            }
+            output.extend_from_slice(bytes);
+        }
+        output
+    }
+
+    /// Everything from the start of scan on, which DPI changes must leave alone
+    fn scan_data(jpeg: &[u8]) -> &[u8] {
+        let start = jpeg.windows(2).position(|w| w == [0xff, 0xda]).unwrap();
+        &jpeg[start..]
+    }
+
+    fn without_app0(jpeg: &[u8]) -> Vec<u8> {
+        let segments = jpeg_segments(jpeg).unwrap();
+        let (_, offset, len) = segments.iter().find(|(marker, ..)| *marker == 0xe0).copied().unwrap();
+        [&jpeg[..offset], &jpeg[offset + len..]].concat()
+    }
+
+    /// An APP1 segment holding an EXIF TIFF with 72 dpi resolution tags
+    fn exif_app1() -> Vec<u8> {
+        let mut tiff = b"II*\0".to_vec();
+        tiff.extend_from_slice(&8u32.to_le_bytes());
+        tiff.extend_from_slice(&3u16.to_le_bytes());
+        let entries = [
+            (TAG_X_RESOLUTION, TIFF_RATIONAL, 50u32),
+            (TAG_Y_RESOLUTION, TIFF_RATIONAL, 58),
+            (TAG_RESOLUTION_UNIT, TIFF_SHORT, 2),
+        ];
+        for (tag, field_type, value) in entries {
+            tiff.extend_from_slice(&tag.to_le_bytes());
+            tiff.extend_from_slice(&field_type.to_le_bytes());
+            tiff.extend_from_slice(&1u32.to_le_bytes());
+            tiff.extend_from_slice(&value.to_le_bytes());
+        }
+        tiff.extend_from_slice(&0u32.to_le_bytes());
+        for _ in 0..2 {
+            tiff.extend_from_slice(&72u32.to_le_bytes());
+            tiff.extend_from_slice(&1u32.to_le_bytes());
+        }
+        let mut segment = vec![0xff, 0xe1];
+        segment.extend_from_slice(&((tiff.len() + 8) as u16).to_be_bytes());
+        segment.extend_from_slice(b"Exif\0\0");
+        segment.extend_from_slice(&tiff);
+        segment
+    }
+
+    const STRIP: [u8; 4] = [10, 20, 30, 40];
+
+    /// A 2×2 greyscale TIFF, with resolution tags in dots per centimetre when `dpcm` is given
+    fn tiff(little_endian: bool, dpcm: Option<f64>) -> Vec<u8> {
+        let header = Tiff { little_endian, ifd_offset: 8, entries: Vec::new() };
+        let mut entries: Vec<(u16, u16, u32)> = vec![
+            (256, TIFF_SHORT, 2),
+            (257, TIFF_SHORT, 2),
+            (258, TIFF_SHORT, 8),
+            (259, TIFF_SHORT, 1),
+            (262, TIFF_SHORT, 1),
+            (273, 4, 0),
+            (278, TIFF_SHORT, 2),
+            (279, 4, STRIP.len() as u32),
+        ];
+        if dpcm.is_some() {
+            entries.extend([(282, TIFF_RATIONAL, 0), (283, TIFF_RATIONAL, 0), (296, TIFF_SHORT, 3)]);
+        }
+        let after_ifd = 8 + 2 + entries.len() * 12 + 4;
+        let rationals = after_ifd as u32;
+        let strip = if dpcm.is_some() { after_ifd + 16 } else { after_ifd } as u32;
+
+        let mut data = if little_endian { b"II*\0".to_vec() } else { b"MM\0*".to_vec() };
+        data.extend_from_slice(&header.u32_bytes(8));
+        data.extend_from_slice(&header.u16_bytes(entries.len() as u16));
+        for (tag, field_type, value) in entries {
+            let value = match tag {
+                273 => strip,
+                282 => rationals,
+                283 => rationals + 8,
+                _ => value,
+            };
+            data.extend_from_slice(&header.u16_bytes(tag));
+            data.extend_from_slice(&header.u16_bytes(field_type));
+            data.extend_from_slice(&header.u32_bytes(1));
+            let mut field = [0u8; 4];
+            match field_type {
+                TIFF_SHORT => field[..2].copy_from_slice(&header.u16_bytes(value as u16)),
+                _ => field.copy_from_slice(&header.u32_bytes(value)),
+            }
+            data.extend_from_slice(&field);
+        }
+        data.extend_from_slice(&header.u32_bytes(0));
+        if let Some(dpcm) = dpcm {
+            data.extend_from_slice(&header.rational_bytes(dpcm));
+            data.extend_from_slice(&header.rational_bytes(dpcm));
+        }
+        data.extend_from_slice(&STRIP);
+        data
+    }
+
+    fn strip_of(data: &[u8]) -> &[u8] {
+        let tiff = Tiff::parse(data).unwrap();
+        let offset = tiff.u32_at(data, tiff.entry(273).unwrap().value_pos).unwrap() as usize;
+        &data[offset..offset + STRIP.len()]
+    }
+
+    #[test]
+    fn png_phys_is_replaced_and_other_chunks_are_kept() {
+        let original = png_with_phys(2835, 1);
+        assert_eq!(read_dpi(&original).unwrap().unwrap().dpi_x, 72.01);
+
+        let stamped = set_dpi(&original, 300.0, 150.0).unwrap();
+        assert_eq!(chunk_types(&stamped)[..3], ["IHDR", "pHYs", "tEXt"]);
+        assert_eq!(chunk_types(&stamped).iter().filter(|t| *t == "pHYs").count(), 1);
+        assert_eq!(chunks_except_phys(&stamped), chunks_except_phys(&original));
+        let (_, phys) = png_chunks(&stamped).unwrap()[1];
+        assert_eq!(phys, png_chunk(b"pHYs", &[0, 0, 0x2e, 0x23, 0, 0, 0x17, 0x12, 1]));
+
+        // pHYs holds whole pixels per metre, so 150 dpi comes back as 5906 ppm
+        let dpi = read_dpi(&stamped).unwrap().unwrap();
+        assert_eq!((dpi.format, dpi.dpi_x, dpi.dpi_y, dpi.source.as_str()), (ImageFormat::Png, 300.0, 150.01, "pHYs"));
+        assert_eq!(pixels(&stamped), pixels(&original));
+        assert_eq!(set_dpi(&stamped, 300.0, 150.0).unwrap(), stamped);
+    }
+
+    #[test]
+    fn png_without_a_physical_unit_has_no_dpi() {
+        assert!(read_dpi(&encoded(image::ImageOutputFormat::Png)).unwrap().is_none());
+        assert!(read_dpi(&png_with_phys(2835, 0)).unwrap().is_none());
+        let not_ihdr = [&PNG_SIGNATURE[..], &png_chunk(b"tEXt", b"a\0b")].concat();
+        assert_eq!(set_dpi(&not_ihdr, 300.0, 300.0).unwrap_err(), "PNG does not start with IHDR");
+    }
+
+    #[test]
+    fn jfif_density_is_rewritten_in_place() {
+        let original = encoded(image::ImageOutputFormat::Jpeg(90));
+        let stamped = set_dpi(&original, 300.0, 150.0).unwrap();
+
+        assert_eq!(stamped.len(), original.len());
+        let (_, offset, _) = jpeg_segments(&stamped).unwrap()[0];
+        assert_eq!(&stamped[offset + 4..offset + 9], b"JFIF\0");
+        assert_eq!(stamped[offset + 11..offset + 16], [1, 0x01, 0x2c, 0x00, 0x96]);
+        let changed: Vec<usize> = (0..original.len()).filter(|&i| original[i] != stamped[i]).collect();
+        assert!(changed.iter().all(|i| (offset + 11..offset + 16).contains(i)), "{:?}", changed);
+
+        let dpi = read_dpi(&stamped).unwrap().unwrap();
+        assert_eq!((dpi.dpi_x, dpi.dpi_y, dpi.source.as_str()), (300.0, 150.0, "JFIF"));
+        assert_eq!(pixels(&stamped), pixels(&original));
+    }
+
+    #[test]
+    fn exif_only_jpegs_get_jfif_and_updated_exif_resolution() {
+        let bare = without_app0(&encoded(image::ImageOutputFormat::Jpeg(90)));
+        let original = [&bare[..2], &exif_app1(), &bare[2..]].concat();
+        let dpi = read_dpi(&original).unwrap().unwrap();
+        assert_eq!((dpi.dpi_x, dpi.dpi_y, dpi.source.as_str()), (72.0, 72.0, "EXIF"));
+
+        let stamped = set_dpi(&original, 300.0, 150.5).unwrap();
+        let markers: Vec<u8> = jpeg_segments(&stamped).unwrap().iter().map(|(marker, ..)| *marker).collect();
+        assert_eq!(markers[..2], [0xe0, 0xe1]);
+        assert_eq!(stamped.len(), original.len() + 18);
+        let (start, end) = exif_segment(&stamped, &jpeg_segments(&stamped).unwrap()).unwrap();
+        assert_eq!(tiff_read_dpi(&stamped[start..end]).unwrap(), Some((300.0, 150.5)));
+        assert_eq!(read_dpi(&stamped).unwrap().unwrap().dpi_y, 151.0, "JFIF densities are whole numbers");
+        assert_eq!(scan_data(&stamped), scan_data(&original));
+        assert_eq!(pixels(&stamped), pixels(&original));
+    }
+
+    #[test]
+    fn jpegs_without_jfif_or_exif_get_a_new_app0() {
+        let original = without_app0(&encoded(image::ImageOutputFormat::Jpeg(90)));
+        assert!(read_dpi(&original).unwrap().is_none());
+
+        let stamped = set_dpi(&original, 240.0, 240.0).unwrap();
+        let mut app0 = vec![0xff, 0xe0, 0x00, 0x10];
+        app0.extend_from_slice(b"JFIF\0");
+        app0.extend_from_slice(&[1, 1, 1, 0, 240, 0, 240, 0, 0]);
+        assert_eq!(stamped, [&original[..2], &app0, &original[2..]].concat());
+        assert_eq!(read_dpi(&stamped).unwrap().unwrap().dpi_x, 240.0);
+        assert_eq!(pixels(&stamped), pixels(&original));
+
+        let mut dpcm = stamped.clone();
+        dpcm[13] = 2;
+        assert!((read_dpi(&dpcm).unwrap().unwrap().dpi_x - 609.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn little_endian_tiffs_without_resolution_get_a_rewritten_ifd() {
+        let original = tiff(true, None);
+        assert!(read_dpi(&original).unwrap().is_none());
+
+        let stamped = set_dpi(&original, 300.0, 150.0).unwrap();
+        assert_eq!(stamped[..4], original[..4]);
+        assert_eq!(stamped[8..original.len()], original[8..], "the old IFD and the image data stay where they were");
+        let tiff = Tiff::parse(&stamped).unwrap();
+        assert!(tiff.ifd_offset >= original.len() && tiff.ifd_offset.is_multiple_of(2));
+        let tags: Vec<u16> = tiff.entries.iter().map(|e| e.tag).collect();
+        assert_eq!(tags, [256, 257, 258, 259, 262, 273, 278, 279, 282, 283, 296]);
+
+        let dpi = read_dpi(&stamped).unwrap().unwrap();
+        assert_eq!((dpi.format, dpi.dpi_x, dpi.dpi_y), (ImageFormat::Tiff, 300.0, 150.0));
+        assert_eq!(strip_of(&stamped), STRIP);
+    }
+
+    #[test]
+    fn big_endian_tiff_resolution_is_updated_in_place() {
+        let original = tiff(false, Some(118.11));
+        let dpi = read_dpi(&original).unwrap().unwrap();
+        assert!((dpi.dpi_x - 299.9994).abs() < 1e-9, "{}", dpi.dpi_x);
+
+        let stamped = set_dpi(&original, 300.0, 600.0).unwrap();
+        assert_eq!(stamped.len(), original.len());
+        let tiff = Tiff::parse(&stamped).unwrap();
+        assert_eq!(tiff.ifd_offset, 8);
+        let unit = tiff.entry(TAG_RESOLUTION_UNIT).unwrap();
+        assert_eq!(stamped[unit.value_pos..unit.value_pos + 2], [0, 2]);
+        let x = tiff.u32_at(&stamped, tiff.entry(TAG_X_RESOLUTION).unwrap().value_pos).unwrap() as usize;
+        assert_eq!(stamped[x..x + 16], [0, 0, 1, 44, 0, 0, 0, 1, 0, 0, 2, 88, 0, 0, 0, 1]);
+        assert_eq!(read_dpi(&stamped).unwrap().unwrap().dpi_y, 600.0);
+        assert_eq!(strip_of(&stamped), STRIP);
+    }
+
+    #[test]
+    fn unsupported_data_and_bad_dpi_are_refused() {
+        assert_eq!(read_dpi(b"GIF89a").unwrap_err(), "Unsupported image format for DPI metadata");
+        assert_eq!(set_dpi(b"GIF89a", 300.0, 300.0).unwrap_err(), "Unsupported image format for DPI metadata");
+        let png = encoded(image::ImageOutputFormat::Png);
+        for (x, y) in [(0.0, 300.0), (300.0, -1.0), (f64::NAN, 300.0)] {
+            assert_eq!(set_dpi(&png, x, y).unwrap_err(), "DPI must be positive");
+        }
+    }
+}
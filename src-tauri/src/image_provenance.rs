@@ -0,0 +1,509 @@
+use crate::image_dpi::{ detect_format, jpeg_segments, png_chunk, png_chunks, ImageFormat, PNG_SIGNATURE };
+use serde::{ Deserialize, Serialize };
+use serde_json::Value;
+use sha2::{ Digest, Sha256 };
+use std::io::Read;
+
+const PROVENANCE_KEY: &str = "coinop:provenance";
+const COMFY_PROMPT_KEY: &str = "prompt";
+const COMFY_WORKFLOW_KEY: &str = "workflow";
+const XMP_HEADER: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+const XMP_NAMESPACE: &str = "https://coinop.themanufactory.xyz/ns/provenance/1.0/";
+const MAX_JPEG_SEGMENT: usize = 65533;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Provenance {
+    pub provider: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(rename = "workflowHash", default, skip_serializing_if = "Option::is_none")]
+    pub workflow_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+    #[serde(rename = "comfyPrompt", default, skip_serializing_if = "Option::is_none")]
+    pub comfy_prompt: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workflow: Option<Value>,
+}
+
+impl Provenance {
+    /// The compact record stored under our own key, without the ComfyUI graphs which are stored separately.
+    fn record(&self) -> Provenance {
+        Provenance { comfy_prompt: None, workflow: None, ..self.clone() }
+    }
+
+    fn completed(&self) -> Provenance {
+        let mut provenance = self.clone();
+        if provenance.workflow_hash.is_none() {
+            provenance.workflow_hash = self.workflow
+                .as_ref()
+                .or(self.comfy_prompt.as_ref())
+                .map(workflow_hash);
+        }
+        provenance.timestamp.get_or_insert_with(|| chrono::Utc::now().to_rfc3339());
+        provenance
+    }
+}
+
+pub fn workflow_hash(workflow: &Value) -> String {
+    let digest = Sha256::digest(workflow.to_string().as_bytes());
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Embeds a provenance record into a PNG or JPEG file, keeping the image data as is.
+pub fn embed_provenance(data: &[u8], provenance: &Provenance) -> Result<Vec<u8>, String> {
+    let provenance = provenance.completed();
+    match detect_format(data) {
+        Some(ImageFormat::Png) => png_embed(data, &provenance),
+        Some(ImageFormat::Jpeg) => jpeg_embed(data, &provenance),
+        _ => Err("Provenance can only be embedded in PNG and JPEG files".to_string()),
+    }
+}
+
+pub fn read_provenance(data: &[u8]) -> Result<Option<Provenance>, String> {
+    let fields = match detect_format(data) {
+        Some(ImageFormat::Png) => png_text_fields(data)?,
+        Some(ImageFormat::Jpeg) => jpeg_xmp_fields(data)?,
+        _ => {
+            return Err("Provenance can only be read from PNG and JPEG files".to_string());
+        }
+    };
+    let field = |key: &str| fields.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str());
+
+    let mut provenance = match field(PROVENANCE_KEY) {
+        Some(json) =>
+            serde_json::from_str::<Provenance>(json)
+                .map_err(|e| format!("Invalid provenance record: {}", e))?,
+        None => Provenance { provider: "comfyui".to_string(), ..Default::default() },
+    };
+    provenance.comfy_prompt = field(COMFY_PROMPT_KEY).and_then(|s| serde_json::from_str(s).ok());
+    provenance.workflow = field(COMFY_WORKFLOW_KEY).and_then(|s| serde_json::from_str(s).ok());
+
+    if field(PROVENANCE_KEY).is_none() && provenance.comfy_prompt.is_none() && provenance.workflow.is_none() {
+        return Ok(None);
+    }
+    Ok(Some(provenance))
+}
+
+fn embedded_fields(provenance: &Provenance) -> Result<Vec<(&'static str, String)>, String> {
+    let record = serde_json::to_string(&provenance.record())
+        .map_err(|e| format!("Failed to serialize provenance: {}", e))?;
+    let mut fields = vec![(PROVENANCE_KEY, record)];
+    if let Some(prompt) = &provenance.comfy_prompt {
+        fields.push((COMFY_PROMPT_KEY, prompt.to_string()));
+    }
+    if let Some(workflow) = &provenance.workflow {
+        fields.push((COMFY_WORKFLOW_KEY, workflow.to_string()));
+    }
+    Ok(fields)
+}
+
+fn png_text_chunk(keyword: &str, text: &str) -> Vec<u8> {
+    let mut payload = keyword.as_bytes().to_vec();
+    payload.push(0);
+    if text.is_ascii() {
+        payload.extend_from_slice(text.as_bytes());
+        return png_chunk(b"tEXt", &payload);
+    }
+    payload.extend_from_slice(&[0, 0, 0, 0]);
+    payload.extend_from_slice(text.as_bytes());
+    png_chunk(b"iTXt", &payload)
+}
+
+fn inflate(data: &[u8]) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    flate2::read::ZlibDecoder::new(data)
+        .read_to_end(&mut out)
+        .map_err(|e| format!("Failed to decompress text chunk: {}", e))?;
+    Ok(out)
+}
+
+fn split_null(data: &[u8]) -> Option<(&[u8], &[u8])> {
+    let pos = data.iter().position(|b| *b == 0)?;
+    Some((&data[..pos], &data[pos + 1..]))
+}
+
+fn latin1(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| *b as char).collect()
+}
+
+fn png_text_field(chunk_type: &[u8; 4], payload: &[u8]) -> Result<Option<(String, String)>, String> {
+    let Some((keyword, rest)) = split_null(payload) else {
+        return Ok(None);
+    };
+    let keyword = latin1(keyword);
+    let text = match chunk_type {
+        b"tEXt" => latin1(rest),
+        b"zTXt" => latin1(&inflate(rest.get(1..).unwrap_or_default())?),
+        b"iTXt" => {
+            if rest.len() < 2 {
+                return Ok(None);
+            }
+            let compressed = rest[0] == 1;
+            let Some((_, rest)) = split_null(&rest[2..]) else {
+                return Ok(None);
+            };
+            let Some((_, text)) = split_null(rest) else {
+                return Ok(None);
+            };
+            let text = if compressed { inflate(text)? } else { text.to_vec() };
+            String::from_utf8(text).map_err(|e| format!("Invalid UTF-8 in iTXt chunk: {}", e))?
+        }
+        _ => {
+            return Ok(None);
+        }
+    };
+    Ok(Some((keyword, text)))
+}
+
+fn png_text_fields(data: &[u8]) -> Result<Vec<(String, String)>, String> {
+    let mut fields = Vec::new();
+    for (chunk_type, bytes) in png_chunks(data)? {
+        if let Some(field) = png_text_field(chunk_type, &bytes[8..bytes.len() - 4])? {
+            fields.push(field);
+        }
+    }
+    Ok(fields)
+}
+
+fn png_embed(data: &[u8], provenance: &Provenance) -> Result<Vec<u8>, String> {
+    let fields = embedded_fields(provenance)?;
+    let chunks = png_chunks(data)?;
+
+    let mut output = Vec::with_capacity(data.len());
+    output.extend_from_slice(&PNG_SIGNATURE);
+    let mut inserted = false;
+    for (chunk_type, bytes) in chunks {
+        if matches!(chunk_type, b"tEXt" | b"zTXt" | b"iTXt") {
+            let existing = png_text_field(chunk_type, &bytes[8..bytes.len() - 4]).ok().flatten();
+            if existing.is_some_and(|(k, _)| fields.iter().any(|(key, _)| *key == k)) {
+                continue;
+            }
+        }
+        if !inserted && (chunk_type == b"IDAT" || chunk_type == b"IEND") {
+            for (keyword, text) in &fields {
+                output.extend_from_slice(&png_text_chunk(keyword, text));
+            }
+            inserted = true;
+        }
+        output.extend_from_slice(bytes);
+    }
+    if !inserted {
+        return Err("PNG has no image data".to_string());
+    }
+    Ok(output)
+}
+
+pub(crate) fn xml_escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\n' => out.push_str("&#10;"),
+            '\r' => out.push_str("&#13;"),
+            '\t' => out.push_str("&#9;"),
+            // Other C0 controls are not allowed in XML 1.0, not even as character references
+            c if c < ' ' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn xmp_description(fields: &[(&str, String)]) -> String {
+    let mut description = format!(r#"<rdf:Description rdf:about="" xmlns:coinop="{}""#, XMP_NAMESPACE);
+    for (key, value) in fields {
+        let name = key.trim_start_matches("coinop:");
+        description.push_str(&format!(r#" coinop:{}="{}""#, name, xml_escape(value)));
+    }
+    description.push_str("/>");
+    description
+}
+
+fn xmp_packet(existing: Option<&str>, description: &str) -> String {
+    if let Some(packet) = existing {
+        let mut packet = packet.to_string();
+        if let Some(ns) = packet.find("xmlns:coinop=") {
+            if let (Some(start), Some(end)) = (packet[..ns].rfind("<rdf:Description"), packet[ns..].find("/>")) {
+                packet.replace_range(start..ns + end + 2, "");
+            }
+        }
+        if let Some(pos) = packet.find("</rdf:RDF>") {
+            packet.insert_str(pos, description);
+            return packet;
+        }
+    }
+    format!(
+        "<?xpacket begin=\"\u{feff}\" id=\"W5M0MpCehiHzreSzNTczkc9d\"?><x:xmpmeta xmlns:x=\"adobe:ns:meta/\"><rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">{}</rdf:RDF></x:xmpmeta><?xpacket end=\"w\"?>",
+        description
+    )
+}
+
+fn xmp_segment(data: &[u8], segments: &[(u8, usize, usize)]) -> Option<(usize, usize)> {
+    segments
+        .iter()
+        .find(|(marker, offset, len)| {
+            *marker == 0xe1 && *len >= 4 + XMP_HEADER.len() && data[offset + 4..offset + len].starts_with(XMP_HEADER)
+        })
+        .map(|(_, offset, len)| (*offset, *len))
+}
+
+fn jpeg_embed(data: &[u8], provenance: &Provenance) -> Result<Vec<u8>, String> {
+    let mut fields = embedded_fields(provenance)?;
+    let segments = jpeg_segments(data)?;
+    let existing = xmp_segment(data, &segments);
+    let existing_packet = existing.map(|(offset, len)| {
+        String::from_utf8_lossy(&data[offset + 4 + XMP_HEADER.len()..offset + len]).to_string()
+    });
+
+    // JPEG segments are limited to 64 KB, so the ComfyUI graphs are dropped first if they do not fit.
+    let packet = loop {
+        let packet = xmp_packet(existing_packet.as_deref(), &xmp_description(&fields));
+        if XMP_HEADER.len() + packet.len() + 2 <= MAX_JPEG_SEGMENT {
+            break packet;
+        }
+        if fields.len() == 1 {
+            return Err("Provenance record is too large for a JPEG XMP segment".to_string());
+        }
+        fields.pop();
+    };
+
+    let mut segment = vec![0xff, 0xe1];
+    segment.extend_from_slice(&((XMP_HEADER.len() + packet.len() + 2) as u16).to_be_bytes());
+    segment.extend_from_slice(XMP_HEADER);
+    segment.extend_from_slice(packet.as_bytes());
+
+    let mut output = data.to_vec();
+    match existing {
+        Some((offset, len)) => {
+            output.splice(offset..offset + len, segment);
+        }
+        None => {
+            let position = segments
+                .iter()
+                .take_while(|(marker, _, _)| *marker == 0xe0 || *marker == 0xe1)
+                .last()
+                .map(|(_, offset, len)| offset + len)
+                .unwrap_or(2);
+            output.splice(position..position, segment);
+        }
+    }
+    Ok(output)
+}
+
+fn jpeg_xmp_fields(data: &[u8]) -> Result<Vec<(String, String)>, String> {
+    let segments = jpeg_segments(data)?;
+    let Some((offset, len)) = xmp_segment(data, &segments) else {
+        return Ok(Vec::new());
+    };
+    let packet = String::from_utf8_lossy(&data[offset + 4 + XMP_HEADER.len()..offset + len]).to_string();
+    let doc = roxmltree::Document::parse(&packet).map_err(|e| format!("Invalid XMP packet: {}", e))?;
+
+    let mut fields = Vec::new();
+    for node in doc.descendants().filter(|n| n.is_element()) {
+        for key in [PROVENANCE_KEY, COMFY_PROMPT_KEY, COMFY_WORKFLOW_KEY] {
+            let name = key.trim_start_matches("coinop:");
+            if let Some(value) = node.attribute((XMP_NAMESPACE, name)) {
+                fields.push((key.to_string(), value.to_string()));
+            }
+        }
+    }
+    Ok(fields)
+}
+
+#[tauri::command]
+pub fn embed_image_provenance(data: Vec<u8>, provenance: Provenance) -> Result<Vec<u8>, String> {
+    embed_provenance(&data, &provenance)
+}
+
+#[tauri::command]
+pub fn read_image_provenance(data: Vec<u8>) -> Result<Option<Provenance>, String> {
+    read_provenance(&data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn encoded(format: image::ImageOutputFormat) -> Vec<u8> {
+        let pixels = image::RgbImage::from_fn(8, 8, |x, y| image::Rgb([x as u8 * 30, y as u8 * 30, 90]));
+        let mut out = std::io::Cursor::new(Vec::new());
+        image::DynamicImage::ImageRgb8(pixels).write_to(&mut out, format).unwrap();
+        out.into_inner()
+    }
+
+    fn with_chunks_before_idat(png: &[u8], extra: &[Vec<u8>]) -> Vec<u8> {
+        let mut output = PNG_SIGNATURE.to_vec();
+        let mut inserted = false;
+        for (chunk_type, bytes) in png_chunks(png).unwrap() {
+            if chunk_type == b"IDAT" && !inserted {
+                extra.iter().for_each(|chunk| output.extend_from_slice(chunk));
+                inserted = true;
+            }
+            output.extend_from_slice(bytes);
+        }
+        output
+    }
+
+    fn chunks_of<'a>(png: &'a [u8], wanted: &[u8; 4]) -> Vec<&'a [u8]> {
+        png_chunks(png)
+            .unwrap()
+            .into_iter()
+            .filter(|(chunk_type, _)| *chunk_type == wanted)
+            .map(|(_, bytes)| bytes)
+            .collect()
+    }
+
+    fn text_keywords(png: &[u8]) -> Vec<(String, String)> {
+        png_chunks(png)
+            .unwrap()
+            .into_iter()
+            .filter(|(chunk_type, _)| matches!(*chunk_type, b"tEXt" | b"iTXt"))
+            .map(|(chunk_type, bytes)| {
+                let (keyword, _) = png_text_field(chunk_type, &bytes[8..bytes.len() - 4]).unwrap().unwrap();
+                (String::from_utf8_lossy(chunk_type).to_string(), keyword)
+            })
+            .collect()
+    }
+
+    fn comfy_graphs() -> (Value, Value) {
+        let prompt = json!({ "3": { "class_type": "KSampler", "inputs": { "seed": 42, "model": ["4", 0] } } });
+        let workflow = json!({ "nodes": [{ "id": 3, "type": "KSampler", "widgets_values": [42, "fixed"] }], "links": [] });
+        (prompt, workflow)
+    }
+
+    #[test]
+    fn png_text_and_itxt_round_trip() {
+        let png = encoded(image::ImageOutputFormat::Png);
+        let (prompt, workflow) = comfy_graphs();
+        let provenance = Provenance {
+            provider: "replicate".to_string(),
+            model: Some("black-forest-labs/flux".to_string()),
+            prompt: Some("a wool coat, café au lait ✂".to_string()),
+            seed: Some(7),
+            comfy_prompt: Some(prompt.clone()),
+            workflow: Some(workflow.clone()),
+            ..Default::default()
+        };
+        let embedded = embed_provenance(&png, &provenance).unwrap();
+
+        let keywords = text_keywords(&embedded);
+        assert!(keywords.contains(&("iTXt".to_string(), PROVENANCE_KEY.to_string())), "{keywords:?}");
+        assert!(keywords.contains(&("tEXt".to_string(), COMFY_PROMPT_KEY.to_string())));
+        assert!(keywords.contains(&("tEXt".to_string(), COMFY_WORKFLOW_KEY.to_string())));
+        assert_eq!(chunks_of(&embedded, b"IDAT"), chunks_of(&png, b"IDAT"));
+
+        let read = read_provenance(&embedded).unwrap().unwrap();
+        assert_eq!(read.provider, "replicate");
+        assert_eq!(read.model, provenance.model);
+        assert_eq!(read.prompt, provenance.prompt);
+        assert_eq!(read.seed, Some(7));
+        assert_eq!(read.workflow_hash, Some(workflow_hash(&workflow)));
+        assert!(read.timestamp.is_some());
+        assert_eq!((read.comfy_prompt, read.workflow), (Some(prompt), Some(workflow)));
+
+        // embedding again replaces the chunks instead of adding a second set
+        let again = embed_provenance(&embedded, &Provenance { seed: Some(8), ..provenance }).unwrap();
+        assert_eq!(text_keywords(&again).len(), 3);
+        assert_eq!(read_provenance(&again).unwrap().unwrap().seed, Some(8));
+        assert_eq!(read_provenance(&png).unwrap().map(|p| p.provider), None);
+    }
+
+    #[test]
+    fn comfyui_prompt_and_workflow_chunks_round_trip() {
+        let (prompt, workflow) = comfy_graphs();
+        let from_comfyui = with_chunks_before_idat(
+            &encoded(image::ImageOutputFormat::Png),
+            &[png_text_chunk("prompt", &prompt.to_string()), png_text_chunk("workflow", &workflow.to_string())]
+        );
+
+        let read = read_provenance(&from_comfyui).unwrap().unwrap();
+        assert_eq!(read.provider, "comfyui");
+        assert_eq!((read.comfy_prompt.clone(), read.workflow.clone()), (Some(prompt.clone()), Some(workflow.clone())));
+
+        let stamped = embed_provenance(&from_comfyui, &read).unwrap();
+        let keywords: Vec<String> = text_keywords(&stamped).into_iter().map(|(_, k)| k).collect();
+        assert_eq!(keywords.iter().filter(|k| *k == "prompt").count(), 1);
+        assert_eq!(keywords.iter().filter(|k| *k == "workflow").count(), 1);
+        let reread = read_provenance(&stamped).unwrap().unwrap();
+        assert_eq!((reread.comfy_prompt, reread.workflow), (Some(prompt), Some(workflow.clone())));
+        assert_eq!(reread.workflow_hash, Some(workflow_hash(&workflow)));
+
+        let jpeg = embed_provenance(&encoded(image::ImageOutputFormat::Jpeg(90)), &read).unwrap();
+        let from_jpeg = read_provenance(&jpeg).unwrap().unwrap();
+        assert_eq!((from_jpeg.comfy_prompt, from_jpeg.workflow), (read.comfy_prompt, read.workflow));
+    }
+
+    #[test]
+    fn jpeg_xmp_is_embedded_and_replaced() {
+        let jpeg = encoded(image::ImageOutputFormat::Jpeg(90));
+        let packet = concat!(
+            r#"<x:xmpmeta xmlns:x="adobe:ns:meta/"><rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">"#,
+            r#"<rdf:Description rdf:about="" xmlns:dc="http://purl.org/dc/elements/1.1/" dc:format="image/jpeg"/>"#,
+            "</rdf:RDF></x:xmpmeta>"
+        );
+        let mut app1 = vec![0xff, 0xe1];
+        app1.extend_from_slice(&((XMP_HEADER.len() + packet.len() + 2) as u16).to_be_bytes());
+        app1.extend_from_slice(XMP_HEADER);
+        app1.extend_from_slice(packet.as_bytes());
+        let mut tagged = jpeg.clone();
+        let after_app0 = jpeg_segments(&jpeg).unwrap()[0].2 + 2;
+        tagged.splice(after_app0..after_app0, app1);
+
+        let provenance = Provenance {
+            provider: "comfyui".to_string(),
+            prompt: Some("line one\nline <two> & \"three\"\u{7}".to_string()),
+            ..Default::default()
+        };
+        let embedded = embed_provenance(&tagged, &provenance).unwrap();
+        let read = read_provenance(&embedded).unwrap().unwrap();
+        assert_eq!(read.prompt, provenance.prompt);
+
+        let xmp_segments = |data: &[u8]| {
+            let segments = jpeg_segments(data).unwrap();
+            segments
+                .iter()
+                .filter(|(marker, offset, len)| *marker == 0xe1 && data[offset + 4..offset + len].starts_with(XMP_HEADER))
+                .map(|(_, offset, len)| {
+                    String::from_utf8_lossy(&data[offset + 4 + XMP_HEADER.len()..offset + len]).to_string()
+                })
+                .collect::<Vec<_>>()
+        };
+        let replaced = embed_provenance(&embedded, &Provenance { prompt: Some("second".to_string()), ..provenance }).unwrap();
+        let packets = xmp_segments(&replaced);
+        assert_eq!(packets.len(), 1);
+        assert!(packets[0].contains(r#"dc:format="image/jpeg""#));
+        assert_eq!(packets[0].matches("xmlns:coinop=").count(), 1);
+        assert_eq!(read_provenance(&replaced).unwrap().unwrap().prompt.as_deref(), Some("second"));
+
+        // everything from the start of scan on is untouched
+        let scan = |data: &[u8]| data[data.windows(2).position(|w| w == [0xff, 0xda]).unwrap()..].to_vec();
+        assert_eq!(scan(&replaced), scan(&jpeg));
+    }
+
+    #[test]
+    fn truncated_app1_segments_are_rejected_without_panicking() {
+        let zero_length = [0xff, 0xd8, 0xff, 0xe1, 0x00, 0x00, 0xff, 0xd9];
+        assert!(read_provenance(&zero_length).unwrap_err().contains("Invalid JPEG segment length"));
+        assert!(embed_provenance(&zero_length, &Provenance::default()).is_err());
+
+        let empty_app1 = [0xff, 0xd8, 0xff, 0xe1, 0x00, 0x02, 0xff, 0xd9];
+        assert!(read_provenance(&empty_app1).unwrap().is_none());
+        let provenance = Provenance { provider: "replicate".to_string(), ..Default::default() };
+        let embedded = embed_provenance(&empty_app1, &provenance).unwrap();
+        assert_eq!(read_provenance(&embedded).unwrap().unwrap().provider, "replicate");
+    }
+
+    #[test]
+    fn xml_escape_drops_characters_xml_cannot_hold() {
+        assert_eq!(xml_escape("a\u{0}b\u{7}c\u{1f}\t\n<&>"), "abc&#9;&#10;&lt;&amp;&gt;");
+    }
+}
@@ -0,0 +1,778 @@
+use once_cell::sync::Lazy;
+use serde::{ Deserialize, Serialize };
+use sha2::{ Digest, Sha256 };
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::RwLock;
+use std::time::Duration;
+use tauri::Manager;
+
+const CONFIG_FILE: &str = "ipfs.json";
+const CACHE_DIR: &str = "ipfs/blocks";
+/// Gateways serve blocks of at most 2 MiB; anything larger is not a block.
+const MAX_BLOCK_SIZE: usize = 2 * 1024 * 1024;
+const MAX_FILE_SIZE: usize = 64 * 1024 * 1024;
+
+const CODEC_RAW: u64 = 0x55;
+const CODEC_DAG_PB: u64 = 0x70;
+const HASH_IDENTITY: u64 = 0x00;
+const HASH_SHA2_256: u64 = 0x12;
+
+const UNIXFS_RAW: u64 = 0;
+const UNIXFS_DIRECTORY: u64 = 1;
+const UNIXFS_FILE: u64 = 2;
+const UNIXFS_HAMT_SHARD: u64 = 5;
+
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BASE32_ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+static IPFS_CONFIG: Lazy<RwLock<IpfsConfig>> = Lazy::new(|| RwLock::new(IpfsConfig::default()));
+static BLOCK_CACHE: Lazy<RwLock<Option<PathBuf>>> = Lazy::new(|| RwLock::new(None));
+/// Shared so concurrent fetches reuse pooled connections; timeouts are applied per request.
+static HTTP_CLIENT: Lazy<reqwest::Client> = Lazy::new(reqwest::Client::new);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IpfsConfig {
+    /// Tried in order for every block until one returns content matching the CID
+    pub gateways: Vec<String>,
+    #[serde(rename = "timeoutSecs", default = "default_timeout")]
+    pub timeout_secs: u64,
+}
+
+fn default_timeout() -> u64 {
+    10
+}
+
+impl Default for IpfsConfig {
+    fn default() -> Self {
+        IpfsConfig {
+            gateways: vec![
+                "https://thedial.infura-ipfs.io".to_string(),
+                "https://ipfs.io".to_string(),
+                "https://dweb.link".to_string()
+            ],
+            timeout_secs: default_timeout(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheStats {
+    pub blocks: usize,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Cid {
+    version: u64,
+    codec: u64,
+    hash_code: u64,
+    digest: Vec<u8>,
+}
+
+fn read_varint(bytes: &[u8], pos: &mut usize) -> Result<u64, String> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = *bytes.get(*pos).ok_or("Truncated varint")?;
+        *pos += 1;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err("Varint is too long".to_string())
+}
+
+fn write_varint(mut value: u64, out: &mut Vec<u8>) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn base58_decode(text: &str) -> Result<Vec<u8>, String> {
+    let mut bytes: Vec<u8> = Vec::new();
+    for c in text.bytes() {
+        let mut carry = BASE58_ALPHABET.iter()
+            .position(|a| *a == c)
+            .ok_or_else(|| format!("Invalid base58 character {}", c as char))? as u32;
+        for byte in bytes.iter_mut().rev() {
+            carry += (*byte as u32) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.insert(0, carry as u8);
+            carry >>= 8;
+        }
+    }
+    let zeros = text
+        .bytes()
+        .take_while(|c| *c == b'1')
+        .count();
+    Ok([vec![0; zeros], bytes].concat())
+}
+
+fn base58_encode(bytes: &[u8]) -> String {
+    let mut digits: Vec<u8> = Vec::new();
+    for byte in bytes {
+        let mut carry = *byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    let zeros = bytes
+        .iter()
+        .take_while(|b| **b == 0)
+        .count();
+    std::iter
+        ::repeat_n('1', zeros)
+        .chain(digits.iter().rev().map(|d| BASE58_ALPHABET[*d as usize] as char))
+        .collect()
+}
+
+fn base32_decode(text: &str) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    let (mut buffer, mut bits) = (0u32, 0);
+    for c in text.bytes() {
+        let value = BASE32_ALPHABET.iter()
+            .position(|a| *a == c.to_ascii_lowercase())
+            .ok_or_else(|| format!("Invalid base32 character {}", c as char))? as u32;
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+    Ok(bytes)
+}
+
+fn base32_encode(bytes: &[u8]) -> String {
+    let mut text = String::new();
+    let (mut buffer, mut bits) = (0u32, 0);
+    for byte in bytes {
+        buffer = (buffer << 8) | (*byte as u32);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            text.push(BASE32_ALPHABET[((buffer >> bits) & 31) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        text.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+    }
+    text
+}
+
+impl Cid {
+    fn parse(text: &str) -> Result<Cid, String> {
+        if text.len() == 46 && text.starts_with("Qm") {
+            return Cid::from_bytes(&base58_decode(text)?);
+        }
+        let bytes = match text.chars().next() {
+            Some('b') | Some('B') => base32_decode(&text[1..])?,
+            Some('z') => base58_decode(&text[1..])?,
+            _ => {
+                return Err(format!("Unsupported CID {}", text));
+            }
+        };
+        Cid::from_bytes(&bytes)
+    }
+
+    /// Binary CID as found in dag-pb links: a bare sha2-256 multihash is CIDv0.
+    fn from_bytes(bytes: &[u8]) -> Result<Cid, String> {
+        let mut pos = 0;
+        let (version, codec) = if bytes.len() == 34 && bytes[0] == 0x12 && bytes[1] == 0x20 {
+            (0, CODEC_DAG_PB)
+        } else {
+            let version = read_varint(bytes, &mut pos)?;
+            if version != 1 {
+                return Err(format!("Unsupported CID version {}", version));
+            }
+            (version, read_varint(bytes, &mut pos)?)
+        };
+        let hash_code = read_varint(bytes, &mut pos)?;
+        let length = read_varint(bytes, &mut pos)? as usize;
+        let digest = bytes.get(pos..pos + length).ok_or("Truncated multihash")?.to_vec();
+        if pos + length != bytes.len() {
+            return Err("Trailing bytes after multihash".to_string());
+        }
+        Ok(Cid { version, codec, hash_code, digest })
+    }
+
+    fn verify(&self, block: &[u8]) -> Result<(), String> {
+        let matches = match self.hash_code {
+            HASH_SHA2_256 => Sha256::digest(block).as_slice() == self.digest.as_slice(),
+            HASH_IDENTITY => block == self.digest.as_slice(),
+            code => {
+                return Err(format!("Unsupported multihash 0x{:x} in {}", code, self));
+            }
+        };
+        if matches { Ok(()) } else { Err(format!("Content does not match {}", self)) }
+    }
+}
+
+impl fmt::Display for Cid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut multihash = Vec::new();
+        write_varint(self.hash_code, &mut multihash);
+        write_varint(self.digest.len() as u64, &mut multihash);
+        multihash.extend_from_slice(&self.digest);
+        if self.version == 0 {
+            return write!(f, "{}", base58_encode(&multihash));
+        }
+        let mut bytes = Vec::new();
+        write_varint(self.version, &mut bytes);
+        write_varint(self.codec, &mut bytes);
+        bytes.extend_from_slice(&multihash);
+        write!(f, "b{}", base32_encode(&bytes))
+    }
+}
+
+enum PbValue<'a> {
+    Varint(u64),
+    Bytes(&'a [u8]),
+}
+
+/// Splits a protobuf message into (field number, value), skipping fixed-width fields neither dag-pb nor
+/// UnixFS uses.
+fn pb_fields(bytes: &[u8]) -> Result<Vec<(u64, PbValue<'_>)>, String> {
+    let mut fields = Vec::new();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let key = read_varint(bytes, &mut pos)?;
+        let value = match key & 7 {
+            0 => PbValue::Varint(read_varint(bytes, &mut pos)?),
+            2 => {
+                let length = read_varint(bytes, &mut pos)? as usize;
+                let value = bytes.get(pos..pos + length).ok_or("Truncated protobuf field")?;
+                pos += length;
+                PbValue::Bytes(value)
+            }
+            1 => {
+                pos += 8;
+                continue;
+            }
+            5 => {
+                pos += 4;
+                continue;
+            }
+            wire => {
+                return Err(format!("Unsupported protobuf wire type {}", wire));
+            }
+        };
+        fields.push((key >> 3, value));
+    }
+    Ok(fields)
+}
+
+struct DagLink {
+    cid: Cid,
+    name: String,
+}
+
+struct DagNode {
+    links: Vec<DagLink>,
+    kind: u64,
+    data: Vec<u8>,
+}
+
+fn parse_dag_pb(block: &[u8]) -> Result<DagNode, String> {
+    let mut node = DagNode { links: Vec::new(), kind: UNIXFS_RAW, data: Vec::new() };
+    for (field, value) in pb_fields(block)? {
+        match (field, value) {
+            (2, PbValue::Bytes(link)) => {
+                let mut cid = None;
+                let mut name = String::new();
+                for (field, value) in pb_fields(link)? {
+                    match (field, value) {
+                        (1, PbValue::Bytes(hash)) => {
+                            cid = Some(Cid::from_bytes(hash)?);
+                        }
+                        (2, PbValue::Bytes(n)) => {
+                            name = String::from_utf8_lossy(n).into_owned();
+                        }
+                        _ => {}
+                    }
+                }
+                node.links.push(DagLink { cid: cid.ok_or("dag-pb link without a hash")?, name });
+            }
+            (1, PbValue::Bytes(unixfs)) => {
+                for (field, value) in pb_fields(unixfs)? {
+                    match (field, value) {
+                        (1, PbValue::Varint(kind)) => {
+                            node.kind = kind;
+                        }
+                        (2, PbValue::Bytes(data)) => {
+                            node.data = data.to_vec();
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(node)
+}
+
+pub fn config() -> IpfsConfig {
+    IPFS_CONFIG.read().unwrap().clone()
+}
+
+fn cache_path(cid: &Cid) -> Option<PathBuf> {
+    BLOCK_CACHE.read()
+        .unwrap()
+        .as_ref()
+        .map(|dir| dir.join(cid.to_string()))
+}
+
+fn cached_block(cid: &Cid) -> Option<Vec<u8>> {
+    let path = cache_path(cid)?;
+    let block = std::fs::read(&path).ok()?;
+    if cid.verify(&block).is_ok() {
+        Some(block)
+    } else {
+        let _ = std::fs::remove_file(&path);
+        None
+    }
+}
+
+fn cache_block(cid: &Cid, block: &[u8]) {
+    let Some(path) = cache_path(cid) else {
+        return;
+    };
+    let temp = path.with_extension("tmp");
+    let written = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| std::fs::write(&temp, block))
+        .and_then(|_| std::fs::rename(&temp, &path));
+    if let Err(e) = written {
+        log::warn!("Failed to cache IPFS block {}: {}", cid, e);
+    }
+}
+
+async fn fetch_from_gateway(gateway: &str, timeout: Duration, cid: &Cid) -> Result<Vec<u8>, String> {
+    let url = format!("{}/ipfs/{}?format=raw", gateway.trim_end_matches('/'), cid);
+    let response = HTTP_CLIENT.get(&url)
+        .header("Accept", "application/vnd.ipld.raw")
+        .timeout(timeout)
+        .send().await
+        .map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+    if response.content_length().is_some_and(|length| length > (MAX_BLOCK_SIZE as u64)) {
+        return Err("block is too large".to_string());
+    }
+    let block = response.bytes().await.map_err(|e| e.to_string())?;
+    if block.len() > MAX_BLOCK_SIZE {
+        return Err("block is too large".to_string());
+    }
+    cid.verify(&block)?;
+    Ok(block.to_vec())
+}
+
+/// One verified block, from the cache when possible and otherwise from the first gateway that serves it.
+async fn block(config: &IpfsConfig, cid: &Cid) -> Result<Vec<u8>, String> {
+    if cid.hash_code == HASH_IDENTITY {
+        return Ok(cid.digest.clone());
+    }
+    if let Some(block) = cached_block(cid) {
+        return Ok(block);
+    }
+    let mut errors = Vec::new();
+    let timeout = Duration::from_secs(config.timeout_secs.max(1));
+    for gateway in &config.gateways {
+        match fetch_from_gateway(gateway, timeout, cid).await {
+            Ok(block) => {
+                cache_block(cid, &block);
+                return Ok(block);
+            }
+            Err(e) => errors.push(format!("{}: {}", gateway, e)),
+        }
+    }
+    Err(format!("Failed to fetch {} from any gateway ({})", cid, errors.join("; ")))
+}
+
+/// Splits `ipfs://<cid>/path`, `/ipfs/<cid>/path` or a bare `<cid>/path` into the root CID and path segments.
+fn parse_uri(uri: &str) -> Result<(Cid, Vec<&str>), String> {
+    let path = uri
+        .strip_prefix("ipfs://")
+        .or_else(|| uri.strip_prefix("/ipfs/"))
+        .unwrap_or(uri);
+    let mut segments = path.split('/').filter(|s| !s.is_empty());
+    let cid = Cid::parse(segments.next().ok_or_else(|| format!("No CID in {}", uri))?)?;
+    Ok((cid, segments.collect()))
+}
+
+/// Fetches a UnixFS file by IPFS URI. Every block is checked against its CID, so a misbehaving gateway
+/// can only fail a request, never corrupt it, and anything fetched once is served from disk afterwards.
+pub async fn fetch(uri: &str) -> Result<Vec<u8>, String> {
+    let (mut cid, segments) = parse_uri(uri)?;
+    let config = config();
+
+    for segment in segments {
+        if cid.codec != CODEC_DAG_PB {
+            return Err(format!("{} is not a directory", cid));
+        }
+        let node = parse_dag_pb(&block(&config, &cid).await?)?;
+        match node.kind {
+            UNIXFS_DIRECTORY => {}
+            UNIXFS_HAMT_SHARD => {
+                return Err(format!("Sharded directory {} is not supported", cid));
+            }
+            _ => {
+                return Err(format!("{} is not a directory", cid));
+            }
+        }
+        cid = node.links
+            .into_iter()
+            .find(|link| link.name == segment)
+            .map(|link| link.cid)
+            .ok_or_else(|| format!("{} not found in {}", segment, uri))?;
+    }
+
+    let mut content = Vec::new();
+    let mut pending = vec![cid];
+    while let Some(cid) = pending.pop() {
+        let block = block(&config, &cid).await?;
+        match cid.codec {
+            CODEC_RAW => content.extend_from_slice(&block),
+            CODEC_DAG_PB => {
+                let node = parse_dag_pb(&block)?;
+                if node.kind != UNIXFS_FILE && node.kind != UNIXFS_RAW {
+                    return Err(format!("{} is not a file", uri));
+                }
+                content.extend_from_slice(&node.data);
+                pending.extend(node.links.into_iter().rev().map(|link| link.cid));
+            }
+            codec => {
+                return Err(format!("Unsupported codec 0x{:x} in {}", codec, cid));
+            }
+        }
+        if content.len() > MAX_FILE_SIZE {
+            return Err(format!("{} is larger than {} bytes", uri, MAX_FILE_SIZE));
+        }
+    }
+    Ok(content)
+}
+
+pub async fn fetch_text(uri: &str) -> Result<String, String> {
+    String::from_utf8(fetch(uri).await?).map_err(|e| format!("{} is not UTF-8 text: {}", uri, e))
+}
+
+fn config_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    Ok(app_data_dir.join(CONFIG_FILE))
+}
+
+fn cache_dir() -> Result<PathBuf, String> {
+    BLOCK_CACHE.read().unwrap().clone().ok_or_else(|| "IPFS cache is not initialised".to_string())
+}
+
+/// Points the block cache at app data and loads the saved gateway list at startup.
+pub fn load_config(app: &tauri::AppHandle) {
+    if let Ok(app_data_dir) = app.path().app_data_dir() {
+        *BLOCK_CACHE.write().unwrap() = Some(app_data_dir.join(CACHE_DIR));
+    }
+    let Ok(json) = config_path(app).and_then(|path| std::fs::read_to_string(path).map_err(|e| e.to_string())) else {
+        return;
+    };
+    match serde_json::from_str::<IpfsConfig>(&json) {
+        Ok(config) => {
+            *IPFS_CONFIG.write().unwrap() = config;
+        }
+        Err(e) => log::warn!("Ignoring unreadable {}: {}", CONFIG_FILE, e),
+    }
+}
+
+#[tauri::command]
+pub async fn ipfs_get_config() -> Result<IpfsConfig, String> {
+    Ok(config())
+}
+
+#[tauri::command]
+pub async fn ipfs_set_config(app: tauri::AppHandle, mut config: IpfsConfig) -> Result<(), String> {
+    config.gateways = config.gateways
+        .iter()
+        .map(|gateway| gateway.trim().trim_end_matches('/').to_string())
+        .filter(|gateway| !gateway.is_empty())
+        .collect();
+    if config.gateways.is_empty() {
+        return Err("At least one IPFS gateway is required".to_string());
+    }
+    if let Some(gateway) = config.gateways.iter().find(|g| !g.starts_with("http://") && !g.starts_with("https://")) {
+        return Err(format!("IPFS gateway {} must be an http(s) URL", gateway));
+    }
+    let path = config_path(&app)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    let json = serde_json::to_string_pretty(&config).map_err(|e| format!("Failed to serialize config: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write config: {}", e))?;
+    *IPFS_CONFIG.write().unwrap() = config;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn ipfs_cache_stats() -> Result<CacheStats, String> {
+    let mut stats = CacheStats { blocks: 0, bytes: 0 };
+    for entry in std::fs::read_dir(cache_dir()?).into_iter().flatten().flatten() {
+        // Blocks are named by CID; anything else, such as a `.tmp` left by an interrupted write, is not a block
+        if entry.file_name().to_str().is_none_or(|name| Cid::parse(name).is_err()) {
+            continue;
+        }
+        if let Ok(metadata) = entry.metadata() {
+            stats.blocks += 1;
+            stats.bytes += metadata.len();
+        }
+    }
+    Ok(stats)
+}
+
+#[tauri::command]
+pub async fn ipfs_clear_cache() -> Result<(), String> {
+    let dir = cache_dir()?;
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir).map_err(|e| format!("Failed to clear IPFS cache: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Points fetches at stub gateways and a temporary cache; callers hold `test_support::REMOTE_CONFIG`.
+#[cfg(test)]
+pub fn use_gateways_for_tests(gateways: &[&str], cache: &std::path::Path) {
+    *IPFS_CONFIG.write().unwrap() = IpfsConfig {
+        gateways: gateways
+            .iter()
+            .map(|g| g.to_string())
+            .collect(),
+        timeout_secs: 5,
+    };
+    *BLOCK_CACHE.write().unwrap() = Some(cache.to_path_buf());
+}
+
+/// The CIDv1 of `content` stored as a single raw block, as a gateway would be asked for it
+#[cfg(test)]
+pub fn raw_cid_for_tests(content: &[u8]) -> String {
+    Cid { version: 1, codec: CODEC_RAW, hash_code: HASH_SHA2_256, digest: Sha256::digest(content).to_vec() }.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{ StubResponse, StubServer, REMOTE_CONFIG };
+    use std::collections::HashMap;
+
+    /// The empty UnixFS directory, whose CIDs are well known
+    const EMPTY_DIRECTORY: [u8; 4] = [0x0a, 0x02, 0x08, 0x01];
+    const EMPTY_DIRECTORY_V0: &str = "QmUNLLsPACCz1vLxQVkXqqLX5R1X345qqfHbsf67hvA3Nn";
+    const EMPTY_DIRECTORY_V1: &str = "bafybeiczsscdsbs7ffqz55asqdf3smv6klcw3gofszvwlyarci47bgf354";
+    /// `hello world` as a raw block
+    const HELLO_WORLD_RAW: &str = "bafkreifzjut3te2nhyekklss27nh3k72ysco7y32koao5eei66wof36n5e";
+
+    fn sha256_cid(version: u64, codec: u64, block: &[u8]) -> Cid {
+        Cid { version, codec, hash_code: HASH_SHA2_256, digest: Sha256::digest(block).to_vec() }
+    }
+
+    fn cid_bytes(cid: &Cid) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        if cid.version == 1 {
+            write_varint(1, &mut bytes);
+            write_varint(cid.codec, &mut bytes);
+        }
+        write_varint(cid.hash_code, &mut bytes);
+        write_varint(cid.digest.len() as u64, &mut bytes);
+        bytes.extend_from_slice(&cid.digest);
+        bytes
+    }
+
+    fn pb_bytes(field: u64, value: &[u8], out: &mut Vec<u8>) {
+        write_varint((field << 3) | 2, out);
+        write_varint(value.len() as u64, out);
+        out.extend_from_slice(value);
+    }
+
+    /// A dag-pb node with UnixFS `kind` and `data` and named links
+    fn dag_pb(kind: u64, data: &[u8], links: &[(&str, &Cid)]) -> Vec<u8> {
+        let mut node = Vec::new();
+        for (name, cid) in links {
+            let mut link = Vec::new();
+            pb_bytes(1, &cid_bytes(cid), &mut link);
+            pb_bytes(2, name.as_bytes(), &mut link);
+            pb_bytes(2, &link, &mut node);
+        }
+        let mut unixfs = Vec::new();
+        write_varint(1 << 3, &mut unixfs);
+        write_varint(kind, &mut unixfs);
+        if !data.is_empty() {
+            pb_bytes(2, data, &mut unixfs);
+        }
+        pb_bytes(1, &unixfs, &mut node);
+        node
+    }
+
+    /// Serves `blocks` by CID, with `tampered` blocks altered in transit
+    async fn gateway(blocks: &HashMap<String, Vec<u8>>, tampered: &[String]) -> StubServer {
+        let mut served = blocks.clone();
+        for cid in tampered {
+            if let Some(block) = served.get_mut(cid) {
+                block[0] ^= 0xff;
+            }
+        }
+        StubServer::start(move |request| {
+            let cid = request.path.trim_start_matches("/ipfs/").split('?').next().unwrap_or("");
+            match served.get(cid) {
+                Some(block) => StubResponse::bytes(200, block),
+                None => StubResponse::bytes(404, b"not found"),
+            }
+        }).await
+    }
+
+    fn use_gateways(gateways: &[&StubServer], cache: &std::path::Path) {
+        let urls: Vec<&str> = gateways
+            .iter()
+            .map(|g| g.url.as_str())
+            .collect();
+        use_gateways_for_tests(&urls, cache);
+    }
+
+    /// A file split over two raw leaves and a dag-pb leaf, inside `docs/` of a directory that also holds `hello.txt`
+    fn tree() -> (Cid, Vec<u8>, HashMap<String, Vec<u8>>) {
+        let mut blocks = HashMap::new();
+        let mut add = |cid: Cid, block: Vec<u8>| {
+            blocks.insert(cid.to_string(), block);
+            cid
+        };
+        let first = add(sha256_cid(1, CODEC_RAW, b"first chunk, "), b"first chunk, ".to_vec());
+        let second = add(sha256_cid(1, CODEC_RAW, b"second chunk, "), b"second chunk, ".to_vec());
+        let third_block = dag_pb(UNIXFS_FILE, b"third chunk", &[]);
+        let third = add(sha256_cid(0, CODEC_DAG_PB, &third_block), third_block);
+        let file_block = dag_pb(UNIXFS_FILE, b"", &[("", &first), ("", &second), ("", &third)]);
+        let file = add(sha256_cid(0, CODEC_DAG_PB, &file_block), file_block);
+        let hello = add(sha256_cid(1, CODEC_RAW, b"hello world"), b"hello world".to_vec());
+        let docs_block = dag_pb(UNIXFS_DIRECTORY, b"", &[("chunks.txt", &file)]);
+        let docs = add(sha256_cid(1, CODEC_DAG_PB, &docs_block), docs_block);
+        let root_block = dag_pb(UNIXFS_DIRECTORY, b"", &[("docs", &docs), ("hello.txt", &hello)]);
+        let root = add(sha256_cid(1, CODEC_DAG_PB, &root_block), root_block);
+        (root, b"first chunk, second chunk, third chunk".to_vec(), blocks)
+    }
+
+    #[test]
+    fn known_cids_parse_and_display_unchanged() {
+        let v0 = Cid::parse(EMPTY_DIRECTORY_V0).unwrap();
+        assert_eq!((v0.version, v0.codec, v0.hash_code), (0, CODEC_DAG_PB, HASH_SHA2_256));
+        assert_eq!(v0.to_string(), EMPTY_DIRECTORY_V0);
+        v0.verify(&EMPTY_DIRECTORY).unwrap();
+
+        let v1 = Cid::parse(EMPTY_DIRECTORY_V1).unwrap();
+        assert_eq!((v1.version, v1.codec), (1, CODEC_DAG_PB));
+        assert_eq!(v1.digest, v0.digest);
+        assert_eq!(v1.to_string(), EMPTY_DIRECTORY_V1);
+        assert_eq!(Cid::parse(&EMPTY_DIRECTORY_V1.to_uppercase()).unwrap(), v1);
+
+        let raw = Cid::parse(HELLO_WORLD_RAW).unwrap();
+        assert_eq!(raw, sha256_cid(1, CODEC_RAW, b"hello world"));
+        assert_eq!(raw.to_string(), HELLO_WORLD_RAW);
+        assert!(raw.verify(b"hello world!").is_err());
+
+        let base58 = format!("z{}", base58_encode(&cid_bytes(&v1)));
+        assert_eq!(Cid::parse(&base58).unwrap(), v1);
+        assert_eq!(Cid::from_bytes(&cid_bytes(&v0)).unwrap(), v0);
+
+        for bad in ["", "Qm", "xyz", "bafy!", &EMPTY_DIRECTORY_V1[..20]] {
+            assert!(Cid::parse(bad).is_err(), "{} parsed", bad);
+        }
+        let uri = format!("ipfs://{}/a//b/", EMPTY_DIRECTORY_V0);
+        let (cid, segments) = parse_uri(&uri).unwrap();
+        assert_eq!((cid, segments), (v0, vec!["a", "b"]));
+    }
+
+    #[test]
+    fn dag_pb_nodes_decode() {
+        let node = parse_dag_pb(&EMPTY_DIRECTORY).unwrap();
+        assert_eq!(node.kind, UNIXFS_DIRECTORY);
+        assert!(node.links.is_empty());
+
+        let child = sha256_cid(1, CODEC_RAW, b"x");
+        let node = parse_dag_pb(&dag_pb(UNIXFS_FILE, b"head", &[("part", &child)])).unwrap();
+        assert_eq!((node.kind, node.data.as_slice()), (UNIXFS_FILE, b"head".as_slice()));
+        assert_eq!(node.links.len(), 1);
+        assert_eq!((node.links[0].name.as_str(), &node.links[0].cid), ("part", &child));
+        assert!(parse_dag_pb(&[0x12, 0x05, 0x0a]).is_err());
+    }
+
+    #[tokio::test]
+    async fn fetches_multi_block_files_through_directories() {
+        let _turn = REMOTE_CONFIG.lock().await;
+        let cache = tempfile::tempdir().unwrap();
+        let (root, content, blocks) = tree();
+        let server = gateway(&blocks, &[]).await;
+        use_gateways(&[&server], cache.path());
+
+        assert_eq!(fetch(&format!("ipfs://{}/docs/chunks.txt", root)).await.unwrap(), content);
+        assert_eq!(fetch_text(&format!("/ipfs/{}/hello.txt", root)).await.unwrap(), "hello world");
+        let missing = fetch(&format!("{}/docs/missing.txt", root)).await.unwrap_err();
+        assert!(missing.contains("missing.txt not found"), "{}", missing);
+        assert!(fetch(&format!("ipfs://{}", root)).await.unwrap_err().contains("is not a file"));
+        assert!(fetch(&format!("ipfs://{}/hello.txt/more", root)).await.unwrap_err().contains("is not a directory"));
+
+        // Every block was fetched once and is now served from the cache
+        let fetched = server.requests().len();
+        assert_eq!(fetch(&format!("ipfs://{}/docs/chunks.txt", root)).await.unwrap(), content);
+        assert_eq!(server.requests().len(), fetched);
+    }
+
+    #[tokio::test]
+    async fn tampered_blocks_are_rejected() {
+        let _turn = REMOTE_CONFIG.lock().await;
+        let cache = tempfile::tempdir().unwrap();
+        let (root, content, blocks) = tree();
+        let uri = format!("ipfs://{}/docs/chunks.txt", root);
+        let leaf = sha256_cid(1, CODEC_RAW, b"second chunk, ").to_string();
+        let tampering = gateway(&blocks, std::slice::from_ref(&leaf)).await;
+        let honest = gateway(&blocks, &[]).await;
+
+        use_gateways(&[&tampering], cache.path());
+        let error = fetch(&uri).await.unwrap_err();
+        assert!(error.contains("Content does not match"), "{}", error);
+        assert!(!cache.path().join(&leaf).exists());
+
+        use_gateways(&[&tampering, &honest], cache.path());
+        assert_eq!(fetch(&uri).await.unwrap(), content);
+        assert_eq!(honest.requests_to("GET", &format!("/ipfs/{}", leaf)).len(), 1);
+
+        // A cached block that no longer matches its CID is dropped and fetched again
+        std::fs::write(cache.path().join(&leaf), b"corrupted on disk").unwrap();
+        use_gateways(&[&honest], cache.path());
+        assert_eq!(fetch(&uri).await.unwrap(), content);
+        assert_eq!(std::fs::read(cache.path().join(&leaf)).unwrap(), b"second chunk, ");
+    }
+
+    #[tokio::test]
+    async fn cache_stats_count_only_blocks() {
+        let _turn = REMOTE_CONFIG.lock().await;
+        let cache = tempfile::tempdir().unwrap();
+        *BLOCK_CACHE.write().unwrap() = Some(cache.path().to_path_buf());
+        cache_block(&sha256_cid(1, CODEC_RAW, b"hello world"), b"hello world");
+        std::fs::write(cache.path().join(format!("{}.tmp", EMPTY_DIRECTORY_V0)), b"partial").unwrap();
+
+        let stats = ipfs_cache_stats().await.unwrap();
+        assert_eq!((stats.blocks, stats.bytes), (1, 11));
+        ipfs_clear_cache().await.unwrap();
+        assert_eq!(ipfs_cache_stats().await.unwrap().blocks, 0);
+    }
+}
@@ -5,6 +5,30 @@ use std::fs;
 mod pattern_nesting;
 mod pattern_export;
 mod subgraph;
+mod cutter_export;
+mod pdf_tiling;
+mod print_calibration;
+mod pdf_layers;
+mod image_dpi;
+mod image_provenance;
+mod comfyui_connection;
+mod comfyui_jobs;
+mod comfyui_schema;
+mod comfyui_validate;
+mod comfyui_convert;
+mod comfyui_templates;
+mod replicate;
+mod generation;
+mod credentials;
+mod assets;
+pub mod binary_ipc;
+mod fs_sandbox;
+mod subgraph_client;
+mod catalog_snapshot;
+mod ipfs;
+mod template_types;
+#[cfg(test)]
+mod test_support;
 use serde_json::Value;
 
 
@@ -53,11 +77,12 @@ async fn save_image_with_dialog(image_data: String) -> Result<String, String> {
 #[tauri::command]
 async fn replicate_create_prediction(
     model_name: String,
-    api_key: String,
+    credential: String,
     input: serde_json::Value
 ) -> Result<serde_json::Value, String> {
-    let client = reqwest::Client::new();
     let url = format!("https://api.replicate.com/v1/models/{}/predictions", model_name);
+    let api_key = credentials::secret_for(&credential, &url)?;
+    let client = reqwest::Client::new();
     let response = client
         .post(&url)
         .header("Authorization", format!("Bearer {}", api_key))
@@ -80,10 +105,11 @@ async fn replicate_create_prediction(
 #[tauri::command]
 async fn replicate_get_prediction(
     prediction_id: String,
-    api_key: String
+    credential: String
 ) -> Result<serde_json::Value, String> {
-    let client = reqwest::Client::new();
     let url = format!("https://api.replicate.com/v1/predictions/{}", prediction_id);
+    let api_key = credentials::secret_for(&credential, &url)?;
+    let client = reqwest::Client::new();
     let response = client
         .get(&url)
         .header("Authorization", format!("Bearer {}", api_key))
@@ -101,18 +127,22 @@ async fn replicate_get_prediction(
 }
 #[tauri::command]
 async fn download_image_as_base64(image_url: String) -> Result<String, String> {
+    let bytes = download_image_bytes(&image_url).await?;
+    let base64_string = general_purpose::STANDARD.encode(&bytes);
+    let data_url = format!("data:image/png;base64,{}", base64_string);
+    Ok(data_url)
+}
+async fn download_image_bytes(image_url: &str) -> Result<Vec<u8>, String> {
     let client = reqwest::Client::new();
     let response = client
-        .get(&image_url)
+        .get(image_url)
         .send().await
         .map_err(|e| format!("Failed to download image: {}", e))?;
     if !response.status().is_success() {
         return Err(format!("Failed to download image: HTTP {}", response.status()));
     }
     let bytes = response.bytes().await.map_err(|e| format!("Failed to read image bytes: {}", e))?;
-    let base64_string = general_purpose::STANDARD.encode(&bytes);
-    let data_url = format!("data:image/png;base64,{}", base64_string);
-    Ok(data_url)
+    Ok(bytes.to_vec())
 }
 #[tauri::command]
 async fn comfyui_execute_workflow(
@@ -120,11 +150,7 @@ async fn comfyui_execute_workflow(
     workflow_json: serde_json::Value,
     client_id: String
 ) -> Result<String, String> {
-    let client = reqwest::Client
-        ::builder()
-        .danger_accept_invalid_certs(true)
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let client = comfyui_connection::http_client(&comfy_url).await?;
     let prompt_url = format!("{}/prompt", comfy_url.trim_end_matches('/'));
     let request_body =
         serde_json::json!({
@@ -153,11 +179,7 @@ async fn comfyui_get_history(
     comfy_url: String,
     prompt_id: String
 ) -> Result<serde_json::Value, String> {
-    let client = reqwest::Client
-        ::builder()
-        .danger_accept_invalid_certs(true)
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let client = comfyui_connection::http_client(&comfy_url).await?;
     let history_url = format!("{}/history/{}", comfy_url.trim_end_matches('/'), prompt_id);
     let response = client
         .get(&history_url)
@@ -175,11 +197,6 @@ async fn comfyui_get_history(
 }
 #[tauri::command]
 async fn comfyui_upload_image(comfy_url: String, image_data: String) -> Result<String, String> {
-    let client = reqwest::Client
-        ::builder()
-        .danger_accept_invalid_certs(true)
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
     let base64_data = if image_data.starts_with("data:image") {
         image_data.split(',').nth(1).ok_or("Invalid base64 image data")?
     } else {
@@ -188,6 +205,10 @@ async fn comfyui_upload_image(comfy_url: String, image_data: String) -> Result<S
     let image_bytes = general_purpose::STANDARD
         .decode(base64_data)
         .map_err(|e| format!("Failed to decode base64: {}", e))?;
+    comfyui_upload_bytes(&comfy_url, image_bytes).await
+}
+async fn comfyui_upload_bytes(comfy_url: &str, image_bytes: Vec<u8>) -> Result<String, String> {
+    let client = comfyui_connection::http_client(comfy_url).await?;
     let upload_url = format!("{}/upload/image", comfy_url.trim_end_matches('/'));
     let form = reqwest::multipart::Form::new().part(
         "image",
@@ -220,11 +241,18 @@ async fn comfyui_download_image(
     subfolder: Option<String>,
     image_type: Option<String>
 ) -> Result<String, String> {
-    let client = reqwest::Client
-        ::builder()
-        .danger_accept_invalid_certs(true)
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let bytes = comfyui_view_bytes(&comfy_url, &filename, subfolder.as_deref(), image_type.as_deref()).await?;
+    let base64_string = general_purpose::STANDARD.encode(&bytes);
+    let data_url = format!("data:image/png;base64,{}", base64_string);
+    Ok(data_url)
+}
+async fn comfyui_view_bytes(
+    comfy_url: &str,
+    filename: &str,
+    subfolder: Option<&str>,
+    image_type: Option<&str>
+) -> Result<Vec<u8>, String> {
+    let client = comfyui_connection::http_client(comfy_url).await?;
     let mut image_url = format!("{}/view?filename={}", comfy_url.trim_end_matches('/'), filename);
     if let Some(subfolder) = subfolder {
         if !subfolder.is_empty() {
@@ -244,12 +272,11 @@ async fn comfyui_download_image(
         return Err(format!("Failed to download image: HTTP {}", response.status()));
     }
     let bytes = response.bytes().await.map_err(|e| format!("Failed to read image bytes: {}", e))?;
-    let base64_string = general_purpose::STANDARD.encode(&bytes);
-    let data_url = format!("data:image/png;base64,{}", base64_string);
-    Ok(data_url)
+    Ok(bytes.to_vec())
 }
 #[tauri::command]
-async fn write_image_file(image_data: String, file_path: String) -> Result<String, String> {
+async fn write_image_file(app: tauri::AppHandle, image_data: String, file_path: String) -> Result<String, String> {
+    let target = fs_sandbox::user_path(&app, &file_path)?;
     let base64_data = if image_data.starts_with("data:image") {
         image_data.split(',').nth(1).ok_or("Invalid base64 image data")?
     } else {
@@ -258,22 +285,41 @@ async fn write_image_file(image_data: String, file_path: String) -> Result<Strin
     let image_bytes = general_purpose::STANDARD
         .decode(base64_data)
         .map_err(|e| format!("Failed to decode base64: {}", e))?;
-    fs::write(&file_path, image_bytes).map_err(|e| format!("Failed to write file: {}", e))?;
+    fs::write(&target, image_bytes).map_err(|e| format!("Failed to write file: {}", e))?;
     Ok(format!("Image saved successfully: {}", file_path))
 }
 
 #[tauri::command]
-async fn write_file_bytes(path: String, contents: Vec<u8>) -> Result<String, String> {
-    fs::write(&path, contents).map_err(|e| format!("Failed to write file: {}", e))?;
+async fn write_file_bytes(app: tauri::AppHandle, path: String, contents: Vec<u8>) -> Result<String, String> {
+    let target = fs_sandbox::user_path(&app, &path)?;
+    fs::write(&target, contents).map_err(|e| format!("Failed to write file: {}", e))?;
     Ok(format!("File saved successfully: {}", path))
 }
 
 
 #[tauri::command]
-async fn parse_comfyui_workflow(workflow_json: String) -> Result<ComfyUIWorkflow, String> {
-    let raw_workflow: HashMap<String, serde_json::Value> = serde_json
+async fn parse_comfyui_workflow(
+    workflow_json: String,
+    comfy_url: Option<String>
+) -> Result<ComfyUIWorkflow, String> {
+    let parsed: serde_json::Value = serde_json
         ::from_str(&workflow_json)
         .map_err(|e| format!("Failed to parse workflow JSON: {}", e))?;
+    let (raw_workflow, positions): (HashMap<String, serde_json::Value>, _) = if
+        comfyui_convert::is_ui_workflow(&parsed)
+    {
+        let comfy_url = comfy_url.ok_or(
+            "Workflows exported from the ComfyUI editor need a ComfyUI URL to map widget values"
+        )?;
+        let schemas = comfyui_schema::object_info(&comfy_url, false).await?;
+        let converted = comfyui_convert::ui_to_api(&parsed, &schemas)?;
+        (converted.prompt.into_iter().collect(), converted.positions)
+    } else {
+        let raw_workflow: HashMap<String, serde_json::Value> = serde_json
+            ::from_value(parsed)
+            .map_err(|e| format!("Failed to parse workflow JSON: {}", e))?;
+        (raw_workflow, HashMap::new())
+    };
     let mut nodes = Vec::new();
     let mut connections = Vec::new();
     for (node_id, node_data) in raw_workflow {
@@ -338,12 +384,12 @@ async fn parse_comfyui_workflow(workflow_json: String) -> Result<ComfyUIWorkflow
             }
         }
         nodes.push(ComfyUINode {
+            position: positions.get(&node_id).copied(),
             id: node_id,
             class_type,
             title,
             inputs,
             outputs: Vec::new(),
-            position: None,
         });
     }
     for node in &mut nodes {
@@ -360,15 +406,34 @@ async fn parse_comfyui_workflow(workflow_json: String) -> Result<ComfyUIWorkflow
             .map(|i| format!("output_{}", i))
             .collect();
     }
+    if nodes.iter().any(|node| node.position.is_none()) {
+        let sizes: Vec<(String, (f32, f32))> = nodes
+            .iter()
+            .map(|node| (node.id.clone(), (315.0, 60.0 + 26.0 * (node.inputs.len() as f32))))
+            .collect();
+        let edges: Vec<(String, String)> = connections
+            .iter()
+            .map(|c| (c.from_node.clone(), c.to_node.clone()))
+            .collect();
+        let layout = comfyui_convert::auto_layout(&sizes, &edges);
+        for node in &mut nodes {
+            if node.position.is_none() {
+                node.position = layout.get(&node.id).copied();
+            }
+        }
+    }
     Ok(ComfyUIWorkflow { nodes, connections })
 }
 #[tauri::command]
-async fn comfyui_get_models(comfy_url: String, model_type: String) -> Result<Vec<String>, String> {
-    let client = reqwest::Client
-        ::builder()
-        .danger_accept_invalid_certs(true)
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+async fn comfyui_get_models(
+    comfy_url: String,
+    model_type: String,
+    input_name: Option<String>
+) -> Result<Vec<String>, String> {
+    if let Ok(Some(schema)) = comfyui_schema::node_schema(&comfy_url, &model_type).await {
+        return comfyui_schema::model_choices(&schema, input_name.as_deref());
+    }
+    let client = comfyui_connection::http_client(&comfy_url).await?;
     let models_url = match model_type.as_str() {
         "VAELoader" => format!("{}/models/vae", comfy_url.trim_end_matches('/')),
         "CheckpointLoaderSimple" =>
@@ -396,7 +461,15 @@ async fn comfyui_get_models(comfy_url: String, model_type: String) -> Result<Vec
     Ok(models)
 }
 #[tauri::command]
-async fn get_node_info(class_type: String) -> Result<serde_json::Value, String> {
+async fn get_node_info(
+    class_type: String,
+    comfy_url: Option<String>
+) -> Result<serde_json::Value, String> {
+    if let Some(comfy_url) = comfy_url {
+        if let Ok(Some(schema)) = comfyui_schema::node_schema(&comfy_url, &class_type).await {
+            return Ok(comfyui_schema::node_info_json(&schema));
+        }
+    }
     let node_info = match class_type.as_str() {
         "CLIPTextEncode" =>
             serde_json::json!({
@@ -508,15 +581,9 @@ async fn fetch_children_materials_colors() -> Result<Value, String> {
 
 #[tauri::command]
 async fn remove_directory_recursive(app: tauri::AppHandle, path: String) -> Result<(), String> {
-    use tauri::Manager;
-    
-    let app_data_dir = app.path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    let full_path = fs_sandbox::app_data_path(&app, &path)?;
     
-    let full_path = app_data_dir.join(&path);
-    
-    if full_path.exists() {
+    if full_path.is_dir() {
         fs::remove_dir_all(&full_path)
             .map_err(|e| format!("Failed to remove directory: {}", e))?;
     }
@@ -526,13 +593,7 @@ async fn remove_directory_recursive(app: tauri::AppHandle, path: String) -> Resu
 
 #[tauri::command]
 async fn save_binary_file(app: tauri::AppHandle, path: String, data: Vec<u8>) -> Result<(), String> {
-    use tauri::Manager;
-    
-    let app_data_dir = app.path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
-    
-    let full_path = app_data_dir.join(&path);
+    let full_path = fs_sandbox::app_data_path(&app, &path)?;
     
     if let Some(parent) = full_path.parent() {
         fs::create_dir_all(parent)
@@ -548,78 +609,13 @@ async fn save_binary_file(app: tauri::AppHandle, path: String, data: Vec<u8>) ->
 
 #[tauri::command]
 fn add_dpi_metadata(data: Vec<u8>, dpi: u32) -> Result<Vec<u8>, String> {
-    let img = image::load_from_memory(&data)
-        .map_err(|e| format!("Failed to load image: {}", e))?;
-    
-    let mut output = Vec::new();
-    {
-        let encoder = image::codecs::png::PngEncoder::new(&mut output);
-        
-        img.write_with_encoder(encoder)
-            .map_err(|e| format!("Failed to encode PNG: {}", e))?;
-    }
-    
-    let mut final_output = Vec::new();
-    
-    final_output.extend_from_slice(&[137, 80, 78, 71, 13, 10, 26, 10]);
-    
-    let mut cursor = 8;
-    
-    while cursor < output.len() {
-        if cursor + 8 > output.len() { break; }
-        let length = u32::from_be_bytes([
-            output[cursor], output[cursor + 1], 
-            output[cursor + 2], output[cursor + 3]
-        ]);
-        
-        let chunk_type = &output[cursor + 4..cursor + 8];
-        
-        if chunk_type == b"IHDR" {
-            let chunk_size = 8 + length as usize + 4; 
-            final_output.extend_from_slice(&output[cursor..cursor + chunk_size]);
-            cursor += chunk_size;
-            
-            let pixels_per_meter = ((dpi as f64) / 0.0254) as u32;
-            let phys_data = [
-                (pixels_per_meter >> 24) as u8, (pixels_per_meter >> 16) as u8,
-                (pixels_per_meter >> 8) as u8, pixels_per_meter as u8,
-                (pixels_per_meter >> 24) as u8, (pixels_per_meter >> 16) as u8,
-                (pixels_per_meter >> 8) as u8, pixels_per_meter as u8,
-                1,
-            ];
-            
-            final_output.extend_from_slice(&9u32.to_be_bytes());
-            final_output.extend_from_slice(b"pHYs"); 
-            final_output.extend_from_slice(&phys_data);
-            
-            let mut crc_data = Vec::new();
-            crc_data.extend_from_slice(b"pHYs");
-            crc_data.extend_from_slice(&phys_data);
-            let crc = crc32fast::hash(&crc_data);
-            final_output.extend_from_slice(&crc.to_be_bytes());
-            
-        } else {
-            let chunk_size = 8 + length as usize + 4;
-            if cursor + chunk_size <= output.len() {
-                final_output.extend_from_slice(&output[cursor..cursor + chunk_size]);
-            }
-            cursor += chunk_size;
-        }
-    }
-    
-    Ok(final_output)
+    image_dpi::set_dpi(&data, dpi as f64, dpi as f64)
 }
 
 
 #[tauri::command]
 async fn save_png_with_dpi(app: tauri::AppHandle, path: String, data: Vec<u8>, dpi: u32) -> Result<(), String> {
-    use tauri::Manager;
-    
-    let app_data_dir = app.path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
-    
-    let full_path = app_data_dir.join(&path);
+    let full_path = fs_sandbox::app_data_path(&app, &path)?;
     
     if let Some(parent) = full_path.parent() {
         fs::create_dir_all(parent)
@@ -636,13 +632,7 @@ async fn save_png_with_dpi(app: tauri::AppHandle, path: String, data: Vec<u8>, d
 
 #[tauri::command]
 async fn read_binary_file_as_base64(app: tauri::AppHandle, path: String) -> Result<String, String> {
-    use tauri::Manager;
-    
-    let app_data_dir = app.path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
-    
-    let full_path = app_data_dir.join(&path);
+    let full_path = fs_sandbox::app_data_path(&app, &path)?;
     
     let data = fs::read(&full_path)
         .map_err(|e| format!("Failed to read binary file: {}", e))?;
@@ -652,15 +642,10 @@ async fn read_binary_file_as_base64(app: tauri::AppHandle, path: String) -> Resu
 
 #[tauri::command]
 async fn copy_binary_file(app: tauri::AppHandle, source_path: String, target_path: String) -> Result<(), String> {
-    use tauri::Manager;
-    
-    let app_data_dir = app.path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
-    
-    let source_full_path = app_data_dir.join(&source_path);
+    let source_full_path = fs_sandbox::app_data_path(&app, &source_path)?;
+    let target_full_path = fs_sandbox::user_path(&app, &target_path)?;
     
-    fs::copy(&source_full_path, &target_path)
+    fs::copy(&source_full_path, &target_full_path)
         .map_err(|e| format!("Failed to copy file: {}", e))?;
     
     Ok(())
@@ -676,6 +661,19 @@ pub fn run() {
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_http::init())
         .plugin(tauri_plugin_deep_link::init())
+        .register_asynchronous_uri_scheme_protocol(binary_ipc::PROTOCOL, |ctx, request, responder| {
+            binary_ipc::handle_protocol(ctx.app_handle().clone(), request, responder);
+        })
+        .setup(|app| {
+            comfyui_connection::load_profiles(app.handle());
+            credentials::load_credentials(app.handle());
+            assets::load_assets(app.handle());
+            subgraph_client::load_config(app.handle());
+            catalog_snapshot::load_snapshots(app.handle());
+            ipfs::load_config(app.handle());
+            template_types::load_registry(app.handle());
+            Ok(())
+        })
         .invoke_handler(
             tauri::generate_handler![
                 save_image_with_dialog,
@@ -683,16 +681,77 @@ pub fn run() {
                 write_file_bytes,
                 replicate_create_prediction,
                 replicate_get_prediction,
+                replicate::replicate_run_prediction,
+                replicate::replicate_cancel_prediction,
+                generation::generation_configure_provider,
+                generation::generation_list_providers,
+                generation::generation_submit,
+                generation::generation_status,
+                generation::generation_cancel,
+                generation::generation_outputs,
+                credentials::credentials_status,
+                credentials::credentials_unlock,
+                credentials::credentials_lock,
+                credentials::credentials_set_passphrase,
+                credentials::credentials_set,
+                credentials::credentials_list,
+                credentials::credentials_delete,
+                credentials::credentials_test,
+                assets::assets_add,
+                assets::assets_add_file,
+                assets::assets_get,
+                assets::assets_search,
+                assets::assets_read,
+                assets::assets_tag,
+                assets::assets_set_favourite,
+                assets::assets_delete,
+                assets::assets_thumbnail,
+                assets::assets_export,
+                binary_ipc::read_binary_file,
+                binary_ipc::save_binary_file_raw,
+                binary_ipc::write_image_bytes,
+                binary_ipc::download_image_bytes,
+                binary_ipc::comfyui_download_image_bytes,
+                binary_ipc::comfyui_upload_image_bytes,
+                subgraph_client::subgraph_get_config,
+                subgraph_client::subgraph_set_config,
+                subgraph_client::subgraph_test_connection,
+                catalog_snapshot::catalog_list_snapshots,
+                catalog_snapshot::catalog_refresh_snapshot,
+                catalog_snapshot::catalog_diff,
+                ipfs::ipfs_get_config,
+                ipfs::ipfs_set_config,
+                ipfs::ipfs_cache_stats,
+                ipfs::ipfs_clear_cache,
+                template_types::template_types_get,
+                template_types::template_types_set,
+                template_types::template_types_reset,
                 download_image_as_base64,
+                comfyui_connection::comfyui_set_connection_profile,
+                comfyui_connection::comfyui_remove_connection_profile,
+                comfyui_connection::comfyui_list_connection_profiles,
+                comfyui_connection::comfyui_test_connection,
                 comfyui_execute_workflow,
                 comfyui_get_history,
+                comfyui_jobs::comfyui_start_job,
+                comfyui_jobs::comfyui_cancel_job,
+                comfyui_jobs::comfyui_get_queue_position,
                 comfyui_upload_image,
                 comfyui_download_image,
                 parse_comfyui_workflow,
                 get_node_info,
                 comfyui_get_models,
+                comfyui_schema::comfyui_get_object_info,
+                comfyui_schema::comfyui_get_node_schema,
+                comfyui_validate::validate_comfyui_workflow,
+                comfyui_convert::comfyui_ui_to_api,
+                comfyui_convert::comfyui_api_to_ui,
+                comfyui_templates::comfyui_execute_template,
                 pattern_export::export_pattern_to_pdf,
                 pattern_export::crop_svg,
+                cutter_export::export_layout_to_hpgl,
+                cutter_export::export_layout_to_dxf,
+                print_calibration::verify_pattern_pdf_scale,
                 pattern_nesting::nest_pattern_pieces,
                 pattern_nesting::get_live_sparrow_svg,
                 pattern_nesting::get_sparrow_stats,
@@ -705,6 +764,9 @@ pub fn run() {
                 save_binary_file,
                 save_png_with_dpi,
                 add_dpi_metadata,
+                image_dpi::read_image_dpi,
+                image_provenance::embed_image_provenance,
+                image_provenance::read_image_provenance,
                 read_binary_file_as_base64,
                 copy_binary_file
             ]
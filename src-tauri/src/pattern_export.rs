@@ -1,9 +1,14 @@
 use svg2pdf::{ usvg, ConversionOptions };
 use chrono;
 use regex::Regex;
-use lopdf::{Document, Object};
+use lopdf::{ dictionary, Document, Object, ObjectId };
+use serde::{ Deserialize, Serialize };
+use crate::fs_sandbox;
+use crate::pdf_tiling::{ row_label, tile_label, tile_svg, TileOptions };
+use crate::print_calibration::{ stamp_calibration_marks, MarkPlacement, CALIBRATION_BAND_PT };
+use crate::pdf_layers::{ combine_layer_pdfs, split_svg_layers, text_string, unify_optional_content };
 
-fn svg_to_pdf_bytes(svg: &str, dpi: f32) -> Result<Vec<u8>, String> {
+fn render_svg_to_pdf(svg: &str, dpi: f32) -> Result<Vec<u8>, String> {
 
     let mut uopts = usvg::Options::default();
     uopts.fontdb_mut().load_system_fonts();
@@ -15,32 +20,184 @@ fn svg_to_pdf_bytes(svg: &str, dpi: f32) -> Result<Vec<u8>, String> {
         .map_err(|e| format!("SVG to PDF failed: {:?}", e))
 }
 
-fn merge_pdfs(pdfs: &[Vec<u8>]) -> Result<Vec<u8>, String> {
-    if pdfs.is_empty() {
-        return Err("no hay PDFs para unir".into());
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PdfMetadata {
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub subject: Option<String>,
+    pub keywords: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Bookmark {
+    pub title: String,
+    pub page: usize,
+    pub children: Vec<Bookmark>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MergeOptions {
+    pub metadata: PdfMetadata,
+    pub bookmarks: Vec<Bookmark>,
+    pub page_labels: Vec<String>,
+}
+
+struct OutlineNode {
+    title: String,
+    page: ObjectId,
+    children: Vec<OutlineNode>,
+    imported: Vec<(ObjectId, ObjectId)>,
+}
+
+fn outline_nodes(bookmarks: &[Bookmark], page_ids: &[ObjectId]) -> Vec<OutlineNode> {
+    bookmarks
+        .iter()
+        .filter_map(|b| {
+            page_ids.get(b.page).map(|&page| OutlineNode {
+                title: b.title.clone(),
+                page,
+                children: outline_nodes(&b.children, page_ids),
+                imported: Vec::new(),
+            })
+        })
+        .collect()
+}
+
+fn attach_imported(nodes: &mut [OutlineNode], page: ObjectId, chain: (ObjectId, ObjectId)) -> bool {
+    for node in nodes.iter_mut() {
+        if attach_imported(&mut node.children, page, chain) {
+            return true;
+        }
+        if node.page == page {
+            node.imported.push(chain);
+            return true;
+        }
     }
+    false
+}
 
+fn imported_items(document: &Document, chain: (ObjectId, ObjectId)) -> Vec<ObjectId> {
+    let mut items = vec![chain.0];
+    let mut current = chain.0;
+    while current != chain.1 && items.len() <= document.objects.len() {
+        match document.get_dictionary(current).and_then(|d| d.get(b"Next")).and_then(Object::as_reference) {
+            Ok(next) => {
+                items.push(next);
+                current = next;
+            }
+            Err(_) => break,
+        }
+    }
+    items
+}
+
+fn write_outline_level(
+    document: &mut Document,
+    nodes: &[OutlineNode],
+    imported: &[(ObjectId, ObjectId)],
+    parent: ObjectId
+) -> Option<(ObjectId, ObjectId, i64)> {
+    let ids: Vec<ObjectId> = nodes.iter().map(|_| document.new_object_id()).collect();
+    let mut items: Vec<ObjectId> = ids.clone();
+    for chain in imported {
+        items.extend(imported_items(document, *chain));
+    }
+    if items.is_empty() {
+        return None;
+    }
+
+    let mut count = items.len() as i64;
+    for (i, node) in nodes.iter().enumerate() {
+        let mut item = dictionary! {
+            "Title" => text_string(&node.title),
+            "Parent" => parent,
+            "Dest" => vec![Object::Reference(node.page), "Fit".into()],
+        };
+        if let Some((first, last, descendants)) = write_outline_level(document, &node.children, &node.imported, ids[i]) {
+            item.set("First", first);
+            item.set("Last", last);
+            item.set("Count", descendants);
+            count += descendants;
+        }
+        document.objects.insert(ids[i], Object::Dictionary(item));
+    }
+
+    for (i, id) in items.iter().enumerate() {
+        if let Ok(item) = document.get_object_mut(*id).and_then(Object::as_dict_mut) {
+            item.set("Parent", parent);
+            item.remove(b"Prev");
+            item.remove(b"Next");
+            if i > 0 {
+                item.set("Prev", items[i - 1]);
+            }
+            if i + 1 < items.len() {
+                item.set("Next", items[i + 1]);
+            }
+            if i >= ids.len() {
+                if let Ok(Object::Integer(n)) = item.get(b"Count") {
+                    count += (*n).max(0);
+                }
+            }
+        }
+    }
+    Some((items[0], items[items.len() - 1], count))
+}
+
+fn svg_to_pdf_bytes(svg: &str, dpi: f32) -> Result<Vec<u8>, String> {
+    let layers = match split_svg_layers(svg)? {
+        Some(layers) => layers,
+        None => return render_svg_to_pdf(svg, dpi),
+    };
+
+    let base = render_svg_to_pdf(&layers.base, dpi)?;
+    let mut layer_pdfs = Vec::with_capacity(layers.layers.len());
+    for (name, layer_svg) in &layers.layers {
+        let bytes = render_svg_to_pdf(layer_svg, dpi)
+            .map_err(|e| format!("layer {}: {}", name, e))?;
+        layer_pdfs.push((name.clone(), bytes));
+    }
+    combine_layer_pdfs(&base, &layer_pdfs)
+}
+
+fn merge_pdfs(pdfs: &[Vec<u8>], options: &MergeOptions) -> Result<Vec<u8>, String> {
     let documents: Result<Vec<Document>, _> = pdfs
         .iter()
         .map(|bytes| Document::load_mem(bytes))
         .collect();
     
     let documents = documents.map_err(|e| format!("lopdf load: {e:?}"))?;
+    if documents.iter().all(|doc| doc.get_pages().is_empty()) {
+        return Err("No pages to merge: the export produced an empty PDF".into());
+    }
 
     use std::collections::BTreeMap;
-    use lopdf::ObjectId;
 
     let mut max_id = 1;
     let mut documents_pages = BTreeMap::new();
     let mut documents_objects = BTreeMap::new();
+    let mut source_outlines = Vec::new();
     let mut document = Document::with_version("1.5");
 
     for mut doc in documents {
         doc.renumber_objects_with(max_id);
         max_id = doc.max_id + 1;
 
+        let pages = doc.get_pages();
+        let outline_root = doc
+            .catalog()
+            .and_then(|c| c.get(b"Outlines"))
+            .and_then(Object::as_reference)
+            .and_then(|id| doc.get_dictionary(id));
+        if let (Some(first_page), Ok(root)) = (pages.values().next(), outline_root) {
+            let first = root.get(b"First").and_then(Object::as_reference);
+            let last = root.get(b"Last").and_then(Object::as_reference);
+            if let (Ok(first), Ok(last)) = (first, last) {
+                source_outlines.push((*first_page, (first, last)));
+            }
+        }
+
         documents_pages.extend(
-            doc.get_pages()
+            pages
                 .into_values()
                 .map(|object_id| {
                     (object_id, doc.get_object(object_id).unwrap().to_owned())
@@ -86,7 +243,6 @@ fn merge_pdfs(pdfs: &[Vec<u8>]) -> Result<Vec<u8>, String> {
             }
             "Page" => {}
             "Outlines" => {}
-            "Outline" => {}
             _ => {
                 document.objects.insert(*object_id, object.clone());
             }
@@ -114,6 +270,7 @@ fn merge_pdfs(pdfs: &[Vec<u8>]) -> Result<Vec<u8>, String> {
 
     let catalog_object = catalog_object.unwrap();
     let pages_object = pages_object.unwrap();
+    let page_ids: Vec<ObjectId> = documents_pages.keys().copied().collect();
 
     if let Ok(dictionary) = pages_object.1.as_dict() {
         let mut dictionary = dictionary.clone();
@@ -131,10 +288,75 @@ fn merge_pdfs(pdfs: &[Vec<u8>]) -> Result<Vec<u8>, String> {
             .insert(pages_object.0, Object::Dictionary(dictionary));
     }
 
+    document.max_id = documents_objects.keys().map(|id| id.0).max().unwrap_or(0);
+
+    let mut nodes = outline_nodes(&options.bookmarks, &page_ids);
+    let mut top_level_imported = Vec::new();
+    for (first_page, chain) in source_outlines {
+        if !attach_imported(&mut nodes, first_page, chain) {
+            top_level_imported.push(chain);
+        }
+    }
+    let outlines_id = document.new_object_id();
+    let outline = write_outline_level(&mut document, &nodes, &top_level_imported, outlines_id);
+    if let Some((first, last, count)) = outline {
+        document.objects.insert(
+            outlines_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Outlines",
+                "First" => first,
+                "Last" => last,
+                "Count" => count,
+            })
+        );
+    }
+
+    let metadata = &options.metadata;
+    let now = chrono::Local::now();
+    let mut info = dictionary! {
+        "Creator" => Object::string_literal("Coin Op"),
+        "Producer" => Object::string_literal("Coin Op"),
+        "CreationDate" => now,
+        "ModDate" => now,
+    };
+    for (key, value) in [
+        ("Title", &metadata.title),
+        ("Author", &metadata.author),
+        ("Subject", &metadata.subject),
+        ("Keywords", &metadata.keywords),
+    ] {
+        if let Some(value) = value {
+            info.set(key, text_string(value));
+        }
+    }
+    let info_id = document.add_object(info);
+
+    let optional_content = unify_optional_content(&mut document);
+
     if let Ok(dictionary) = catalog_object.1.as_dict() {
         let mut dictionary = dictionary.clone();
         dictionary.set("Pages", pages_object.0);
+        dictionary.remove(b"OCProperties");
+        if let Some(properties) = optional_content {
+            dictionary.set("OCProperties", properties);
+        }
         dictionary.remove(b"Outlines");
+        dictionary.remove(b"PageLabels");
+        if outline.is_some() {
+            dictionary.set("Outlines", outlines_id);
+            dictionary.set("PageMode", "UseOutlines");
+        }
+        if !options.page_labels.is_empty() {
+            let nums = options.page_labels
+                .iter()
+                .take(page_ids.len())
+                .enumerate()
+                .flat_map(|(i, label)| {
+                    vec![Object::Integer(i as i64), Object::Dictionary(dictionary! { "P" => text_string(label) })]
+                })
+                .collect::<Vec<_>>();
+            dictionary.set("PageLabels", dictionary! { "Nums" => nums });
+        }
 
         document
             .objects
@@ -142,7 +364,8 @@ fn merge_pdfs(pdfs: &[Vec<u8>]) -> Result<Vec<u8>, String> {
     }
 
     document.trailer.set("Root", catalog_object.0);
-    document.max_id = document.objects.len() as u32;
+    document.trailer.set("Info", info_id);
+    document.max_id = document.objects.keys().map(|id| id.0).max().unwrap_or(0);
     document.renumber_objects();
     document.adjust_zero_pages();
     document.compress();
@@ -152,90 +375,173 @@ fn merge_pdfs(pdfs: &[Vec<u8>]) -> Result<Vec<u8>, String> {
     Ok(out)
 }
 
+fn svg_page_title(svg: &str) -> Option<String> {
+    let re = Regex::new(r#"<title[^>]*>([^<]+)</title>"#).ok()?;
+    let title = re.captures(svg)?.get(1)?.as_str().trim();
+    match title.is_empty() {
+        true => None,
+        false => Some(title.to_string()),
+    }
+}
+
+/// Renders each page of a pattern SVG (one page, or the `data-pages` list) into a single PDF, stamping calibration
+/// marks with `calibration` when given.
+fn pattern_pdf(
+    svg_string: String,
+    metadata: PdfMetadata,
+    calibration: Option<MarkPlacement>
+) -> Result<Vec<u8>, String> {
+    let page_svgs = extract_data_pages(&svg_string).unwrap_or_else(|| vec![svg_string]);
+    let dpi = 96.0;
+    let mut page_pdfs = Vec::with_capacity(page_svgs.len());
+    let mut bookmarks = Vec::with_capacity(page_svgs.len());
+    let mut page_labels = Vec::with_capacity(page_svgs.len());
+    for (i, s) in page_svgs.iter().enumerate() {
+        let bytes = svg_to_pdf_bytes(s, dpi)
+            .map_err(|e| format!("page {}: {}", i + 1, e))?;
+        page_pdfs.push(bytes);
+        let title = svg_page_title(s).unwrap_or_else(|| format!("Page {}", i + 1));
+        page_labels.push(title.clone());
+        bookmarks.push(Bookmark { title, page: i, children: Vec::new() });
+    }
+    if page_svgs.len() == 1 && svg_page_title(&page_svgs[0]).is_none() {
+        bookmarks.clear();
+        page_labels.clear();
+    }
+
+    let bytes = merge_pdfs(&page_pdfs, &MergeOptions { metadata, bookmarks, page_labels })?;
+    match calibration {
+        Some(placement) => stamp_calibration_marks(&bytes, placement, &[]),
+        None => Ok(bytes),
+    }
+}
+
+/// Calibration marks go in the bottom `CALIBRATION_BAND_PT` of each page, which the SVG has to keep clear, so pages
+/// print at their own size. `extend_page` adds the band below each page instead, making it taller than the SVG.
 #[tauri::command]
 pub async fn export_pattern_to_pdf(
+    app: tauri::AppHandle,
     svg_string: String,
-    out_path: Option<String>
+    out_path: Option<String>,
+    calibration: Option<bool>,
+    extend_page: Option<bool>,
+    metadata: Option<PdfMetadata>
 ) -> Result<String, String> {
-    
-    let output_path = out_path.unwrap_or_else(|| {
-        format!("pattern_export_{}.pdf", chrono::Utc::now().format("%Y%m%d_%H%M%S"))
-    });
+    let output_path = fs_sandbox::export_path(
+        &app,
+        out_path.as_deref(),
+        &format!("pattern_export_{}.pdf", chrono::Utc::now().format("%Y%m%d_%H%M%S"))
+    )?;
 
-    if let Some(page_svgs) = extract_data_pages(&svg_string) {
-        let dpi = 96.0;
-        let mut page_pdfs = Vec::with_capacity(page_svgs.len());
-        for (i, s) in page_svgs.iter().enumerate() {
-            let bytes = svg_to_pdf_bytes(s, dpi)
-                .map_err(|e| format!("page {}: {}", i + 1, e))?;
-            page_pdfs.push(bytes);
-        }
-        let merged = merge_pdfs(&page_pdfs)?;
-        std::fs::write(&output_path, merged)
-            .map_err(|e| format!("write merged pdf: {e}"))?;
-        return Ok(output_path);
-    }
+    let mut metadata = metadata.unwrap_or_default();
+    metadata.title.get_or_insert_with(|| "Pattern".to_string());
+    let placement = match extend_page.unwrap_or(false) {
+        true => MarkPlacement::ExtendPage,
+        false => MarkPlacement::ReservedBand,
+    };
+    let bytes = pattern_pdf(svg_string, metadata, calibration.unwrap_or(true).then_some(placement))?;
 
-    let bytes = svg_to_pdf_bytes(&svg_string, 96.0)?;
     std::fs::write(&output_path, bytes)
         .map_err(|e| format!("write pdf: {e}"))?;
-    Ok(output_path)
+    Ok(output_path.to_string_lossy().to_string())
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CropSvgRequest {
+    #[serde(rename = "svgString")]
+    pub svg_string: String,
+    #[serde(rename = "canvasWidthPt")]
+    pub canvas_width_pt: f64,
+    #[serde(rename = "canvasHeightPt")]
+    pub canvas_height_pt: f64,
+    #[serde(rename = "outPath", default)]
+    pub out_path: Option<String>,
+    /// Paper, overlap and marks; the A4 defaults when unset
+    #[serde(default)]
+    pub options: Option<TileOptions>,
+    #[serde(default)]
+    pub calibration: Option<bool>,
+    #[serde(default)]
+    pub metadata: Option<PdfMetadata>,
 }
 
 #[tauri::command]
-pub async fn crop_svg(
-    svg_string: String,
-    canvas_width_pt: f64,
-    canvas_height_pt: f64,
-    out_path: Option<String>
-) -> Result<String, String> {
-   
-    const A4_WIDTH_PT: f64 = 595.0;
-    const A4_HEIGHT_PT: f64 = 842.0;
-    
-    if canvas_width_pt <= A4_WIDTH_PT && canvas_height_pt <= A4_HEIGHT_PT {
-        return export_pattern_to_pdf(svg_string, out_path).await;
+pub async fn crop_svg(app: tauri::AppHandle, request: CropSvgRequest) -> Result<String, String> {
+    let CropSvgRequest { svg_string, canvas_width_pt, canvas_height_pt, out_path, options, calibration, metadata } =
+        request;
+    if let Some(path) = &out_path {
+        fs_sandbox::user_path(&app, path)?;
     }
-    
-    let output = std::process::Command::new("node")
-        .arg("svg_crop.js")
-        .arg(&svg_string)
-        .arg(canvas_width_pt.to_string())
-        .arg(canvas_height_pt.to_string())
-        .output()
-        .map_err(|e| format!("Failed to run SVG cropper: {}", e))?;
-    
-    if !output.status.success() {
-        let error = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("SVG cropper failed: {}", error));
+    let calibrate = calibration.unwrap_or(true);
+    let mut options = options.unwrap_or_default();
+    if calibrate {
+        options.reserved_bottom_pt = CALIBRATION_BAND_PT;
     }
-    
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    
-    let tiles: Vec<String> = serde_json::from_str(&stdout)
-        .map_err(|e| format!("Failed to parse cropper output: {} | STDOUT: {}", e, stdout))?;
-    
+    let (page_width, page_height) = options.page_size();
+
+    // The canvas and the calibration band fit on one sheet, so the band can go below the canvas
+    if canvas_width_pt <= page_width && canvas_height_pt + options.reserved_bottom_pt <= page_height {
+        return export_pattern_to_pdf(app, svg_string, out_path, Some(calibrate), Some(true), metadata).await;
+    }
+
+    let tiled = tile_svg(&svg_string, canvas_width_pt, canvas_height_pt, &options)?;
+    let grid = &tiled.grid;
 
-    let mut page_pdfs = Vec::with_capacity(tiles.len());
-    for (i, tile_svg) in tiles.iter().enumerate() {
+    let mut page_pdfs = Vec::with_capacity(tiled.pages.len());
+    let mut page_labels = Vec::with_capacity(tiled.pages.len());
+    let mut bookmarks = Vec::new();
+    let mut rows: Vec<Bookmark> = Vec::new();
+    for (i, (tile, tile_svg)) in tiled.pages.iter().enumerate() {
         let pdf_bytes = svg_to_pdf_bytes(tile_svg, 96.0)
             .map_err(|e| format!("tile {} PDF conversion: {}", i + 1, e))?;
         page_pdfs.push(pdf_bytes);
+
+        match tile {
+            None => {
+                page_labels.push("Cover".to_string());
+                bookmarks.push(Bookmark { title: "Assembly map".to_string(), page: i, children: Vec::new() });
+            }
+            Some(tile) => {
+                page_labels.push(tile_label(tile.row, tile.col));
+                let row_title = format!("Row {}", row_label(tile.row));
+                if rows.last().is_none_or(|r| r.title != row_title) {
+                    rows.push(Bookmark { title: row_title, page: i, children: Vec::new() });
+                }
+                if let Some(row) = rows.last_mut() {
+                    row.children.push(Bookmark { title: format!("Col {}", tile.col + 1), page: i, children: Vec::new() });
+                }
+            }
+        }
     }
-    
-  
-    let merged = merge_pdfs(&page_pdfs)?;
-    
-    let output_path = out_path.unwrap_or_else(|| {
-        let cols = (canvas_width_pt / A4_WIDTH_PT).ceil() as usize;
-        let rows = (canvas_height_pt / A4_HEIGHT_PT).ceil() as usize;
-        format!("pattern_cropped_{}x{}_{}.pdf", cols, rows, chrono::Utc::now().format("%Y%m%d_%H%M%S"))
+    if let Some(first) = rows.first() {
+        let page = first.page;
+        bookmarks.push(Bookmark { title: "Tiles".to_string(), page, children: rows });
+    }
+
+    let mut metadata = metadata.unwrap_or_default();
+    metadata.title.get_or_insert_with(|| format!("Pattern ({} × {} tiles)", grid.cols, grid.rows));
+    metadata.subject.get_or_insert_with(|| {
+        format!("Tiled on {} with {:.0} pt overlap", options.paper.name(), options.overlap_pt)
     });
-    
+
+    let merged = merge_pdfs(&page_pdfs, &MergeOptions { metadata, bookmarks, page_labels })?;
+    let merged = if calibrate {
+        let cover: &[u32] = if options.cover_page { &[1] } else { &[] };
+        stamp_calibration_marks(&merged, MarkPlacement::ReservedBand, cover)?
+    } else {
+        merged
+    };
+
+    let output_path = fs_sandbox::export_path(
+        &app,
+        out_path.as_deref(),
+        &format!("pattern_cropped_{}x{}_{}.pdf", grid.cols, grid.rows, chrono::Utc::now().format("%Y%m%d_%H%M%S"))
+    )?;
+
     std::fs::write(&output_path, merged)
         .map_err(|e| format!("write cropped pdf: {e}"))?;
-    
-    Ok(output_path)
+
+    Ok(output_path.to_string_lossy().to_string())
 }
 
 fn extract_data_pages(svg_string: &str) -> Option<Vec<String>> {
@@ -262,3 +568,249 @@ fn extract_data_pages(svg_string: &str) -> Option<Vec<String>> {
 }
 
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::print_calibration::verify_pdf_scale;
+    use lopdf::{ Dictionary, Stream };
+
+    fn pdf_without_pages() -> Vec<u8> {
+        let mut doc = Document::with_version("1.5");
+        let pages = doc.add_object(dictionary! { "Type" => "Pages", "Count" => 0, "Kids" => Vec::<Object>::new() });
+        let catalog = doc.add_object(dictionary! { "Type" => "Catalog", "Pages" => pages });
+        doc.trailer.set("Root", catalog);
+        let mut out = Vec::new();
+        doc.save_to(&mut out).unwrap();
+        out
+    }
+
+    /// A PDF of `pages` pages whose outline has one bookmark per entry of `outline`, on the page of the same index,
+    /// with the given children
+    fn pdf_with_outline(pages: usize, outline: &[(&str, &[&str])]) -> Vec<u8> {
+        let mut doc = Document::with_version("1.5");
+        let pages_id = doc.new_object_id();
+        let page_ids: Vec<ObjectId> = (0..pages)
+            .map(|_| {
+                let content = doc.add_object(Stream::new(Dictionary::new(), b"0 0 10 10 re f".to_vec()));
+                doc.add_object(dictionary! { "Type" => "Page", "Parent" => pages_id, "Contents" => content })
+            })
+            .collect();
+        let media_box = vec![0.into(), 0.into(), 595.into(), 842.into()];
+        doc.objects.insert(
+            pages_id,
+            Object::Dictionary(
+                dictionary! {
+                    "Type" => "Pages",
+                    "Count" => pages as i64,
+                    "Kids" => page_ids.iter().map(|id| Object::Reference(*id)).collect::<Vec<_>>(),
+                    "MediaBox" => media_box,
+                }
+            )
+        );
+        let catalog = doc.add_object(dictionary! { "Type" => "Catalog", "Pages" => pages_id });
+
+        let nodes: Vec<OutlineNode> = outline
+            .iter()
+            .zip(&page_ids)
+            .map(|((title, children), page)| OutlineNode {
+                title: title.to_string(),
+                page: *page,
+                children: children
+                    .iter()
+                    .map(|child| OutlineNode {
+                        title: child.to_string(),
+                        page: *page,
+                        children: Vec::new(),
+                        imported: Vec::new(),
+                    })
+                    .collect(),
+                imported: Vec::new(),
+            })
+            .collect();
+        let outlines_id = doc.new_object_id();
+        if let Some((first, last, count)) = write_outline_level(&mut doc, &nodes, &[], outlines_id) {
+            doc.objects.insert(
+                outlines_id,
+                Object::Dictionary(
+                    dictionary! { "Type" => "Outlines", "First" => first, "Last" => last, "Count" => count }
+                )
+            );
+            doc.get_object_mut(catalog).and_then(Object::as_dict_mut).unwrap().set("Outlines", outlines_id);
+        }
+        doc.trailer.set("Root", catalog);
+        let mut out = Vec::new();
+        doc.save_to(&mut out).unwrap();
+        out
+    }
+
+    fn text(object: &Object) -> String {
+        let bytes = object.as_str().unwrap();
+        match bytes.strip_prefix(&[0xfe, 0xff]) {
+            Some(utf16) => {
+                let units: Vec<u16> = utf16.chunks(2).map(|pair| u16::from_be_bytes([pair[0], pair[1]])).collect();
+                String::from_utf16(&units).unwrap()
+            }
+            None => String::from_utf8(bytes.to_vec()).unwrap(),
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Item {
+        title: String,
+        /// Index of the page the bookmark opens
+        page: usize,
+        count: Option<i64>,
+        children: Vec<Item>,
+    }
+
+    fn item(title: &str, page: usize, count: Option<i64>, children: Vec<Item>) -> Item {
+        Item { title: title.to_string(), page, count, children }
+    }
+
+    /// Reads the items under `parent`, checking their Parent, Prev and Next links and the parent's First and Last
+    fn outline_items(doc: &Document, parent: ObjectId, pages: &[ObjectId]) -> Vec<Item> {
+        let parent_dict = doc.get_dictionary(parent).unwrap();
+        let Ok(first) = parent_dict.get(b"First").and_then(Object::as_reference) else {
+            assert!(parent_dict.get(b"Last").is_err());
+            return Vec::new();
+        };
+        let mut items = Vec::new();
+        let (mut previous, mut current) = (None, Some(first));
+        while let Some(id) = current {
+            let dict = doc.get_dictionary(id).unwrap();
+            assert_eq!(dict.get(b"Parent").and_then(Object::as_reference).unwrap(), parent);
+            assert_eq!(dict.get(b"Prev").and_then(Object::as_reference).ok(), previous);
+            let destination = dict.get(b"Dest").and_then(Object::as_array).unwrap();
+            let page = destination[0].as_reference().unwrap();
+            items.push(
+                item(
+                    &text(dict.get(b"Title").unwrap()),
+                    pages.iter().position(|p| *p == page).unwrap(),
+                    dict.get(b"Count").and_then(Object::as_i64).ok(),
+                    outline_items(doc, id, pages)
+                )
+            );
+            previous = Some(id);
+            current = dict.get(b"Next").and_then(Object::as_reference).ok();
+        }
+        assert_eq!(parent_dict.get(b"Last").and_then(Object::as_reference).ok(), previous);
+        items
+    }
+
+    #[test]
+    fn merged_outlines_metadata_and_page_labels() {
+        let cover = pdf_with_outline(1, &[]);
+        let pieces = pdf_with_outline(2, &[("Front", &["Notch", "Grainline"]), ("Back", &[])]);
+        let options = MergeOptions {
+            metadata: PdfMetadata {
+                title: Some("Pièces de patron".to_string()),
+                author: Some("Atelier".to_string()),
+                subject: None,
+                keywords: Some("jacket".to_string()),
+            },
+            bookmarks: vec![
+                Bookmark { title: "Assembly map".to_string(), page: 0, children: Vec::new() },
+                Bookmark {
+                    title: "Pieces".to_string(),
+                    page: 1,
+                    children: vec![Bookmark { title: "Back piece".to_string(), page: 2, children: Vec::new() }],
+                },
+                Bookmark { title: "Missing".to_string(), page: 9, children: Vec::new() },
+            ],
+            page_labels: vec!["Cover".to_string(), "A1".to_string(), "A2".to_string(), "A3".to_string()],
+        };
+
+        let merged = merge_pdfs(&[cover, pieces], &options).unwrap();
+        let doc = Document::load_mem(&merged).unwrap();
+        let pages: Vec<ObjectId> = doc.get_pages().into_values().collect();
+        assert_eq!(pages.len(), 3);
+
+        let catalog = doc.catalog().unwrap();
+        assert_eq!(catalog.get(b"PageMode").and_then(Object::as_name_str).unwrap(), "UseOutlines");
+        let outlines_id = catalog.get(b"Outlines").and_then(Object::as_reference).unwrap();
+        let outlines = doc.get_dictionary(outlines_id).unwrap();
+        assert_eq!(outlines.get(b"Type").and_then(Object::as_name_str).unwrap(), "Outlines");
+        assert_eq!(
+            outline_items(&doc, outlines_id, &pages),
+            [
+                item("Assembly map", 0, None, Vec::new()),
+                item(
+                    "Pieces",
+                    1,
+                    Some(5),
+                    vec![
+                        item("Back piece", 2, None, Vec::new()),
+                        item(
+                            "Front",
+                            1,
+                            Some(2),
+                            vec![item("Notch", 1, None, Vec::new()), item("Grainline", 1, None, Vec::new())]
+                        ),
+                        item("Back", 2, None, Vec::new())
+                    ]
+                ),
+            ]
+        );
+        assert_eq!(outlines.get(b"Count").and_then(Object::as_i64).unwrap(), 7);
+
+        let info_id = doc.trailer.get(b"Info").and_then(Object::as_reference).unwrap();
+        let info = doc.get_dictionary(info_id).unwrap();
+        assert_eq!(text(info.get(b"Title").unwrap()), "Pièces de patron");
+        assert_eq!(text(info.get(b"Author").unwrap()), "Atelier");
+        assert_eq!(text(info.get(b"Keywords").unwrap()), "jacket");
+        assert!(info.get(b"Subject").is_err());
+        assert_eq!(text(info.get(b"Creator").unwrap()), "Coin Op");
+        let created = text(info.get(b"CreationDate").unwrap());
+        assert!(created.starts_with(&format!("D:{}", chrono::Local::now().format("%Y"))), "{}", created);
+
+        let labels = catalog.get(b"PageLabels").and_then(Object::as_dict).unwrap();
+        let nums = labels.get(b"Nums").and_then(Object::as_array).unwrap();
+        let labels: Vec<(i64, String)> = nums
+            .chunks(2)
+            .map(|pair| {
+                let label = pair[1].as_dict().unwrap().get(b"P").unwrap();
+                (pair[0].as_i64().unwrap(), text(label))
+            })
+            .collect();
+        assert_eq!(labels, [(0, "Cover".to_string()), (1, "A1".to_string()), (2, "A2".to_string())]);
+    }
+
+    const A4_SVG: &str = concat!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="210mm" height="297mm" viewBox="0 0 210 297">"#,
+        r#"<rect x="20" y="20" width="170" height="150" fill="none" stroke="black"/></svg>"#
+    );
+
+    #[test]
+    fn calibrated_exports_keep_their_page_size_and_verify_at_full_scale() {
+        let pages = serde_json::to_string(&[A4_SVG.replace("<rect", "<title>Front</title><rect"), A4_SVG.to_string()])
+            .unwrap()
+            .replace('"', "___QUOT___")
+            .replace('<', "___LT___")
+            .replace('>', "___GT___");
+        let svg = format!(r#"<svg xmlns="http://www.w3.org/2000/svg" data-pages="{}"/>"#, pages);
+
+        let reserved = pattern_pdf(svg.clone(), PdfMetadata::default(), Some(MarkPlacement::ReservedBand)).unwrap();
+        let report = verify_pdf_scale(&reserved, Some(210.0), Some(297.0), 0.5, &[]).unwrap();
+        assert!(report.ok, "{:?}", report);
+        assert_eq!(report.pages.len(), 2);
+
+        let extended = pattern_pdf(svg.clone(), PdfMetadata::default(), Some(MarkPlacement::ExtendPage)).unwrap();
+        let band_mm = CALIBRATION_BAND_PT * 25.4 / 72.0;
+        let report = verify_pdf_scale(&extended, Some(210.0), Some(297.0 + band_mm), 0.5, &[]).unwrap();
+        assert!(report.ok, "{:?}", report);
+        assert!(!verify_pdf_scale(&extended, Some(210.0), Some(297.0), 0.5, &[]).unwrap().ok);
+
+        let plain = pattern_pdf(svg, PdfMetadata::default(), None).unwrap();
+        let report = verify_pdf_scale(&plain, Some(210.0), Some(297.0), 0.5, &[]).unwrap();
+        assert!(report.pages.iter().all(|p| p.media_box_ok && !p.ruler_ok));
+    }
+
+    #[test]
+    fn merging_nothing_is_a_clear_error() {
+        for pdfs in [Vec::new(), vec![pdf_without_pages(), pdf_without_pages()]] {
+            let error = merge_pdfs(&pdfs, &MergeOptions::default()).unwrap_err();
+            assert!(error.starts_with("No pages to merge"), "{}", error);
+        }
+    }
+}
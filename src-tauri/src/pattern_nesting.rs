@@ -9,7 +9,6 @@ use std::process::{ Child, Command };
 #[cfg(unix)]
 use std::os::unix::process::CommandExt;
 use once_cell::sync::Lazy;
-use reqwest;
 static SPARROW_PROCESS: Lazy<Arc<Mutex<Option<Child>>>> = Lazy::new(|| Arc::new(Mutex::new(None)));
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -531,19 +530,8 @@ fn extract_stats_from_svg(svg_content: &str) -> SparrowStats {
 }
 
 async fn get_svg_content(svg_path: &str) -> Result<String> {
-    let ipfs_url = if svg_path.starts_with("ipfs://") {
-        format!("https://thedial.infura-ipfs.io/ipfs/{}", svg_path.replace("ipfs://", ""))
-    } else if svg_path.starts_with("Qm") {
-        format!("https://thedial.infura-ipfs.io/ipfs/{}", svg_path)
-    } else {
+    if !svg_path.starts_with("ipfs://") && !svg_path.starts_with("Qm") {
         return Err(anyhow::anyhow!("Invalid SVG path format. Expected IPFS URI but got: {}", svg_path));
-    };
-    
-    let response = reqwest::get(&ipfs_url).await?;
-    if response.status().is_success() {
-        let svg_content = response.text().await?;
-        Ok(svg_content)
-    } else {
-        Err(anyhow::anyhow!("Failed to fetch IPFS content from: {}", ipfs_url))
     }
+    crate::ipfs::fetch_text(svg_path).await.map_err(anyhow::Error::msg)
 }
@@ -0,0 +1,328 @@
+use lopdf::{ dictionary, Dictionary, Document, Object, ObjectId, Stream };
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+const LAYER_ATTRIBUTES: [&str; 2] = ["data-layer", "data-size"];
+
+/// Non-rendering elements that layer content may reference, kept in every layer.
+const DEFINITION_ELEMENTS: [&str; 10] = [
+    "defs",
+    "style",
+    "clipPath",
+    "mask",
+    "marker",
+    "pattern",
+    "symbol",
+    "linearGradient",
+    "radialGradient",
+    "filter",
+];
+
+/// A copy of the SVG for each tagged layer holding only that layer's elements, plus one with every tagged element
+/// removed.
+pub struct SvgLayers {
+    pub base: String,
+    pub layers: Vec<(String, String)>,
+}
+
+fn layer_name(node: &roxmltree::Node) -> Option<String> {
+    LAYER_ATTRIBUTES.iter()
+        .find_map(|attr| node.attribute(*attr))
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+fn without_ranges(svg: &str, ranges: &[&Range<usize>]) -> String {
+    let mut out = String::with_capacity(svg.len());
+    let mut pos = 0;
+    for range in ranges {
+        out.push_str(&svg[pos..range.start]);
+        pos = range.end;
+    }
+    out.push_str(&svg[pos..]);
+    out
+}
+
+/// Collects the ranges to cut from `node` so that only definitions and the elements tagged `name` remain, along with
+/// the groups wrapping them. Returns whether anything of the layer was found.
+fn cut_to_layer(node: roxmltree::Node, name: &str, cut: &mut Vec<Range<usize>>) -> bool {
+    let mut found = false;
+    for child in node.children().filter(|n| n.is_element()) {
+        if DEFINITION_ELEMENTS.contains(&child.tag_name().name()) {
+            continue;
+        }
+        match layer_name(&child) {
+            Some(tag) if tag == name => {
+                found = true;
+            }
+            Some(_) => cut.push(child.range()),
+            None => {
+                let mut inner = Vec::new();
+                if cut_to_layer(child, name, &mut inner) {
+                    found = true;
+                    cut.extend(inner);
+                } else {
+                    cut.push(child.range());
+                }
+            }
+        }
+    }
+    found
+}
+
+/// Splits an SVG on elements tagged with `data-layer` or `data-size`, returning `None` if nothing is tagged.
+pub fn split_svg_layers(svg: &str) -> Result<Option<SvgLayers>, String> {
+    let doc = roxmltree::Document::parse(svg).map_err(|e| format!("SVG parse failed: {}", e))?;
+
+    let mut tagged: Vec<(String, Range<usize>)> = Vec::new();
+    for node in doc.descendants().filter(|n| n.is_element()) {
+        if let Some(name) = layer_name(&node) {
+            if !node.ancestors().skip(1).any(|a| layer_name(&a).is_some()) {
+                tagged.push((name, node.range()));
+            }
+        }
+    }
+    if tagged.is_empty() {
+        return Ok(None);
+    }
+
+    let mut names: Vec<String> = Vec::new();
+    for (name, _) in &tagged {
+        if !names.contains(name) {
+            names.push(name.clone());
+        }
+    }
+
+    let base = without_ranges(svg, &tagged.iter().map(|(_, r)| r).collect::<Vec<_>>());
+    let layers = names
+        .into_iter()
+        .map(|name| {
+            let mut hidden = Vec::new();
+            cut_to_layer(doc.root_element(), &name, &mut hidden);
+            let layer_svg = without_ranges(svg, &hidden.iter().collect::<Vec<_>>());
+            (name, layer_svg)
+        })
+        .collect();
+    Ok(Some(SvgLayers { base, layers }))
+}
+
+pub fn text_string(s: &str) -> Object {
+    if s.is_ascii() {
+        return Object::string_literal(s);
+    }
+    let mut bytes = vec![0xFE, 0xFF];
+    for unit in s.encode_utf16() {
+        bytes.extend_from_slice(&unit.to_be_bytes());
+    }
+    Object::String(bytes, lopdf::StringFormat::Hexadecimal)
+}
+
+fn first_page(doc: &Document) -> Result<ObjectId, String> {
+    doc.get_pages().into_values().next().ok_or_else(|| "PDF has no pages".to_string())
+}
+
+/// Combines a base page with one page per layer, drawing each layer as a form XObject in its own optional content group.
+pub fn combine_layer_pdfs(base_pdf: &[u8], layer_pdfs: &[(String, Vec<u8>)]) -> Result<Vec<u8>, String> {
+    let mut doc = Document::load_mem(base_pdf).map_err(|e| format!("lopdf load: {e:?}"))?;
+    let page_id = first_page(&doc)?;
+
+    let mut xobjects = Dictionary::new();
+    let mut ocgs = Vec::new();
+    let mut content = String::new();
+
+    for (i, (name, pdf)) in layer_pdfs.iter().enumerate() {
+        let mut layer = Document::load_mem(pdf).map_err(|e| format!("layer {} load: {e:?}", name))?;
+        layer.renumber_objects_with(doc.max_id + 1);
+        doc.max_id = layer.max_id;
+
+        let layer_page = first_page(&layer)?;
+        let page = layer.get_dictionary(layer_page).map_err(|e| format!("layer {} page: {e:?}", name))?;
+        let bbox = page.get(b"MediaBox").cloned().map_err(|_| format!("layer {} has no MediaBox", name))?;
+        let resources = page.get(b"Resources").cloned().unwrap_or_else(|_| Object::Dictionary(Dictionary::new()));
+        let contents = layer.get_page_content(layer_page).map_err(|e| format!("layer {} content: {e:?}", name))?;
+        let skip = layer.get_page_contents(layer_page);
+
+        for (id, object) in layer.objects {
+            let type_name = object.type_name().unwrap_or("").to_string();
+            if skip.contains(&id) || matches!(type_name.as_str(), "Catalog" | "Pages" | "Page") {
+                continue;
+            }
+            doc.objects.insert(id, object);
+        }
+
+        let ocg = doc.add_object(dictionary! { "Type" => "OCG", "Name" => text_string(name) });
+        let form = doc.add_object(
+            Stream::new(
+                dictionary! {
+                    "Type" => "XObject",
+                    "Subtype" => "Form",
+                    "BBox" => bbox,
+                    "Resources" => resources,
+                    "OC" => ocg,
+                },
+                contents
+            )
+        );
+        let key = format!("Layer{}", i);
+        content.push_str(&format!("q /{} Do Q\n", key));
+        xobjects.set(key, form);
+        ocgs.push(Object::Reference(ocg));
+    }
+
+    let resources_id = match doc.get_dictionary(page_id).and_then(|p| p.get(b"Resources")) {
+        Ok(Object::Reference(id)) => Some(*id),
+        _ => None,
+    };
+    let resources = match resources_id {
+        Some(id) => doc.get_object_mut(id).and_then(Object::as_dict_mut),
+        None => {
+            let page = doc
+                .get_object_mut(page_id)
+                .and_then(Object::as_dict_mut)
+                .map_err(|e| format!("page dictionary: {:?}", e))?;
+            if !page.has(b"Resources") {
+                page.set("Resources", Dictionary::new());
+            }
+            page.get_mut(b"Resources").and_then(Object::as_dict_mut)
+        }
+    }.map_err(|e| format!("page resources: {:?}", e))?;
+
+    match resources.get_mut(b"XObject") {
+        Ok(Object::Dictionary(existing)) => existing.extend(&xobjects),
+        Ok(Object::Reference(id)) => {
+            let id = *id;
+            doc.get_object_mut(id)
+                .and_then(Object::as_dict_mut)
+                .map_err(|e| format!("page xobjects: {:?}", e))?
+                .extend(&xobjects);
+        }
+        _ => resources.set("XObject", xobjects),
+    }
+
+    let existing = doc.get_page_contents(page_id);
+    let open = doc.add_object(Stream::new(Dictionary::new(), b"q\n".to_vec()));
+    let close = doc.add_object(Stream::new(Dictionary::new(), format!("Q\n{}", content).into_bytes()));
+    let mut contents = vec![Object::Reference(open)];
+    contents.extend(existing.into_iter().map(Object::Reference));
+    contents.push(Object::Reference(close));
+    doc.get_object_mut(page_id)
+        .and_then(Object::as_dict_mut)
+        .map_err(|e| format!("page dictionary: {:?}", e))?
+        .set("Contents", contents);
+
+    doc.catalog_mut()
+        .map_err(|e| format!("catalog: {:?}", e))?
+        .set("OCProperties", optional_content_properties(ocgs));
+
+    doc.compress();
+    let mut out = Vec::new();
+    doc.save_to(&mut out).map_err(|e| format!("lopdf save: {e:?}"))?;
+    Ok(out)
+}
+
+fn optional_content_properties(ocgs: Vec<Object>) -> Dictionary {
+    dictionary! {
+        "OCGs" => ocgs.clone(),
+        "D" => dictionary! {
+            "Name" => Object::string_literal("Sizes"),
+            "BaseState" => "ON",
+            "Order" => ocgs.clone(),
+            "ON" => ocgs,
+        },
+    }
+}
+
+fn replace_references(object: &mut Object, map: &BTreeMap<ObjectId, ObjectId>) {
+    match object {
+        Object::Reference(id) => {
+            if let Some(canonical) = map.get(id) {
+                *id = *canonical;
+            }
+        }
+        Object::Array(items) => items.iter_mut().for_each(|o| replace_references(o, map)),
+        Object::Dictionary(dict) => dict.iter_mut().for_each(|(_, o)| replace_references(o, map)),
+        Object::Stream(stream) => stream.dict.iter_mut().for_each(|(_, o)| replace_references(o, map)),
+        _ => {}
+    }
+}
+
+/// Merges optional content groups with the same name across all pages and rebuilds the catalog's `OCProperties`.
+/// Returns the properties dictionary, or `None` if the document has no layers.
+pub fn unify_optional_content(document: &mut Document) -> Option<Dictionary> {
+    let mut by_name: Vec<(Vec<u8>, ObjectId)> = Vec::new();
+    let mut duplicates = BTreeMap::new();
+    for (id, object) in document.objects.iter() {
+        let Ok(dict) = object.as_dict() else {
+            continue;
+        };
+        if dict.get(b"Type").and_then(Object::as_name).ok() != Some(b"OCG".as_slice()) {
+            continue;
+        }
+        let name = match dict.get(b"Name") {
+            Ok(Object::String(bytes, _)) => bytes.clone(),
+            _ => continue,
+        };
+        match by_name.iter().find(|(n, _)| *n == name) {
+            Some((_, canonical)) => {
+                duplicates.insert(*id, *canonical);
+            }
+            None => by_name.push((name, *id)),
+        }
+    }
+    if by_name.is_empty() {
+        return None;
+    }
+
+    for id in duplicates.keys() {
+        document.objects.remove(id);
+    }
+    for object in document.objects.values_mut() {
+        replace_references(object, &duplicates);
+    }
+    let ocgs = by_name.into_iter().map(|(_, id)| Object::Reference(id)).collect();
+    Some(optional_content_properties(ocgs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SVG: &str = concat!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 100 100">"#,
+        r#"<defs><clipPath id="c"><rect width="10" height="10"/></clipPath></defs>"#,
+        r#"<rect id="background" width="100" height="100"/>"#,
+        r#"<g transform="translate(5 5)"><path id="outline" d="M0 0"/>"#,
+        r#"<g data-size="S"><path id="small" d="M1 1"/></g>"#,
+        r#"<g data-size="M"><path id="medium" d="M2 2"/></g></g>"#,
+        r#"<text data-size="S" clip-path="url(#c)">S</text>"#,
+        "</svg>"
+    );
+
+    #[test]
+    fn layers_hold_only_their_own_elements_and_definitions() {
+        let layers = split_svg_layers(SVG).unwrap().unwrap();
+        assert!(layers.base.contains("background") && layers.base.contains("outline"));
+        assert!(!layers.base.contains("small") && !layers.base.contains("medium"));
+
+        let names: Vec<&str> = layers.layers
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        assert_eq!(names, ["S", "M"]);
+        let (_, small) = &layers.layers[0];
+        roxmltree::Document::parse(small).unwrap();
+        assert!(small.contains(r#"<clipPath id="c">"#));
+        assert!(small.contains(r#"<g transform="translate(5 5)">"#));
+        assert!(small.contains("small") && small.contains(">S</text>"));
+        assert!(!small.contains("medium") && !small.contains("background") && !small.contains("outline"));
+
+        let (_, medium) = &layers.layers[1];
+        assert!(medium.contains("medium") && medium.contains("translate(5 5)"));
+        assert!(!medium.contains("small") && !medium.contains("</text>"));
+    }
+
+    #[test]
+    fn untagged_svgs_are_not_split() {
+        assert!(split_svg_layers(r#"<svg xmlns="http://www.w3.org/2000/svg"><rect/></svg>"#).unwrap().is_none());
+    }
+}
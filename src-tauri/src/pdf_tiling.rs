@@ -0,0 +1,559 @@
+use serde::{ Deserialize, Serialize };
+use svg2pdf::usvg;
+use crate::image_provenance::xml_escape;
+
+/// Root attributes the tiler sets itself on each nested copy of the source
+const PLACEMENT_ATTRIBUTES: [&str; 7] = ["x", "y", "width", "height", "viewBox", "preserveAspectRatio", "id"];
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum PaperSize {
+    A4,
+    Letter,
+    A3,
+    Legal,
+    Custom {
+        #[serde(rename = "widthPt")]
+        width_pt: f64,
+        #[serde(rename = "heightPt")]
+        height_pt: f64,
+    },
+}
+
+impl PaperSize {
+    pub fn dimensions_pt(&self) -> (f64, f64) {
+        match self {
+            PaperSize::A4 => (595.28, 841.89),
+            PaperSize::Letter => (612.0, 792.0),
+            PaperSize::A3 => (841.89, 1190.55),
+            PaperSize::Legal => (612.0, 1008.0),
+            PaperSize::Custom { width_pt, height_pt } => (*width_pt, *height_pt),
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            PaperSize::A4 => "A4".to_string(),
+            PaperSize::Letter => "Letter".to_string(),
+            PaperSize::A3 => "A3".to_string(),
+            PaperSize::Legal => "Legal".to_string(),
+            PaperSize::Custom { width_pt, height_pt } =>
+                format!("{:.0} x {:.0} mm", width_pt / 72.0 * 25.4, height_pt / 72.0 * 25.4),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TileOptions {
+    pub paper: PaperSize,
+    #[serde(default)]
+    pub landscape: bool,
+    #[serde(rename = "overlapPt")]
+    pub overlap_pt: f64,
+    #[serde(rename = "marginPt")]
+    pub margin_pt: f64,
+    #[serde(rename = "alignmentMarks")]
+    pub alignment_marks: bool,
+    pub labels: bool,
+    #[serde(rename = "coverPage")]
+    pub cover_page: bool,
+    /// Space kept free along the bottom of every tile page, for calibration marks
+    #[serde(skip)]
+    pub reserved_bottom_pt: f64,
+}
+
+impl Default for TileOptions {
+    fn default() -> Self {
+        TileOptions {
+            paper: PaperSize::A4,
+            landscape: false,
+            overlap_pt: 18.0,
+            margin_pt: 18.0,
+            alignment_marks: true,
+            labels: true,
+            cover_page: true,
+            reserved_bottom_pt: 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TileGrid {
+    pub page_width: f64,
+    pub page_height: f64,
+    pub printable_width: f64,
+    pub printable_height: f64,
+    pub step_x: f64,
+    pub step_y: f64,
+    pub cols: usize,
+    pub rows: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Tile {
+    pub row: usize,
+    pub col: usize,
+    pub has_content: bool,
+}
+
+/// Page SVGs in print order, with the tile shown on each page (`None` for the cover page).
+pub struct TiledPattern {
+    pub pages: Vec<(Option<Tile>, String)>,
+    pub grid: TileGrid,
+}
+
+struct SourceSvg<'a> {
+    view_box: (f64, f64, f64, f64),
+    namespaces: String,
+    /// Presentation attributes of the source root (`fill`, `style`, `font-family`...), inherited by its content
+    attributes: String,
+    content: &'a str,
+    size: (f64, f64),
+    bboxes: Vec<usvg::Rect>,
+}
+
+impl TileOptions {
+    pub fn page_size(&self) -> (f64, f64) {
+        let (w, h) = self.paper.dimensions_pt();
+        if self.landscape {
+            (h.max(w), h.min(w))
+        } else {
+            (w, h)
+        }
+    }
+}
+
+impl TileGrid {
+    pub fn new(canvas_width_pt: f64, canvas_height_pt: f64, options: &TileOptions) -> Result<Self, String> {
+        let (page_width, page_height) = options.page_size();
+        let printable_width = page_width - 2.0 * options.margin_pt;
+        let printable_height = page_height - 2.0 * options.margin_pt - options.reserved_bottom_pt;
+        let step_x = printable_width - options.overlap_pt;
+        let step_y = printable_height - options.overlap_pt;
+        if step_x <= 0.0 || step_y <= 0.0 {
+            return Err("Margins and overlap leave no printable area on the page".to_string());
+        }
+        let count = |canvas: f64, printable: f64, step: f64| -> usize {
+            if canvas <= printable {
+                1
+            } else {
+                ((canvas - printable) / step).ceil() as usize + 1
+            }
+        };
+        Ok(TileGrid {
+            page_width,
+            page_height,
+            printable_width,
+            printable_height,
+            step_x,
+            step_y,
+            cols: count(canvas_width_pt, printable_width, step_x),
+            rows: count(canvas_height_pt, printable_height, step_y),
+        })
+    }
+
+    fn origin(&self, row: usize, col: usize) -> (f64, f64) {
+        (col as f64 * self.step_x, row as f64 * self.step_y)
+    }
+}
+
+pub fn row_label(row: usize) -> String {
+    let mut n = row + 1;
+    let mut label = String::new();
+    while n > 0 {
+        let rem = (n - 1) % 26;
+        label.insert(0, (b'A' + rem as u8) as char);
+        n = (n - 1) / 26;
+    }
+    label
+}
+
+pub fn tile_label(row: usize, col: usize) -> String {
+    format!("{}{}", row_label(row), col + 1)
+}
+
+fn parse_source(svg_string: &str) -> Result<SourceSvg<'_>, String> {
+    let doc = roxmltree::Document
+        ::parse(svg_string)
+        .map_err(|e| format!("SVG parse failed: {}", e))?;
+    let root = doc.root_element();
+    let mut namespaces = String::new();
+    for ns in root.namespaces() {
+        if let Some(prefix) = ns.name() {
+            if prefix != "xml" && prefix != "xlink" {
+                namespaces.push_str(&format!(" xmlns:{}=\"{}\"", prefix, ns.uri()));
+            }
+        }
+    }
+    let mut attributes = String::new();
+    for attribute in root.attributes() {
+        let name = attribute.name();
+        if attribute.namespace().is_none() && !name.starts_with("data-") && !PLACEMENT_ATTRIBUTES.contains(&name) {
+            attributes.push_str(&format!(" {}=\"{}\"", name, xml_escape(attribute.value())));
+        }
+    }
+    let content = match (root.first_child(), root.last_child()) {
+        (Some(first), Some(last)) => &svg_string[first.range().start..last.range().end],
+        _ => "",
+    };
+
+    let tree = usvg::Tree
+        ::from_str(svg_string, &usvg::Options::default())
+        .map_err(|e| format!("SVG parse failed: {:?}", e))?;
+    let size = (tree.size().width() as f64, tree.size().height() as f64);
+    let mut bboxes = Vec::new();
+    collect_leaf_bboxes(tree.root(), &mut bboxes);
+
+    let view_box = root
+        .attribute("viewBox")
+        .map(|vb| {
+            vb.split(|c: char| c.is_whitespace() || c == ',')
+                .filter(|s| !s.is_empty())
+                .filter_map(|s| s.parse::<f64>().ok())
+                .collect::<Vec<f64>>()
+        })
+        .filter(|vb| vb.len() == 4 && vb[2] > 0.0 && vb[3] > 0.0)
+        .map(|vb| (vb[0], vb[1], vb[2], vb[3]))
+        .unwrap_or((0.0, 0.0, size.0, size.1));
+
+    Ok(SourceSvg { view_box, namespaces, attributes, content, size, bboxes })
+}
+
+fn collect_leaf_bboxes(group: &usvg::Group, out: &mut Vec<usvg::Rect>) {
+    for node in group.children() {
+        match node {
+            usvg::Node::Group(ref g) => collect_leaf_bboxes(g, out),
+            other => out.push(other.abs_bounding_box()),
+        }
+    }
+}
+
+fn nested_source(source: &SourceSvg, x: f64, y: f64, width: f64, height: f64, view_box: (f64, f64, f64, f64)) -> String {
+    format!(
+        r#"<svg x="{}" y="{}" width="{}" height="{}" viewBox="{} {} {} {}" preserveAspectRatio="none" overflow="hidden"{}>{}</svg>"#,
+        x, y, width, height, view_box.0, view_box.1, view_box.2, view_box.3, source.attributes, source.content
+    )
+}
+
+fn page_open(grid: &TileGrid, source: &SourceSvg) -> String {
+    format!(
+        r#"<svg width="{w}pt" height="{h}pt" viewBox="0 0 {w} {h}" xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink"{ns}>"#,
+        w = grid.page_width,
+        h = grid.page_height,
+        ns = source.namespaces
+    )
+}
+
+fn registration_mark(x: f64, y: f64) -> String {
+    let r = 6.0;
+    format!(
+        r#"<g stroke="black" stroke-width="0.5" fill="none"><circle cx="{x}" cy="{y}" r="{r}"/><line x1="{}" y1="{y}" x2="{}" y2="{y}"/><line x1="{x}" y1="{}" x2="{x}" y2="{}"/></g>"#,
+        x - r * 1.6,
+        x + r * 1.6,
+        y - r * 1.6,
+        y + r * 1.6
+    )
+}
+
+fn text(x: f64, y: f64, size: f64, anchor: &str, fill: &str, content: &str) -> String {
+    format!(
+        r#"<text x="{x}" y="{y}" font-family="Arial, Helvetica, sans-serif" font-size="{size}" text-anchor="{anchor}" fill="{fill}">{content}</text>"#
+    )
+}
+
+fn tile_page(
+    source: &SourceSvg,
+    grid: &TileGrid,
+    tile: &Tile,
+    page_number: usize,
+    page_count: usize,
+    units_per_pt: (f64, f64),
+    options: &TileOptions
+) -> String {
+    let m = options.margin_pt;
+    let (pw, ph) = (grid.printable_width, grid.printable_height);
+    let (cx, cy) = grid.origin(tile.row, tile.col);
+    let view_box = (
+        source.view_box.0 + cx * units_per_pt.0,
+        source.view_box.1 + cy * units_per_pt.1,
+        pw * units_per_pt.0,
+        ph * units_per_pt.1,
+    );
+
+    let mut page = page_open(grid, source);
+    page.push_str(&nested_source(source, m, m, pw, ph, view_box));
+
+    let overlap = options.overlap_pt;
+    if overlap > 0.0 {
+        let guide = r##"stroke="#888888" stroke-width="0.5" stroke-dasharray="4 3" fill="none""##;
+        if tile.col > 0 {
+            page.push_str(&format!(r#"<line x1="{x}" y1="{}" x2="{x}" y2="{}" {guide}/>"#, m, m + ph, x = m + overlap));
+        }
+        if tile.col + 1 < grid.cols {
+            page.push_str(&format!(r#"<line x1="{x}" y1="{}" x2="{x}" y2="{}" {guide}/>"#, m, m + ph, x = m + pw - overlap));
+        }
+        if tile.row > 0 {
+            page.push_str(&format!(r#"<line x1="{}" y1="{y}" x2="{}" y2="{y}" {guide}/>"#, m, m + pw, y = m + overlap));
+        }
+        if tile.row + 1 < grid.rows {
+            page.push_str(&format!(r#"<line x1="{}" y1="{y}" x2="{}" y2="{y}" {guide}/>"#, m, m + pw, y = m + ph - overlap));
+        }
+    }
+
+    if options.alignment_marks {
+        let half = overlap / 2.0;
+        for fraction in [0.25, 0.75] {
+            if tile.col > 0 {
+                page.push_str(&registration_mark(m + half, m + ph * fraction));
+            }
+            if tile.col + 1 < grid.cols {
+                page.push_str(&registration_mark(m + pw - half, m + ph * fraction));
+            }
+            if tile.row > 0 {
+                page.push_str(&registration_mark(m + pw * fraction, m + half));
+            }
+            if tile.row + 1 < grid.rows {
+                page.push_str(&registration_mark(m + pw * fraction, m + ph - half));
+            }
+        }
+    }
+
+    if options.labels {
+        let label = tile_label(tile.row, tile.col);
+        page.push_str(
+            &format!(
+                r##"<text x="{}" y="{}" font-family="Arial, Helvetica, sans-serif" font-size="72" font-weight="bold" text-anchor="middle" fill="#000000" fill-opacity="0.12">{}</text>"##,
+                m + pw / 2.0,
+                m + ph / 2.0 + 24.0,
+                label
+            )
+        );
+        let size = (m * 0.5).clamp(6.0, 10.0);
+        let inset = if m >= size * 1.5 { m / 2.0 + size / 3.0 } else { m + size };
+        page.push_str(
+            &text(
+                m,
+                inset,
+                size,
+                "start",
+                "#000000",
+                &format!("{} · page {} of {}", label, page_number, page_count)
+            )
+        );
+        if tile.row > 0 {
+            page.push_str(&text(m + pw / 2.0, inset, size, "middle", "#555555", &format!("▲ {}", tile_label(tile.row - 1, tile.col))));
+        }
+        if tile.row + 1 < grid.rows {
+            let y = grid.page_height - inset + size * 0.7;
+            page.push_str(&text(m + pw / 2.0, y, size, "middle", "#555555", &format!("▼ {}", tile_label(tile.row + 1, tile.col))));
+        }
+        if tile.col > 0 {
+            page.push_str(
+                &format!(
+                    r#"<g transform="translate({} {}) rotate(-90)">{}</g>"#,
+                    inset,
+                    m + ph / 2.0,
+                    text(0.0, 0.0, size, "middle", "#555555", &format!("◀ {}", tile_label(tile.row, tile.col - 1)))
+                )
+            );
+        }
+        if tile.col + 1 < grid.cols {
+            page.push_str(
+                &format!(
+                    r#"<g transform="translate({} {}) rotate(90)">{}</g>"#,
+                    grid.page_width - inset,
+                    m + ph / 2.0,
+                    text(0.0, 0.0, size, "middle", "#555555", &format!("▶ {}", tile_label(tile.row, tile.col + 1)))
+                )
+            );
+        }
+    }
+
+    page.push_str("</svg>");
+    page
+}
+
+fn cover_page(
+    source: &SourceSvg,
+    grid: &TileGrid,
+    tiles: &[Tile],
+    canvas: (f64, f64),
+    options: &TileOptions
+) -> String {
+    let m = options.margin_pt.max(24.0);
+    let header = 64.0;
+    let total_w = grid.step_x * ((grid.cols - 1) as f64) + grid.printable_width;
+    let total_h = grid.step_y * ((grid.rows - 1) as f64) + grid.printable_height;
+    let avail_w = grid.page_width - 2.0 * m;
+    let avail_h = grid.page_height - 2.0 * m - header;
+    let scale = (avail_w / total_w).min(avail_h / total_h);
+    let ox = m + (avail_w - total_w * scale) / 2.0;
+    let oy = m + header;
+
+    let mut page = page_open(grid, source);
+    page.push_str(&text(m, m + 18.0, 18.0, "start", "#000000", "Assembly map"));
+    let printed = tiles.iter().filter(|t| t.has_content).count();
+    page.push_str(
+        &text(
+            m,
+            m + 38.0,
+            10.0,
+            "start",
+            "#555555",
+            &format!(
+                "{} rows × {} columns · {} pages to print · {} · {:.0} pt overlap",
+                grid.rows,
+                grid.cols,
+                printed,
+                options.paper.name(),
+                options.overlap_pt
+            )
+        )
+    );
+
+    page.push_str(
+        &nested_source(source, ox, oy, canvas.0 * scale, canvas.1 * scale, source.view_box)
+    );
+
+    let mut page_number = 2;
+    for tile in tiles {
+        let (cx, cy) = grid.origin(tile.row, tile.col);
+        let (x, y) = (ox + cx * scale, oy + cy * scale);
+        let (w, h) = (grid.printable_width * scale, grid.printable_height * scale);
+        let fill = if tile.has_content { "none" } else { "#000000\" fill-opacity=\"0.06" };
+        page.push_str(
+            &format!(
+                r##"<rect x="{x}" y="{y}" width="{w}" height="{h}" fill="{fill}" stroke="#d0021b" stroke-width="0.75"/>"##
+            )
+        );
+        let size = (w.min(h) / 5.0).clamp(5.0, 14.0);
+        let label = if tile.has_content {
+            let label = format!("{} · p{}", tile_label(tile.row, tile.col), page_number);
+            page_number += 1;
+            label
+        } else {
+            format!("{} · —", tile_label(tile.row, tile.col))
+        };
+        page.push_str(&text(x + w / 2.0, y + h / 2.0 + size / 3.0, size, "middle", "#d0021b", &label));
+    }
+
+    page.push_str("</svg>");
+    page
+}
+
+/// Splits a large SVG into page-sized SVG documents, optionally preceded by a cover page with an assembly map.
+pub fn tile_svg(
+    svg_string: &str,
+    canvas_width_pt: f64,
+    canvas_height_pt: f64,
+    options: &TileOptions
+) -> Result<TiledPattern, String> {
+    let source = parse_source(svg_string)?;
+    let grid = TileGrid::new(canvas_width_pt, canvas_height_pt, options)?;
+    let px_to_pt = (canvas_width_pt / source.size.0, canvas_height_pt / source.size.1);
+    let units_per_pt = (source.view_box.2 / canvas_width_pt, source.view_box.3 / canvas_height_pt);
+
+    let mut tiles = Vec::with_capacity(grid.rows * grid.cols);
+    for row in 0..grid.rows {
+        for col in 0..grid.cols {
+            let (cx, cy) = grid.origin(row, col);
+            let has_content = source.bboxes.iter().any(|b| {
+                let (left, right) = ((b.left() as f64) * px_to_pt.0, (b.right() as f64) * px_to_pt.0);
+                let (top, bottom) = ((b.top() as f64) * px_to_pt.1, (b.bottom() as f64) * px_to_pt.1);
+                left < cx + grid.printable_width && right > cx && top < cy + grid.printable_height && bottom > cy
+            });
+            tiles.push(Tile { row, col, has_content });
+        }
+    }
+
+    let printed = tiles.iter().filter(|t| t.has_content).count();
+    if printed == 0 {
+        return Err("Nothing to print: the pattern has no visible content".to_string());
+    }
+    let offset = if options.cover_page { 1 } else { 0 };
+    let page_count = printed + offset;
+    let mut pages = Vec::with_capacity(page_count);
+    if options.cover_page {
+        pages.push((None, cover_page(&source, &grid, &tiles, (canvas_width_pt, canvas_height_pt), options)));
+    }
+    for tile in tiles.iter().filter(|t| t.has_content) {
+        let page_number = pages.len() + 1;
+        let svg = tile_page(&source, &grid, tile, page_number, page_count, units_per_pt, options);
+        pages.push((Some(tile.clone()), svg));
+    }
+    Ok(TiledPattern { pages, grid })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 3 × 2 grid of A4 tiles: 1500 × 1500 pt drawn in a 1000-unit viewBox.
+    fn pattern(content: &str) -> String {
+        format!(
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" id="pattern" width="1500pt" height="1500pt" viewBox="0 0 1000 1000" "#,
+                r##"fill="none" stroke="#112233" stroke-width="2" style="font-family: 'A &amp; B'" data-canvas-width="1500">"##,
+                "{}</svg>"
+            ),
+            content
+        )
+    }
+
+    fn no_cover() -> TileOptions {
+        TileOptions { cover_page: false, ..TileOptions::default() }
+    }
+
+    #[test]
+    fn tiles_keep_the_root_presentation_attributes() {
+        let svg = pattern(r#"<rect x="0" y="0" width="1000" height="1000"/>"#);
+        let tiled = tile_svg(&svg, 1500.0, 1500.0, &TileOptions::default()).unwrap();
+        assert_eq!((tiled.grid.cols, tiled.grid.rows), (3, 2));
+        assert_eq!(tiled.pages.len(), 7);
+
+        for (_, page) in &tiled.pages {
+            roxmltree::Document::parse(page).unwrap();
+            let nested = &page[page.find(r#"<svg x=""#).unwrap()..];
+            let nested = &nested[..nested.find('>').unwrap()];
+            assert!(nested.contains(r#" fill="none""#));
+            assert!(nested.contains(r##" stroke="#112233""##));
+            assert!(nested.contains(r#" stroke-width="2""#));
+            assert!(nested.contains(r#" style="font-family: 'A &amp; B'""#));
+            assert!(!nested.contains("data-canvas-width") && !nested.contains("pattern"));
+            assert!(nested.contains(r#"preserveAspectRatio="none""#));
+        }
+    }
+
+    #[test]
+    fn only_tiles_with_content_are_printed() {
+        let svg = pattern(r#"<rect x="10" y="10" width="50" height="50"/>"#);
+        let tiled = tile_svg(&svg, 1500.0, 1500.0, &no_cover()).unwrap();
+        let printed: Vec<String> = tiled.pages
+            .iter()
+            .map(|(tile, _)| {
+                let tile = tile.as_ref().unwrap();
+                tile_label(tile.row, tile.col)
+            })
+            .collect();
+        assert_eq!(printed, ["A1"]);
+    }
+
+    #[test]
+    fn an_empty_pattern_is_an_error() {
+        let error = tile_svg(&pattern(""), 1500.0, 1500.0, &TileOptions::default()).err().unwrap();
+        assert!(error.contains("Nothing to print"));
+    }
+
+    #[test]
+    fn grid_follows_paper_orientation_and_overlap() {
+        let options = TileOptions { paper: PaperSize::Letter, landscape: true, overlap_pt: 36.0, ..no_cover() };
+        let grid = TileGrid::new(2000.0, 1000.0, &options).unwrap();
+        assert_eq!((grid.page_width, grid.page_height), (792.0, 612.0));
+        assert_eq!((grid.step_x, grid.step_y), (720.0, 540.0));
+        assert_eq!((grid.cols, grid.rows), (3, 2));
+
+        let cramped = TileOptions { overlap_pt: 600.0, ..options };
+        assert!(TileGrid::new(2000.0, 1000.0, &cramped).is_err());
+        assert_eq!(row_label(26), "AA");
+    }
+}
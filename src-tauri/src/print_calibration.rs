@@ -0,0 +1,504 @@
+use crate::fs_sandbox;
+use lopdf::content::Content;
+use lopdf::{ dictionary, Dictionary, Document, Object, ObjectId, Stream };
+use serde::{ Deserialize, Serialize };
+
+const PT_PER_MM: f64 = 72.0 / 25.4;
+const CALIBRATION_TAG: &[u8] = b"Calibration";
+const FONT_NAME: &str = "CalHelv";
+const INSET_PT: f64 = 24.0;
+const SQUARES_MM: [(f64, &str); 2] = [(50.0, "5 cm"), (50.8, "2 in")];
+const GAP_PT: f64 = 12.0;
+
+/// Height of the strip along the bottom of a page that the calibration marks are drawn in.
+pub const CALIBRATION_BAND_PT: f64 = INSET_PT + 50.8 * PT_PER_MM + 20.0;
+
+/// Where the marks go on each page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkPlacement {
+    /// In the bottom `CALIBRATION_BAND_PT` of the page, which the page layout keeps free
+    ReservedBand,
+    /// In a band added below the page, which grows by `CALIBRATION_BAND_PT`
+    ExtendPage,
+}
+
+type Measurements = (Vec<(f64, f64)>, Vec<f64>);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MeasuredSquare {
+    pub label: String,
+    #[serde(rename = "expectedMm")]
+    pub expected_mm: f64,
+    #[serde(rename = "widthMm")]
+    pub width_mm: f64,
+    #[serde(rename = "heightMm")]
+    pub height_mm: f64,
+    pub ok: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PageScaleReport {
+    pub page: u32,
+    /// Left unmarked on purpose, like a cover page
+    pub skipped: bool,
+    #[serde(rename = "widthMm")]
+    pub width_mm: f64,
+    #[serde(rename = "heightMm")]
+    pub height_mm: f64,
+    #[serde(rename = "mediaBoxOk")]
+    pub media_box_ok: bool,
+    pub squares: Vec<MeasuredSquare>,
+    #[serde(rename = "rulerTicks")]
+    pub ruler_ticks: usize,
+    #[serde(rename = "rulerSpacingMm")]
+    pub ruler_spacing_mm: Option<f64>,
+    #[serde(rename = "rulerOk")]
+    pub ruler_ok: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScaleReport {
+    pub pages: Vec<PageScaleReport>,
+    #[serde(rename = "toleranceMm")]
+    pub tolerance_mm: f64,
+    pub ok: bool,
+}
+
+fn media_box(doc: &Document, page_id: ObjectId) -> Result<[f64; 4], String> {
+    let mut node = doc.get_dictionary(page_id).map_err(|e| format!("page {:?}: {:?}", page_id, e))?;
+    loop {
+        if let Ok(mb) = node.get(b"MediaBox") {
+            let (_, mb) = doc.dereference(mb).map_err(|e| format!("MediaBox: {:?}", e))?;
+            let values = mb
+                .as_array()
+                .map_err(|_| "MediaBox is not an array".to_string())?
+                .iter()
+                .map(|v| v.as_float().map(|v| v as f64))
+                .collect::<Result<Vec<f64>, _>>()
+                .map_err(|_| "MediaBox contains non-numeric values".to_string())?;
+            if values.len() != 4 {
+                return Err("MediaBox must have four values".to_string());
+            }
+            return Ok([values[0], values[1], values[2], values[3]]);
+        }
+        node = node
+            .get(b"Parent")
+            .and_then(Object::as_reference)
+            .and_then(|id| doc.get_dictionary(id))
+            .map_err(|_| "page has no MediaBox".to_string())?;
+    }
+}
+
+fn add_font_resource(doc: &mut Document, page_id: ObjectId, font_id: ObjectId) -> Result<(), String> {
+    let resources_id = match doc.get_dictionary(page_id).and_then(|p| p.get(b"Resources")) {
+        Ok(Object::Reference(id)) => Some(*id),
+        _ => None,
+    };
+    let resources = match resources_id {
+        Some(id) => doc.get_object_mut(id).and_then(Object::as_dict_mut),
+        None => {
+            let page = doc
+                .get_object_mut(page_id)
+                .and_then(Object::as_dict_mut)
+                .map_err(|e| format!("page dictionary: {:?}", e))?;
+            if !page.has(b"Resources") {
+                page.set("Resources", Dictionary::new());
+            }
+            page.get_mut(b"Resources").and_then(Object::as_dict_mut)
+        }
+    }.map_err(|e| format!("page resources: {:?}", e))?;
+
+    let fonts_id = match resources.get(b"Font") {
+        Ok(Object::Reference(id)) => Some(*id),
+        _ => None,
+    };
+    let fonts = match fonts_id {
+        Some(id) => doc.get_object_mut(id).and_then(Object::as_dict_mut),
+        None => {
+            if !resources.has(b"Font") {
+                resources.set("Font", Dictionary::new());
+            }
+            resources.get_mut(b"Font").and_then(Object::as_dict_mut)
+        }
+    }.map_err(|e| format!("page fonts: {:?}", e))?;
+    fonts.set(FONT_NAME, font_id);
+    Ok(())
+}
+
+fn text(x: f64, y: f64, size: f64, s: &str) -> String {
+    format!("BT /{} {} Tf {:.3} {:.3} Td ({}) Tj ET\n", FONT_NAME, size, x, y, s)
+}
+
+/// Reference squares at the bottom left with the ruler beside them, all inside the bottom `CALIBRATION_BAND_PT`.
+fn calibration_content(mb: [f64; 4]) -> String {
+    let (x0, y0, width) = (mb[0], mb[1], mb[2] - mb[0]);
+    let mut out = format!("/{} BMC\nq\n0 0 0 RG 0 0 0 rg 0.5 w\n", String::from_utf8_lossy(CALIBRATION_TAG));
+    let squares_width: f64 = SQUARES_MM.iter().map(|(mm, _)| mm * PT_PER_MM).sum::<f64>() + GAP_PT;
+    let largest = SQUARES_MM.iter().map(|(mm, _)| mm * PT_PER_MM).fold(0.0, f64::max);
+    let y = y0 + INSET_PT;
+    if width >= squares_width + 2.0 * INSET_PT {
+        let mut x = x0 + INSET_PT;
+        for (mm, label) in SQUARES_MM {
+            let side = mm * PT_PER_MM;
+            out.push_str(&format!("{:.3} {:.3} {:.3} {:.3} re S\n", x, y, side, side));
+            out.push_str(&text(x + side / 2.0 - 10.0, y + side / 2.0 - 4.0, 10.0, label));
+            x += side + GAP_PT;
+        }
+        out.push_str(
+            &text(x0 + INSET_PT, y + largest + 6.0, 7.0, "Print at 100% / actual size. Both squares must measure as labelled.")
+        );
+    }
+
+    let x = x0 + INSET_PT + if width >= squares_width + 2.0 * INSET_PT { squares_width + 2.0 * GAP_PT } else { 0.0 };
+    let ruler_cm = ((x0 + width - INSET_PT - 12.0 - x) / (10.0 * PT_PER_MM)).floor().min(10.0);
+    if ruler_cm >= 1.0 {
+        let ruler_cm = ruler_cm as usize;
+        let y = y + largest;
+        let length = ruler_cm as f64 * 10.0 * PT_PER_MM;
+        out.push_str(&format!("{:.3} {:.3} m {:.3} {:.3} l S\n", x, y, x + length, y));
+        out.push_str("0.25 w\n");
+        for mm in 0..=ruler_cm * 10 {
+            let tick = match mm {
+                m if m % 10 == 0 => 8.0,
+                m if m % 5 == 0 => 5.0,
+                _ => 3.0,
+            };
+            let tx = x + mm as f64 * PT_PER_MM;
+            out.push_str(&format!("{:.3} {:.3} m {:.3} {:.3} l S\n", tx, y, tx, y - tick));
+            if mm % 10 == 0 {
+                out.push_str(&text(tx - 2.0, y - 15.0, 6.0, &(mm / 10).to_string()));
+            }
+        }
+        out.push_str(&text(x + length + 4.0, y - 8.0, 6.0, "cm"));
+    }
+
+    out.push_str("Q\nEMC\n");
+    out
+}
+
+/// Draws a 5 cm and a 2 in reference square and a centimetre ruler onto every page of a PDF except `skip_pages`
+/// (numbered from 1), such as a cover page.
+pub fn stamp_calibration_marks(pdf: &[u8], placement: MarkPlacement, skip_pages: &[u32]) -> Result<Vec<u8>, String> {
+    let mut doc = Document::load_mem(pdf).map_err(|e| format!("lopdf load: {e:?}"))?;
+    let font_id = doc.add_object(
+        dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => "Helvetica",
+            "Encoding" => "WinAnsiEncoding",
+        }
+    );
+
+    for (page, page_id) in doc.get_pages() {
+        if skip_pages.contains(&page) {
+            continue;
+        }
+        let mut mb = media_box(&doc, page_id)?;
+        if placement == MarkPlacement::ExtendPage {
+            mb[1] -= CALIBRATION_BAND_PT;
+            let page = doc
+                .get_object_mut(page_id)
+                .and_then(Object::as_dict_mut)
+                .map_err(|e| format!("page dictionary: {:?}", e))?;
+            page.set("MediaBox", mb.iter().map(|v| Object::Real(*v as f32)).collect::<Vec<Object>>());
+            page.remove(b"CropBox");
+        }
+        add_font_resource(&mut doc, page_id, font_id)?;
+
+        let existing = doc.get_page_contents(page_id);
+        let open = doc.add_object(Stream::new(Dictionary::new(), b"q\n".to_vec()));
+        let close = doc.add_object(
+            Stream::new(Dictionary::new(), format!("Q\n{}", calibration_content(mb)).into_bytes())
+        );
+        let mut contents = vec![Object::Reference(open)];
+        contents.extend(existing.into_iter().map(Object::Reference));
+        contents.push(Object::Reference(close));
+        doc.get_object_mut(page_id)
+            .and_then(Object::as_dict_mut)
+            .map_err(|e| format!("page dictionary: {:?}", e))?
+            .set("Contents", contents);
+    }
+
+    doc.compress();
+    let mut out = Vec::new();
+    doc.save_to(&mut out).map_err(|e| format!("lopdf save: {e:?}"))?;
+    Ok(out)
+}
+
+fn multiply(m: [f64; 6], ctm: [f64; 6]) -> [f64; 6] {
+    [
+        m[0] * ctm[0] + m[1] * ctm[2],
+        m[0] * ctm[1] + m[1] * ctm[3],
+        m[2] * ctm[0] + m[3] * ctm[2],
+        m[2] * ctm[1] + m[3] * ctm[3],
+        m[4] * ctm[0] + m[5] * ctm[2] + ctm[4],
+        m[4] * ctm[1] + m[5] * ctm[3] + ctm[5],
+    ]
+}
+
+fn apply(ctm: [f64; 6], x: f64, y: f64) -> (f64, f64) {
+    (ctm[0] * x + ctm[2] * y + ctm[4], ctm[1] * x + ctm[3] * y + ctm[5])
+}
+
+fn measure_page(doc: &Document, page_id: ObjectId) -> Result<Measurements, String> {
+    let data = doc.get_page_content(page_id).map_err(|e| format!("page content: {:?}", e))?;
+    let content = Content::decode(&data).map_err(|e| format!("content decode: {:?}", e))?;
+
+    let mut ctm = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+    let mut stack = Vec::new();
+    let mut marked = Vec::new();
+    let mut current = None;
+    let mut rects = Vec::new();
+    let mut ticks = Vec::new();
+
+    for op in &content.operations {
+        let nums: Vec<f64> = op.operands
+            .iter()
+            .filter_map(|o| o.as_float().ok())
+            .map(|v| v as f64)
+            .collect();
+        let inside = marked.iter().any(|m| *m);
+        match op.operator.as_str() {
+            "q" => stack.push(ctm),
+            "Q" => {
+                ctm = stack.pop().unwrap_or(ctm);
+            }
+            "cm" if nums.len() == 6 => {
+                ctm = multiply([nums[0], nums[1], nums[2], nums[3], nums[4], nums[5]], ctm);
+            }
+            "BMC" | "BDC" => {
+                let tag = op.operands.first().and_then(|o| o.as_name().ok());
+                marked.push(tag == Some(CALIBRATION_TAG));
+            }
+            "EMC" => {
+                marked.pop();
+            }
+            "re" if inside && nums.len() == 4 => {
+                let (w, h) = (nums[2], nums[3]);
+                rects.push((
+                    (ctm[0] * w).hypot(ctm[1] * w) / PT_PER_MM,
+                    (ctm[2] * h).hypot(ctm[3] * h) / PT_PER_MM,
+                ));
+            }
+            "m" if inside && nums.len() == 2 => {
+                current = Some(apply(ctm, nums[0], nums[1]));
+            }
+            "l" if inside && nums.len() == 2 => {
+                let end = apply(ctm, nums[0], nums[1]);
+                if let Some(start) = current {
+                    if (end.0 - start.0).abs() < 1e-3 && (end.1 - start.1).abs() > 1e-3 {
+                        ticks.push(start.0 / PT_PER_MM);
+                    }
+                }
+                current = Some(end);
+            }
+            _ => {}
+        }
+    }
+    ticks.sort_by(|a, b| a.total_cmp(b));
+    ticks.dedup_by(|a, b| (*a - *b).abs() < 1e-3);
+    Ok((rects, ticks))
+}
+
+/// Re-reads an exported PDF and checks that page sizes and calibration marks have their physical dimensions. Every
+/// page outside `skip_pages` must carry both squares and the ruler.
+pub fn verify_pdf_scale(
+    pdf: &[u8],
+    expected_width_mm: Option<f64>,
+    expected_height_mm: Option<f64>,
+    tolerance_mm: f64,
+    skip_pages: &[u32]
+) -> Result<ScaleReport, String> {
+    let doc = Document::load_mem(pdf).map_err(|e| format!("lopdf load: {e:?}"))?;
+    let mut pages = Vec::new();
+
+    for (page, page_id) in doc.get_pages() {
+        let mb = media_box(&doc, page_id)?;
+        let width_mm = (mb[2] - mb[0]).abs() / PT_PER_MM;
+        let height_mm = (mb[3] - mb[1]).abs() / PT_PER_MM;
+        let media_box_ok =
+            expected_width_mm.is_none_or(|w| (w - width_mm).abs() <= tolerance_mm) &&
+            expected_height_mm.is_none_or(|h| (h - height_mm).abs() <= tolerance_mm);
+
+        let skipped = skip_pages.contains(&page);
+        let (rects, ticks) = measure_page(&doc, page_id)?;
+        let squares = SQUARES_MM.iter()
+            .map(|(mm, label)| {
+                let (w, h) = rects
+                    .iter()
+                    .min_by(|a, b| (a.0 - mm).abs().total_cmp(&(b.0 - mm).abs()))
+                    .copied()
+                    .unwrap_or((0.0, 0.0));
+                MeasuredSquare {
+                    label: label.to_string(),
+                    expected_mm: *mm,
+                    width_mm: w,
+                    height_mm: h,
+                    ok: skipped || ((w - mm).abs() <= tolerance_mm && (h - mm).abs() <= tolerance_mm),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let spacings: Vec<f64> = ticks.windows(2).map(|w| w[1] - w[0]).collect();
+        let ruler_spacing_mm = match spacings.is_empty() {
+            true => None,
+            false => Some(spacings.iter().sum::<f64>() / spacings.len() as f64),
+        };
+        let ruler_ok = match ticks.len() {
+            _ if skipped => true,
+            0 | 1 => false,
+            n => {
+                let span = ticks[n - 1] - ticks[0];
+                spacings.iter().all(|s| (s - 1.0).abs() <= tolerance_mm) &&
+                    (span - (n - 1) as f64).abs() <= tolerance_mm
+            }
+        };
+
+        pages.push(PageScaleReport {
+            page,
+            skipped,
+            width_mm,
+            height_mm,
+            media_box_ok,
+            squares,
+            ruler_ticks: ticks.len(),
+            ruler_spacing_mm,
+            ruler_ok,
+        });
+    }
+
+    let ok = !pages.is_empty() &&
+        pages.iter().all(|p| p.media_box_ok && p.ruler_ok && p.squares.iter().all(|s| s.ok));
+    Ok(ScaleReport { pages, tolerance_mm, ok })
+}
+
+#[tauri::command]
+pub async fn verify_pattern_pdf_scale(
+    app: tauri::AppHandle,
+    pdf_path: String,
+    expected_width_mm: Option<f64>,
+    expected_height_mm: Option<f64>,
+    tolerance_mm: Option<f64>,
+    skip_pages: Option<Vec<u32>>
+) -> Result<ScaleReport, String> {
+    let pdf_path = fs_sandbox::frontend_path(&app, &pdf_path)?;
+    let bytes = std::fs::read(&pdf_path).map_err(|e| format!("Failed to read PDF: {}", e))?;
+    verify_pdf_scale(
+        &bytes,
+        expected_width_mm,
+        expected_height_mm,
+        tolerance_mm.unwrap_or(0.5),
+        &skip_pages.unwrap_or_default()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const A4_MM: (f64, f64) = (210.0, 297.0);
+
+    /// A plain PDF with `pages` A4 pages, each with a filled rectangle as content.
+    fn generated_pdf(pages: usize) -> Vec<u8> {
+        let mut doc = Document::with_version("1.5");
+        let pages_id = doc.new_object_id();
+        let (width, height) = (A4_MM.0 * PT_PER_MM, A4_MM.1 * PT_PER_MM);
+        let kids: Vec<Object> = (0..pages)
+            .map(|_| {
+                let content = doc.add_object(Stream::new(Dictionary::new(), b"0 0 1 rg 100 300 200 200 re f".to_vec()));
+                doc.add_object(dictionary! { "Type" => "Page", "Parent" => pages_id, "Contents" => content }).into()
+            })
+            .collect();
+        let media_box = vec![0.into(), 0.into(), Object::Real(width as f32), Object::Real(height as f32)];
+        doc.objects.insert(
+            pages_id,
+            Object::Dictionary(
+                dictionary! {
+                    "Type" => "Pages",
+                    "Count" => pages as i64,
+                    "Kids" => kids,
+                    "MediaBox" => media_box,
+                }
+            )
+        );
+        let catalog = doc.add_object(dictionary! { "Type" => "Catalog", "Pages" => pages_id });
+        doc.trailer.set("Root", catalog);
+        let mut out = Vec::new();
+        doc.save_to(&mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn stamped_marks_measure_their_physical_size() {
+        let stamped = stamp_calibration_marks(&generated_pdf(2), MarkPlacement::ReservedBand, &[]).unwrap();
+        let report = verify_pdf_scale(&stamped, Some(A4_MM.0), Some(A4_MM.1), 0.1, &[]).unwrap();
+        assert!(report.ok, "{:?}", report);
+        assert_eq!(report.pages.len(), 2);
+        for page in &report.pages {
+            assert_eq!(page.squares.len(), 2);
+            assert!((page.squares[0].width_mm - 50.0).abs() < 0.01);
+            assert!((page.squares[1].height_mm - 50.8).abs() < 0.01);
+            assert!(page.ruler_ticks > 10);
+            assert!((page.ruler_spacing_mm.unwrap() - 1.0).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn pages_without_marks_fail_verification() {
+        let report = verify_pdf_scale(&generated_pdf(1), Some(A4_MM.0), Some(A4_MM.1), 0.5, &[]).unwrap();
+        assert!(!report.ok);
+        assert_eq!(report.pages[0].ruler_ticks, 0);
+        assert!(!report.pages[0].ruler_ok);
+        assert!(report.pages[0].squares.iter().all(|s| !s.ok));
+
+        let wrong_size = verify_pdf_scale(&generated_pdf(1), Some(215.9), Some(279.4), 0.5, &[1]).unwrap();
+        assert!(!wrong_size.ok);
+    }
+
+    #[test]
+    fn the_cover_page_can_be_left_unmarked() {
+        let stamped = stamp_calibration_marks(&generated_pdf(3), MarkPlacement::ReservedBand, &[1]).unwrap();
+        let report = verify_pdf_scale(&stamped, None, None, 0.5, &[1]).unwrap();
+        assert!(report.ok, "{:?}", report);
+        assert!(report.pages[0].skipped);
+        assert_eq!(report.pages[0].ruler_ticks, 0);
+
+        assert!(!verify_pdf_scale(&stamped, None, None, 0.5, &[]).unwrap().ok);
+    }
+
+    #[test]
+    fn marks_stay_inside_the_bottom_band() {
+        let doc = Document::load_mem(&generated_pdf(1)).unwrap();
+        let (_, page_id) = doc.get_pages().into_iter().next().unwrap();
+        let mb = media_box(&doc, page_id).unwrap();
+        let content = Content::decode(calibration_content(mb).as_bytes()).unwrap();
+        for op in content.operations {
+            let nums: Vec<f64> = op.operands
+                .iter()
+                .filter_map(|o| o.as_float().ok())
+                .map(|v| v as f64)
+                .collect();
+            let top = match op.operator.as_str() {
+                "re" => nums[1] + nums[3],
+                "m" | "l" => nums[1],
+                "Td" => nums[1] + 10.0,
+                _ => continue,
+            };
+            assert!(top <= CALIBRATION_BAND_PT, "{} reaches {}", op.operator, top);
+        }
+    }
+
+    #[test]
+    fn extending_pages_keeps_the_content_and_adds_the_band_below() {
+        let stamped = stamp_calibration_marks(&generated_pdf(1), MarkPlacement::ExtendPage, &[]).unwrap();
+        let band_mm = CALIBRATION_BAND_PT / PT_PER_MM;
+        let report = verify_pdf_scale(&stamped, Some(A4_MM.0), Some(A4_MM.1 + band_mm), 0.1, &[]).unwrap();
+        assert!(report.ok, "{:?}", report);
+
+        let doc = Document::load_mem(&stamped).unwrap();
+        let (_, page_id) = doc.get_pages().into_iter().next().unwrap();
+        let mb = media_box(&doc, page_id).unwrap();
+        assert!((mb[1] + CALIBRATION_BAND_PT).abs() < 0.01);
+        assert!((mb[3] - A4_MM.1 * PT_PER_MM).abs() < 0.01);
+    }
+}
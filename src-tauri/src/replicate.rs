@@ -0,0 +1,706 @@
+use crate::credentials;
+use crate::fs_sandbox;
+use crate::generation::{ image_mime_type, GeneratedImage, GenerationProvider, GenerationRequest, JobState, JobStatus };
+use base64::{ engine::general_purpose, Engine as _ };
+use futures_util::future::BoxFuture;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{ Deserialize, Serialize };
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{ Path, PathBuf };
+use std::sync::{ Arc, Mutex };
+use std::time::{ Duration, Instant };
+use tauri::{ Emitter, Manager };
+use tokio::sync::Notify;
+
+pub const REPLICATE_PREDICTION_EVENT: &str = "replicate-prediction";
+pub const DEFAULT_API_BASE: &str = "https://api.replicate.com/v1";
+
+/// Files up to this size are inlined as data URIs; larger ones go through the files API.
+const DATA_URI_LIMIT: usize = 256 * 1024;
+const INITIAL_POLL: Duration = Duration::from_millis(500);
+const MAX_POLL: Duration = Duration::from_secs(8);
+const DEFAULT_TIMEOUT_SECS: u64 = 600;
+
+/// Cancellation handles of running predictions, by prediction id
+type PredictionRegistry = Arc<Mutex<HashMap<String, Arc<Notify>>>>;
+
+static REPLICATE_PREDICTIONS: Lazy<PredictionRegistry> = Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
+
+static PROGRESS_PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r"(\d{1,3})%\|").unwrap());
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PredictionRequest {
+    /// `owner/name` of an official model; ignored when `version` is set
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub version: Option<String>,
+    pub input: Value,
+    /// Name of the stored credential holding the API token
+    pub credential: String,
+    #[serde(rename = "baseUrl", default)]
+    pub base_url: Option<String>,
+    /// Seconds to hold the create request open with `Prefer: wait`, up to 60
+    #[serde(default)]
+    pub wait: Option<u64>,
+    #[serde(rename = "downloadOutputs", default)]
+    pub download_outputs: Option<bool>,
+    #[serde(rename = "timeoutSecs", default)]
+    pub timeout_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PredictionResult {
+    pub id: String,
+    pub status: String,
+    pub output: Value,
+    /// Downloaded outputs, relative to the app data directory
+    pub files: Vec<String>,
+    pub logs: String,
+    pub metrics: Value,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReplicateEvent {
+    Created {
+        id: String,
+        status: String,
+    },
+    Progress {
+        id: String,
+        status: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        percentage: Option<u32>,
+        logs: String,
+    },
+    Downloaded {
+        id: String,
+        path: String,
+    },
+    Completed {
+        id: String,
+        status: String,
+    },
+}
+
+fn api_base(base_url: Option<&str>) -> String {
+    base_url.unwrap_or(DEFAULT_API_BASE).trim_end_matches('/').to_string()
+}
+
+fn is_terminal(status: &str) -> bool {
+    matches!(status, "succeeded" | "failed" | "canceled")
+}
+
+async fn checked_json(response: reqwest::Response) -> Result<Value, String> {
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("HTTP {}: {}", status, error_text));
+    }
+    response.json().await.map_err(|e| format!("Failed to parse response: {}", e))
+}
+
+fn mime_type(bytes: &[u8], name: &str) -> &'static str {
+    match crate::image_dpi::detect_format(bytes) {
+        Some(crate::image_dpi::ImageFormat::Png) => "image/png",
+        Some(crate::image_dpi::ImageFormat::Jpeg) => "image/jpeg",
+        Some(crate::image_dpi::ImageFormat::Tiff) => "image/tiff",
+        None =>
+            match Path::new(name).extension().and_then(|e| e.to_str()).map(str::to_lowercase).as_deref() {
+                Some("webp") => "image/webp",
+                Some("gif") => "image/gif",
+                Some("mp3") => "audio/mpeg",
+                Some("wav") => "audio/wav",
+                Some("mp4") => "video/mp4",
+                _ => "application/octet-stream",
+            },
+    }
+}
+
+fn decode_data_uri(value: &str) -> Option<(String, Vec<u8>)> {
+    let (header, data) = value.strip_prefix("data:")?.split_once(',')?;
+    let mime = header.trim_end_matches(";base64").to_string();
+    let bytes = general_purpose::STANDARD.decode(data).ok()?;
+    Some((mime, bytes))
+}
+
+async fn upload_file(
+    client: &reqwest::Client,
+    base: &str,
+    api_key: &str,
+    name: &str,
+    mime: &str,
+    bytes: Vec<u8>
+) -> Result<String, String> {
+    let part = reqwest::multipart::Part
+        ::bytes(bytes)
+        .file_name(name.to_string())
+        .mime_str(mime)
+        .map_err(|e| format!("Failed to set mime type: {}", e))?;
+    let response = client
+        .post(format!("{}/files", base))
+        .bearer_auth(api_key)
+        .multipart(reqwest::multipart::Form::new().part("content", part))
+        .send().await
+        .map_err(|e| format!("Failed to upload {}: {}", name, e))?;
+    let file = checked_json(response).await?;
+    file["urls"]["get"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| format!("No URL in upload response for {}", name))
+}
+
+/// `{ "file": "<path>" }`, the only form in which an input names a file to send
+fn file_reference(value: &Value) -> Option<&str> {
+    let wrapper = value.as_object()?;
+    match wrapper.len() {
+        1 => wrapper.get("file")?.as_str(),
+        _ => None,
+    }
+}
+
+/// Replaces file references and oversized data URIs in the top level of the input with data URIs or uploaded
+/// file URLs. Only values wrapped as `{ "file": "<path>" }` are read from disk, and the path must lie under
+/// `app_data_dir`; plain strings are never treated as paths, whatever they name.
+async fn prepare_input(
+    client: &reqwest::Client,
+    base: &str,
+    api_key: &str,
+    app_data_dir: &Path,
+    input: &Value
+) -> Result<Value, String> {
+    let Some(fields) = input.as_object() else {
+        return Ok(input.clone());
+    };
+    let mut prepared = fields.clone();
+    for (key, value) in fields {
+        let (name, mime, bytes) = if let Some(text) = file_reference(value) {
+            let path = fs_sandbox
+                ::resolve_within(app_data_dir, text)
+                .ok()
+                .filter(|p| p.is_file())
+                .ok_or_else(|| format!("Input {} is not a file under app data: {}", key, text))?;
+            let bytes = std::fs::read(&path).map_err(|e| format!("Failed to read {}: {}", text, e))?;
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| key.clone());
+            (name.clone(), mime_type(&bytes, &name).to_string(), bytes)
+        } else {
+            let Some((mime, bytes)) = value.as_str().and_then(decode_data_uri) else {
+                continue;
+            };
+            if bytes.len() <= DATA_URI_LIMIT {
+                continue;
+            }
+            (key.clone(), mime, bytes)
+        };
+
+        let replacement = if bytes.len() <= DATA_URI_LIMIT {
+            format!("data:{};base64,{}", mime, general_purpose::STANDARD.encode(&bytes))
+        } else {
+            upload_file(client, base, api_key, &name, &mime, bytes).await?
+        };
+        prepared.insert(key.clone(), Value::String(replacement));
+    }
+    Ok(Value::Object(prepared))
+}
+
+fn output_urls(output: &Value, urls: &mut Vec<String>) {
+    match output {
+        Value::String(s) if s.starts_with("http://") || s.starts_with("https://") => urls.push(s.clone()),
+        Value::Array(items) => items.iter().for_each(|item| output_urls(item, urls)),
+        Value::Object(fields) => fields.values().for_each(|value| output_urls(value, urls)),
+        _ => {}
+    }
+}
+
+async fn download(client: &reqwest::Client, url: &str) -> Result<Vec<u8>, String> {
+    let bytes = client
+        .get(url)
+        .send().await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| format!("Failed to download {}: {}", url, e))?
+        .bytes().await
+        .map_err(|e| format!("Failed to read {}: {}", url, e))?;
+    Ok(bytes.to_vec())
+}
+
+async fn download_outputs(
+    app: &tauri::AppHandle,
+    client: &reqwest::Client,
+    id: &str,
+    output: &Value
+) -> Result<Vec<String>, String> {
+    let mut urls = Vec::new();
+    output_urls(output, &mut urls);
+    if urls.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    let relative_dir = Path::new("replicate").join(id);
+    std::fs
+        ::create_dir_all(app_data_dir.join(&relative_dir))
+        .map_err(|e| format!("Failed to create directory: {}", e))?;
+
+    let mut files = Vec::new();
+    for (index, url) in urls.iter().enumerate() {
+        let name = url
+            .split('?')
+            .next()
+            .and_then(|u| u.rsplit('/').next())
+            .filter(|n| !n.is_empty())
+            .unwrap_or("output");
+        let relative = relative_dir.join(format!("{}_{}", index, name));
+        let bytes = download(client, url).await?;
+        std::fs
+            ::write(app_data_dir.join(&relative), &bytes)
+            .map_err(|e| format!("Failed to save {}: {}", relative.display(), e))?;
+        let path = relative.to_string_lossy().replace('\\', "/");
+        let _ = app.emit(REPLICATE_PREDICTION_EVENT, &ReplicateEvent::Downloaded { id: id.to_string(), path: path.clone() });
+        files.push(path);
+    }
+    Ok(files)
+}
+
+/// The last tqdm-style percentage printed in a prediction's logs.
+fn log_percentage(logs: &str) -> Option<u32> {
+    PROGRESS_PATTERN.captures_iter(logs)
+        .last()
+        .and_then(|c| c[1].parse::<u32>().ok())
+        .filter(|p| *p <= 100)
+}
+
+fn emit_progress(app: &tauri::AppHandle, prediction: &Value, last_logs: &mut String) {
+    let logs = prediction["logs"].as_str().unwrap_or("");
+    if logs == last_logs.as_str() {
+        return;
+    }
+    let new_logs = logs.strip_prefix(last_logs.as_str()).unwrap_or(logs).to_string();
+    let percentage = log_percentage(logs);
+    let _ = app.emit(REPLICATE_PREDICTION_EVENT, &ReplicateEvent::Progress {
+        id: prediction["id"].as_str().unwrap_or("").to_string(),
+        status: prediction["status"].as_str().unwrap_or("").to_string(),
+        percentage,
+        logs: new_logs,
+    });
+    *last_logs = logs.to_string();
+}
+
+async fn create_prediction(
+    client: &reqwest::Client,
+    base: &str,
+    api_key: &str,
+    model: Option<&str>,
+    version: Option<&str>,
+    input: &Value,
+    wait: Option<u64>
+) -> Result<Value, String> {
+    let (url, body) = match (version, model) {
+        (Some(version), _) =>
+            (format!("{}/predictions", base), serde_json::json!({ "version": version, "input": input })),
+        (None, Some(model)) =>
+            (format!("{}/models/{}/predictions", base, model), serde_json::json!({ "input": input })),
+        (None, None) => {
+            return Err("A model or version is required".to_string());
+        }
+    };
+    let mut create = client.post(&url).bearer_auth(api_key).json(&body);
+    if let Some(wait) = wait {
+        create = create.header("Prefer", format!("wait={}", wait.clamp(1, 60)));
+    }
+    let response = create.send().await.map_err(|e| format!("Request failed: {}", e))?;
+    checked_json(response).await
+}
+
+async fn get_prediction(client: &reqwest::Client, base: &str, api_key: &str, id: &str) -> Result<Value, String> {
+    let response = client
+        .get(format!("{}/predictions/{}", base, id))
+        .bearer_auth(api_key)
+        .send().await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    checked_json(response).await
+}
+
+async fn cancel(client: &reqwest::Client, base: &str, api_key: &str, id: &str) -> Result<Value, String> {
+    let response = client
+        .post(format!("{}/predictions/{}/cancel", base, id))
+        .bearer_auth(api_key)
+        .send().await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    checked_json(response).await
+}
+
+/// Replicate as a generation provider; each job is a prediction.
+pub struct ReplicateProvider {
+    api_key: String,
+    base: String,
+    /// Root that local file inputs are resolved against
+    app_data_dir: PathBuf,
+    client: reqwest::Client,
+}
+
+impl ReplicateProvider {
+    /// Fails when `credential` is not bound to the origin of `base_url`.
+    pub fn new(credential: &str, base_url: Option<String>, app_data_dir: PathBuf) -> Result<Self, String> {
+        let base = api_base(base_url.as_deref());
+        Ok(ReplicateProvider {
+            api_key: credentials::secret_for(credential, &base)?,
+            base,
+            app_data_dir,
+            client: reqwest::Client::new(),
+        })
+    }
+}
+
+impl GenerationProvider for ReplicateProvider {
+    fn submit(&self, request: GenerationRequest) -> BoxFuture<'_, Result<String, String>> {
+        Box::pin(async move {
+            let mut input = request.input;
+            if let (Some(prompt), Some(fields)) = (request.prompt, input.as_object_mut()) {
+                fields.entry("prompt").or_insert(Value::String(prompt));
+            }
+            let input = prepare_input(&self.client, &self.base, &self.api_key, &self.app_data_dir, &input).await?;
+            let prediction = create_prediction(
+                &self.client,
+                &self.base,
+                &self.api_key,
+                request.model.as_deref(),
+                None,
+                &input,
+                None
+            ).await?;
+            prediction["id"].as_str().map(str::to_string).ok_or("No id in prediction response".to_string())
+        })
+    }
+
+    fn status<'a>(&'a self, job_id: &'a str) -> BoxFuture<'a, Result<JobStatus, String>> {
+        Box::pin(async move {
+            let prediction = get_prediction(&self.client, &self.base, &self.api_key, job_id).await?;
+            let state = match prediction["status"].as_str().unwrap_or("") {
+                "starting" => JobState::Queued,
+                "succeeded" => JobState::Succeeded,
+                "failed" => JobState::Failed,
+                "canceled" => JobState::Canceled,
+                _ => JobState::Running,
+            };
+            let logs = prediction["logs"].as_str().unwrap_or("");
+            let mut status = JobStatus::new(job_id, state);
+            status.progress = match state {
+                JobState::Succeeded => Some(1.0),
+                _ => log_percentage(logs).map(|p| (p as f32) / 100.0),
+            };
+            status.message = logs.lines().rev().find(|l| !l.trim().is_empty()).map(str::to_string);
+            status.error = prediction["error"].as_str().map(str::to_string);
+            Ok(status)
+        })
+    }
+
+    fn cancel<'a>(&'a self, job_id: &'a str) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(async move { cancel(&self.client, &self.base, &self.api_key, job_id).await.map(|_| ()) })
+    }
+
+    fn outputs<'a>(&'a self, job_id: &'a str) -> BoxFuture<'a, Result<Vec<GeneratedImage>, String>> {
+        Box::pin(async move {
+            let prediction = get_prediction(&self.client, &self.base, &self.api_key, job_id).await?;
+            let mut urls = Vec::new();
+            output_urls(&prediction["output"], &mut urls);
+            let mut images = Vec::new();
+            for (index, url) in urls.into_iter().enumerate() {
+                let data = download(&self.client, &url).await?;
+                images.push(GeneratedImage {
+                    mime_type: image_mime_type(&data).to_string(),
+                    data,
+                    metadata: serde_json::json!({
+                        "provider": "replicate",
+                        "predictionId": job_id,
+                        "index": index,
+                        "url": url,
+                        "model": prediction["model"],
+                        "version": prediction["version"],
+                        "input": prediction["input"],
+                    }),
+                });
+            }
+            Ok(images)
+        })
+    }
+}
+
+/// Polls a prediction with growing intervals until it reaches a terminal status, cancelling it on Replicate when
+/// `cancelled` is notified or `timeout` passes. `on_update` sees every state, starting with `prediction`.
+async fn follow_prediction(
+    client: &reqwest::Client,
+    base: &str,
+    api_key: &str,
+    mut prediction: Value,
+    cancelled: &Notify,
+    timeout: Duration,
+    mut on_update: impl FnMut(&Value)
+) -> Result<Value, String> {
+    let id = prediction["id"].as_str().unwrap_or("").to_string();
+    let deadline = Instant::now() + timeout;
+    let mut delay = INITIAL_POLL;
+    loop {
+        on_update(&prediction);
+        if is_terminal(prediction["status"].as_str().unwrap_or("")) {
+            return Ok(prediction);
+        }
+        if Instant::now() >= deadline {
+            let _ = cancel(client, base, api_key, &id).await;
+            return Err(format!("Prediction {} timed out", id));
+        }
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = cancelled.notified() => {
+                prediction = cancel(client, base, api_key, &id).await.unwrap_or(prediction);
+                prediction["status"] = Value::String("canceled".to_string());
+                continue;
+            }
+        }
+        delay = (delay * 3 / 2).min(MAX_POLL);
+        prediction = get_prediction(client, base, api_key, &id).await?;
+    }
+}
+
+/// Creates a prediction and follows it to a terminal status, emitting `replicate-prediction` events along the way.
+#[tauri::command]
+pub async fn replicate_run_prediction(app: tauri::AppHandle, request: PredictionRequest) -> Result<PredictionResult, String> {
+    let base = api_base(request.base_url.as_deref());
+    let api_key = credentials::secret_for(&request.credential, &base)?;
+    let client = reqwest::Client::new();
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    let input = prepare_input(&client, &base, &api_key, &app_data_dir, &request.input).await?;
+
+    let prediction = create_prediction(
+        &client,
+        &base,
+        &api_key,
+        request.model.as_deref(),
+        request.version.as_deref(),
+        &input,
+        request.wait
+    ).await?;
+    let id = prediction["id"].as_str().ok_or("No id in prediction response")?.to_string();
+    let _ = app.emit(REPLICATE_PREDICTION_EVENT, &ReplicateEvent::Created {
+        id: id.clone(),
+        status: prediction["status"].as_str().unwrap_or("starting").to_string(),
+    });
+
+    let cancelled = Arc::new(Notify::new());
+    REPLICATE_PREDICTIONS.lock().unwrap().insert(id.clone(), cancelled.clone());
+    let timeout = Duration::from_secs(request.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS));
+    let mut last_logs = String::new();
+    let outcome = follow_prediction(&client, &base, &api_key, prediction, &cancelled, timeout, |latest| {
+        emit_progress(&app, latest, &mut last_logs)
+    }).await;
+    REPLICATE_PREDICTIONS.lock().unwrap().remove(&id);
+    let prediction = outcome?;
+
+    let status = prediction["status"].as_str().unwrap_or("").to_string();
+    let files = if status == "succeeded" && request.download_outputs.unwrap_or(true) {
+        download_outputs(&app, &client, &id, &prediction["output"]).await?
+    } else {
+        Vec::new()
+    };
+    let _ = app.emit(REPLICATE_PREDICTION_EVENT, &ReplicateEvent::Completed { id: id.clone(), status: status.clone() });
+    if status == "failed" {
+        return Err(prediction["error"].as_str().unwrap_or("Replicate prediction failed").to_string());
+    }
+
+    Ok(PredictionResult {
+        id,
+        status,
+        output: prediction["output"].clone(),
+        files,
+        logs: prediction["logs"].as_str().unwrap_or("").to_string(),
+        metrics: prediction["metrics"].clone(),
+    })
+}
+
+/// Cancels a prediction on Replicate and stops its runner, if one is following it.
+#[tauri::command]
+pub async fn replicate_cancel_prediction(
+    prediction_id: String,
+    credential: String,
+    base_url: Option<String>
+) -> Result<Value, String> {
+    let runner = REPLICATE_PREDICTIONS.lock().unwrap().get(&prediction_id).cloned();
+    match runner {
+        Some(cancelled) => {
+            cancelled.notify_one();
+            Ok(serde_json::json!({ "id": prediction_id, "status": "canceled" }))
+        }
+        None => {
+            let base = api_base(base_url.as_deref());
+            let api_key = credentials::secret_for(&credential, &base)?;
+            cancel(&reqwest::Client::new(), &base, &api_key, &prediction_id).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{ StubResponse, StubServer };
+    use std::sync::atomic::{ AtomicUsize, Ordering };
+
+    fn prediction(id: &str, status: &str) -> Value {
+        serde_json::json!({ "id": id, "status": status, "logs": "", "output": null })
+    }
+
+    #[tokio::test]
+    async fn only_wrapped_file_references_are_read_and_large_ones_uploaded() {
+        let server = StubServer::start(|request| {
+            match request.path.as_str() {
+                "/files" => StubResponse::json(201, serde_json::json!({ "urls": { "get": "https://files.test/large.png" } })),
+                _ => StubResponse::json(404, serde_json::json!({})),
+            }
+        }).await;
+        let app_data = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(app_data.path().join("inputs")).unwrap();
+        std::fs::write(app_data.path().join("inputs/small.txt"), b"small").unwrap();
+        std::fs::write(app_data.path().join("inputs/large.png"), vec![7u8; DATA_URI_LIMIT + 1]).unwrap();
+        std::fs::write(app_data.path().join("credentials.json"), b"do not send").unwrap();
+        let secret = outside.path().join("secret.txt");
+        std::fs::write(&secret, b"do not send").unwrap();
+
+        let input = serde_json::json!({
+            "small": { "file": "inputs/small.txt" },
+            "large": { "file": "inputs/large.png" },
+            "named": "credentials.json",
+            "relative": "inputs/small.txt",
+            "absolute": secret.to_string_lossy(),
+            "options": { "file": "inputs/small.txt", "strength": 0.5 },
+            "prompt": "a red hoodie",
+            "steps": 20,
+        });
+        let client = reqwest::Client::new();
+        let prepared = prepare_input(&client, &server.url, "key", app_data.path(), &input).await.unwrap();
+
+        assert_eq!(prepared["small"], "data:application/octet-stream;base64,c21hbGw=");
+        assert_eq!(prepared["large"], "https://files.test/large.png");
+        for passed_through in ["named", "relative", "absolute", "options", "prompt", "steps"] {
+            assert_eq!(prepared[passed_through], input[passed_through], "{}", passed_through);
+        }
+
+        let uploads = server.requests_to("POST", "/files");
+        assert_eq!(uploads.len(), 1);
+        assert_eq!(uploads[0].header("authorization"), Some("Bearer key"));
+        assert!(uploads[0].header("content-type").unwrap().starts_with("multipart/form-data"));
+        assert!(uploads[0].body.len() > DATA_URI_LIMIT);
+        assert!(!server.requests().iter().any(|r| r.body.windows(11).any(|w| w == b"do not send")));
+    }
+
+    #[tokio::test]
+    async fn file_references_outside_app_data_are_refused() {
+        let app_data = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        let secret = outside.path().join("secret.txt");
+        std::fs::write(&secret, b"do not send").unwrap();
+        let client = reqwest::Client::new();
+
+        for path in [secret.to_string_lossy().to_string(), "../secret.txt".to_string(), "missing.png".to_string()] {
+            let input = serde_json::json!({ "image": { "file": path } });
+            let error = prepare_input(&client, "http://127.0.0.1:9", "key", app_data.path(), &input).await.unwrap_err();
+            assert!(error.starts_with("Input image is not a file under app data"), "{}", error);
+        }
+    }
+
+    #[tokio::test]
+    async fn polling_backs_off_until_the_prediction_finishes() {
+        let polls = Arc::new(AtomicUsize::new(0));
+        let counter = polls.clone();
+        let server = StubServer::start(move |request| {
+            assert_eq!(request.path, "/predictions/p1");
+            let status = if counter.fetch_add(1, Ordering::SeqCst) < 2 { "processing" } else { "succeeded" };
+            StubResponse::json(200, prediction("p1", status))
+        }).await;
+
+        let mut seen = Vec::new();
+        let client = reqwest::Client::new();
+        let started = Instant::now();
+        let done = follow_prediction(
+            &client,
+            &server.url,
+            "key",
+            prediction("p1", "starting"),
+            &Notify::new(),
+            Duration::from_secs(30),
+            |p| seen.push(p["status"].as_str().unwrap().to_string())
+        ).await.unwrap();
+
+        assert_eq!(done["status"], "succeeded");
+        assert_eq!(seen, ["starting", "processing", "processing", "succeeded"]);
+        let times: Vec<Instant> = server
+            .requests()
+            .iter()
+            .map(|r| r.received)
+            .collect();
+        assert_eq!(times.len(), 3);
+        let gaps = [times[0] - started, times[1] - times[0], times[2] - times[1]];
+        assert!(gaps[0] >= INITIAL_POLL);
+        assert!(gaps[1] >= (INITIAL_POLL * 3) / 2);
+        assert!(gaps[2] >= (INITIAL_POLL * 9) / 4);
+        assert!(gaps[1] > gaps[0] && gaps[2] > gaps[1]);
+    }
+
+    #[tokio::test]
+    async fn cancelling_posts_to_the_cancel_endpoint() {
+        let server = StubServer::start(|request| {
+            match (request.method.as_str(), request.path.as_str()) {
+                ("POST", "/predictions/p2/cancel") => StubResponse::json(200, prediction("p2", "canceled")),
+                _ => StubResponse::json(200, prediction("p2", "processing")),
+            }
+        }).await;
+
+        let cancelled = Arc::new(Notify::new());
+        cancelled.notify_one();
+        let client = reqwest::Client::new();
+        let done = follow_prediction(
+            &client,
+            &server.url,
+            "key",
+            prediction("p2", "processing"),
+            &cancelled,
+            Duration::from_secs(30),
+            |_| {}
+        ).await.unwrap();
+
+        assert_eq!(done["status"], "canceled");
+        let cancels = server.requests_to("POST", "/predictions/p2/cancel");
+        assert_eq!(cancels.len(), 1);
+        assert_eq!(cancels[0].header("authorization"), Some("Bearer key"));
+        assert!(server.requests_to("GET", "/predictions/p2").is_empty());
+    }
+
+    #[tokio::test]
+    async fn failed_create_requests_surface_the_http_error() {
+        let server = StubServer::start(|_| StubResponse::json(422, serde_json::json!({ "detail": "bad input" }))).await;
+        let client = reqwest::Client::new();
+        let error = create_prediction(
+            &client,
+            &server.url,
+            "key",
+            Some("owner/model"),
+            None,
+            &serde_json::json!({}),
+            Some(90)
+        ).await.unwrap_err();
+
+        assert!(error.starts_with("HTTP 422"), "{}", error);
+        let create = &server.requests_to("POST", "/models/owner/model/predictions")[0];
+        assert_eq!(create.header("prefer"), Some("wait=60"));
+    }
+}
@@ -0,0 +1,563 @@
+use crate::catalog_snapshot::{ self, CatalogSnapshot };
+use crate::credentials;
+use once_cell::sync::Lazy;
+use serde::de::DeserializeOwned;
+use serde::{ Deserialize, Deserializer, Serialize };
+use serde_json::{ json, Value };
+use std::fmt;
+use std::sync::RwLock;
+use std::time::Duration;
+use tauri::Manager;
+
+const CONFIG_FILE: &str = "subgraph.json";
+const DEFAULT_ENDPOINT: &str =
+    "https://gateway.thegraph.com/api/subgraphs/id/4jujS6j8wMahNTSzN2T8wN7iTiEfNRQfoYYQL7Q1Bn9X";
+pub const TEMPLATE_CONTRACT: &str = "0xb3a7fa244e230da90e93a68fd04c1f07e1f62f23";
+pub const MATERIAL_CONTRACT: &str = "0x029bc2ba787c131fd58938a192787c066955cd2f";
+pub const COLOR_CONTRACT: &str = "0xf71d3b3a5efa4f8e34d4cd922831833350fa3dfb";
+
+/// The Graph refuses pages larger than this.
+const MAX_PAGE_SIZE: u32 = 1000;
+
+const TEMPLATES_QUERY: &str =
+    r#"
+query Templates($first: Int!, $after: String!, $contract: String!) {
+  templates(first: $first, orderBy: id, orderDirection: asc, where: { id_gt: $after, templateContract: $contract }) {
+    id
+    templateContract
+    templateId
+    physicalPrice
+    childType
+    infraCurrency
+    uri
+    metadata {
+      title
+      image
+      tags
+    }
+    childReferences {
+      placementURI
+      childId
+      amount
+      childContract
+      child {
+        physicalPrice
+        metadata {
+          title
+          image
+          tags
+          description
+        }
+      }
+    }
+  }
+}
+"#;
+
+const CHILDS_QUERY: &str =
+    r#"
+query Childs($first: Int!, $after: String!, $contracts: [String!]!) {
+  childs(first: $first, orderBy: id, orderDirection: asc, where: { id_gt: $after, childContract_in: $contracts }) {
+    id
+    childContract
+    childId
+    physicalPrice
+    childType
+    infraCurrency
+    uri
+    metadata {
+      title
+      image
+      tags
+      description
+    }
+  }
+}
+"#;
+
+static SUBGRAPH_CONFIG: Lazy<RwLock<SubgraphConfig>> = Lazy::new(|| RwLock::new(SubgraphConfig::default()));
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubgraphConfig {
+    pub endpoint: String,
+    /// Name of the stored credential sent as a bearer token; unset for endpoints that need no key
+    #[serde(rename = "apiKeyName", default)]
+    pub api_key_name: Option<String>,
+    #[serde(rename = "pageSize", default = "default_page_size")]
+    pub page_size: u32,
+    #[serde(rename = "timeoutSecs", default = "default_timeout")]
+    pub timeout_secs: u64,
+    /// Skip the network entirely and serve the bundled snapshot
+    #[serde(default)]
+    pub offline: bool,
+}
+
+fn default_page_size() -> u32 {
+    100
+}
+
+fn default_timeout() -> u64 {
+    20
+}
+
+impl Default for SubgraphConfig {
+    fn default() -> Self {
+        SubgraphConfig {
+            endpoint: DEFAULT_ENDPOINT.to_string(),
+            api_key_name: None,
+            page_size: default_page_size(),
+            timeout_secs: default_timeout(),
+            offline: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphQLError {
+    pub message: String,
+    #[serde(default)]
+    pub path: Option<Vec<Value>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case", rename_all_fields = "camelCase")]
+pub enum SubgraphError {
+    Offline,
+    Credential {
+        message: String,
+    },
+    Network {
+        message: String,
+    },
+    Http {
+        status: u16,
+        body: String,
+    },
+    #[serde(rename = "graphql")]
+    GraphQL {
+        errors: Vec<GraphQLError>,
+    },
+    Decode {
+        message: String,
+    },
+}
+
+impl fmt::Display for SubgraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubgraphError::Offline => write!(f, "Subgraph is configured offline"),
+            SubgraphError::Credential { message } => write!(f, "Subgraph API key: {}", message),
+            SubgraphError::Network { message } => write!(f, "Subgraph request failed: {}", message),
+            SubgraphError::Http { status, body } => write!(f, "Subgraph HTTP {}: {}", status, body),
+            SubgraphError::GraphQL { errors } => {
+                let messages: Vec<&str> = errors
+                    .iter()
+                    .map(|e| e.message.as_str())
+                    .collect();
+                write!(f, "GraphQL errors: {}", messages.join("; "))
+            }
+            SubgraphError::Decode { message } => write!(f, "Unexpected subgraph response: {}", message),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CatalogSource {
+    Live,
+    Snapshot,
+}
+
+/// Items and where they came from; `error` explains why the snapshot was used instead of the live subgraph.
+#[derive(Debug, Clone, Serialize)]
+pub struct Catalog<T> {
+    pub source: CatalogSource,
+    pub error: Option<SubgraphError>,
+    pub items: Vec<T>,
+}
+
+/// Numeric subgraph fields arrive as strings (`BigInt`) but older snapshots hold plain numbers.
+fn int_or_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<i32>, D::Error> {
+    Ok(
+        match Option::<Value>::deserialize(deserializer)? {
+            Some(Value::String(s)) => s.parse().ok(),
+            Some(Value::Number(n)) => n.as_i64().map(|n| n as i32),
+            _ => None,
+        }
+    )
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct RawMetadata {
+    pub title: Option<String>,
+    pub image: Option<String>,
+    pub description: Option<String>,
+    pub tags: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct RawChild {
+    #[serde(rename = "physicalPrice", default)]
+    pub physical_price: String,
+    pub metadata: Option<RawMetadata>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct RawChildReference {
+    #[serde(rename = "placementURI", default)]
+    pub placement_uri: String,
+    #[serde(rename = "childId", default)]
+    pub child_id: String,
+    #[serde(rename = "childContract", default)]
+    pub child_contract: String,
+    #[serde(default, deserialize_with = "int_or_string")]
+    pub amount: Option<i32>,
+    pub child: Option<RawChild>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct RawTemplate {
+    #[serde(rename = "templateContract", default)]
+    pub template_contract: String,
+    #[serde(rename = "templateId", default)]
+    pub template_id: String,
+    #[serde(rename = "physicalPrice", default)]
+    pub physical_price: String,
+    #[serde(rename = "childType", default)]
+    pub child_type: String,
+    #[serde(rename = "infraCurrency", default)]
+    pub infra_currency: String,
+    #[serde(default)]
+    pub uri: String,
+    pub metadata: Option<RawMetadata>,
+    #[serde(rename = "childReferences", default)]
+    pub child_references: Vec<RawChildReference>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct RawChildEntity {
+    #[serde(rename = "childContract", default)]
+    pub child_contract: String,
+    #[serde(rename = "childId", default)]
+    pub child_id: String,
+    #[serde(rename = "physicalPrice", default)]
+    pub physical_price: String,
+    #[serde(rename = "childType", default)]
+    pub child_type: String,
+    #[serde(rename = "infraCurrency", default)]
+    pub infra_currency: String,
+    #[serde(default)]
+    pub uri: String,
+    pub metadata: Option<RawMetadata>,
+}
+
+#[derive(Deserialize)]
+struct GraphQLResponse {
+    data: Option<Value>,
+    #[serde(default)]
+    errors: Vec<GraphQLError>,
+}
+
+pub fn config() -> SubgraphConfig {
+    SUBGRAPH_CONFIG.read().unwrap().clone()
+}
+
+async fn query(config: &SubgraphConfig, query: &str, variables: Value) -> Result<Value, SubgraphError> {
+    let client = reqwest::Client
+        ::builder()
+        .timeout(Duration::from_secs(config.timeout_secs.max(1)))
+        .build()
+        .map_err(|e| SubgraphError::Network { message: e.to_string() })?;
+    let mut request = client.post(&config.endpoint).json(&json!({ "query": query, "variables": variables }));
+    if let Some(name) = &config.api_key_name {
+        let api_key = credentials::secret_for(name, &config.endpoint).map_err(|message| SubgraphError::Credential { message })?;
+        request = request.bearer_auth(api_key);
+    }
+
+    let response = request.send().await.map_err(|e| SubgraphError::Network { message: e.to_string() })?;
+    let status = response.status();
+    let body = response.text().await.map_err(|e| SubgraphError::Network { message: e.to_string() })?;
+    if !status.is_success() {
+        return Err(SubgraphError::Http { status: status.as_u16(), body });
+    }
+    let parsed: GraphQLResponse = serde_json
+        ::from_str(&body)
+        .map_err(|e| SubgraphError::Decode { message: e.to_string() })?;
+    if !parsed.errors.is_empty() {
+        return Err(SubgraphError::GraphQL { errors: parsed.errors });
+    }
+    parsed.data.ok_or(SubgraphError::Decode { message: "response has no data".to_string() })
+}
+
+/// Fetches every entity of `field` by walking `id_gt` cursors, which unlike `skip` has no upper bound.
+async fn paginate<T: DeserializeOwned>(
+    config: &SubgraphConfig,
+    query_text: &str,
+    field: &str,
+    variables: Value
+) -> Result<Vec<T>, SubgraphError> {
+    let first = config.page_size.clamp(1, MAX_PAGE_SIZE);
+    let mut after = String::new();
+    let mut items = Vec::new();
+    loop {
+        let mut page_variables = variables.clone();
+        page_variables["first"] = json!(first);
+        page_variables["after"] = json!(after);
+        let mut data = query(config, query_text, page_variables).await?;
+        let page = data
+            .get_mut(field)
+            .map(Value::take)
+            .and_then(|v| match v {
+                Value::Array(page) => Some(page),
+                _ => None,
+            })
+            .ok_or_else(|| SubgraphError::Decode { message: format!("missing {}", field) })?;
+
+        let count = page.len();
+        let Some(last_id) = page
+            .last()
+            .and_then(|item| item["id"].as_str())
+            .map(str::to_string) else {
+            break;
+        };
+        for item in page {
+            items.push(serde_json::from_value(item).map_err(|e| SubgraphError::Decode { message: e.to_string() })?);
+        }
+        if count < (first as usize) || last_id == after {
+            break;
+        }
+        after = last_id;
+    }
+    Ok(items)
+}
+
+/// Live catalog if the subgraph answers, otherwise the current snapshot: the latest refreshed copy in app
+/// data, or the one bundled with this release.
+async fn with_fallback<T>(
+    live: impl std::future::Future<Output = Result<Vec<T>, SubgraphError>>,
+    snapshot: impl FnOnce(&CatalogSnapshot) -> Vec<T>
+) -> Catalog<T> {
+    match live.await {
+        Ok(items) => Catalog { source: CatalogSource::Live, error: None, items },
+        Err(error) => {
+            let current = catalog_snapshot::current();
+            if !matches!(error, SubgraphError::Offline) {
+                log::warn!("Using catalog snapshot v{}: {}", current.version, error);
+            }
+            Catalog { source: CatalogSource::Snapshot, error: Some(error), items: snapshot(&current) }
+        }
+    }
+}
+
+pub async fn live_templates() -> Result<Vec<RawTemplate>, SubgraphError> {
+    let config = config();
+    if config.offline {
+        return Err(SubgraphError::Offline);
+    }
+    paginate(&config, TEMPLATES_QUERY, "templates", json!({ "contract": TEMPLATE_CONTRACT })).await
+}
+
+pub async fn live_childs() -> Result<Vec<RawChildEntity>, SubgraphError> {
+    let config = config();
+    if config.offline {
+        return Err(SubgraphError::Offline);
+    }
+    paginate(&config, CHILDS_QUERY, "childs", json!({ "contracts": [MATERIAL_CONTRACT, COLOR_CONTRACT] })).await
+}
+
+pub async fn templates() -> Catalog<RawTemplate> {
+    with_fallback(live_templates(), |snapshot| snapshot.templates.clone()).await
+}
+
+pub async fn childs() -> Catalog<RawChildEntity> {
+    with_fallback(live_childs(), |snapshot| snapshot.childs.clone()).await
+}
+
+fn config_path(app: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    Ok(app_data_dir.join(CONFIG_FILE))
+}
+
+/// Loads the saved endpoint settings at startup, keeping the defaults when there are none.
+pub fn load_config(app: &tauri::AppHandle) {
+    let Ok(json) = config_path(app).and_then(|path| std::fs::read_to_string(path).map_err(|e| e.to_string())) else {
+        return;
+    };
+    match serde_json::from_str::<SubgraphConfig>(&json) {
+        Ok(config) => {
+            *SUBGRAPH_CONFIG.write().unwrap() = config;
+        }
+        Err(e) => log::warn!("Ignoring unreadable {}: {}", CONFIG_FILE, e),
+    }
+}
+
+#[tauri::command]
+pub async fn subgraph_get_config() -> Result<SubgraphConfig, String> {
+    Ok(config())
+}
+
+#[tauri::command]
+pub async fn subgraph_set_config(app: tauri::AppHandle, config: SubgraphConfig) -> Result<(), String> {
+    if !config.endpoint.starts_with("http://") && !config.endpoint.starts_with("https://") {
+        return Err(format!("Subgraph endpoint {} must be an http(s) URL", config.endpoint));
+    }
+    if let Some(name) = &config.api_key_name {
+        credentials::check_destination(name, &config.endpoint)?;
+    }
+    let path = config_path(&app)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    let json = serde_json::to_string_pretty(&config).map_err(|e| format!("Failed to serialize config: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write config: {}", e))?;
+    *SUBGRAPH_CONFIG.write().unwrap() = config;
+    Ok(())
+}
+
+/// Runs a one-entity query against the configured endpoint and reports the structured error, if any.
+#[tauri::command]
+pub async fn subgraph_test_connection() -> Result<Option<SubgraphError>, String> {
+    let config = SubgraphConfig { page_size: 1, ..config() };
+    let probe = query(
+        &config,
+        TEMPLATES_QUERY,
+        json!({ "first": 1, "after": "", "contract": TEMPLATE_CONTRACT })
+    ).await;
+    Ok(probe.err())
+}
+
+/// Swaps the endpoint settings for a test; callers hold `test_support::REMOTE_CONFIG`.
+#[cfg(test)]
+pub fn use_config_for_tests(config: SubgraphConfig) {
+    *SUBGRAPH_CONFIG.write().unwrap() = config;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{ StubResponse, StubServer, REMOTE_CONFIG };
+
+    fn stub_config(server: &StubServer, page_size: u32) -> SubgraphConfig {
+        SubgraphConfig { endpoint: server.url.clone(), page_size, timeout_secs: 5, ..SubgraphConfig::default() }
+    }
+
+    fn child(id: &str) -> Value {
+        json!({ "id": id, "childContract": MATERIAL_CONTRACT, "childId": id, "physicalPrice": "1000", "metadata": { "title": id } })
+    }
+
+    #[tokio::test]
+    async fn paginates_by_id_cursor_until_a_short_page() {
+        let server = StubServer::start(|request| {
+            let ids: &[&str] = match request.json()["variables"]["after"].as_str() {
+                Some("") => &["0x01", "0x02"],
+                Some("0x02") => &["0x03", "0x04"],
+                Some("0x04") => &["0x05"],
+                _ => &[],
+            };
+            let page: Vec<Value> = ids
+                .iter()
+                .map(|id| child(id))
+                .collect();
+            StubResponse::json(200, json!({ "data": { "childs": page } }))
+        }).await;
+
+        let childs: Vec<RawChildEntity> = paginate(
+            &stub_config(&server, 2),
+            CHILDS_QUERY,
+            "childs",
+            json!({ "contracts": [MATERIAL_CONTRACT] })
+        ).await.unwrap();
+
+        let ids: Vec<&str> = childs
+            .iter()
+            .map(|c| c.child_id.as_str())
+            .collect();
+        assert_eq!(ids, ["0x01", "0x02", "0x03", "0x04", "0x05"]);
+        let cursors: Vec<Value> = server
+            .requests()
+            .iter()
+            .map(|r| {
+                let body = r.json();
+                assert_eq!(body["variables"]["first"], 2);
+                assert_eq!(body["variables"]["contracts"], json!([MATERIAL_CONTRACT]));
+                body["variables"]["after"].clone()
+            })
+            .collect();
+        assert_eq!(cursors, [json!(""), json!("0x02"), json!("0x04")]);
+    }
+
+    #[tokio::test]
+    async fn stops_on_an_empty_full_page_boundary() {
+        let server = StubServer::start(|request| {
+            let page = match request.json()["variables"]["after"].as_str() {
+                Some("") => vec![child("0x01"), child("0x02")],
+                _ => vec![],
+            };
+            StubResponse::json(200, json!({ "data": { "childs": page } }))
+        }).await;
+
+        let childs: Vec<RawChildEntity> = paginate(&stub_config(&server, 2), CHILDS_QUERY, "childs", json!({})).await.unwrap();
+        assert_eq!(childs.len(), 2);
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn graphql_errors_are_reported_as_such() {
+        let server = StubServer::start(|_| {
+            StubResponse::json(
+                200,
+                json!({ "data": null, "errors": [{ "message": "indexing_error", "path": ["childs"] }, { "message": "too slow" }] })
+            )
+        }).await;
+
+        let error = paginate::<RawChildEntity>(&stub_config(&server, 10), CHILDS_QUERY, "childs", json!({})).await.unwrap_err();
+        match &error {
+            SubgraphError::GraphQL { errors } => {
+                assert_eq!(errors.len(), 2);
+                assert_eq!(errors[0].message, "indexing_error");
+                assert_eq!(errors[0].path, Some(vec![json!("childs")]));
+            }
+            other => panic!("expected GraphQL errors, got {:?}", other),
+        }
+        assert_eq!(error.to_string(), "GraphQL errors: indexing_error; too slow");
+        assert_eq!(serde_json::to_value(&error).unwrap()["kind"], "graphql");
+    }
+
+    #[tokio::test]
+    async fn http_failures_keep_status_and_body() {
+        let server = StubServer::start(|_| StubResponse::json(429, json!({ "error": "rate limited" }))).await;
+
+        let error = query(&stub_config(&server, 10), CHILDS_QUERY, json!({})).await.unwrap_err();
+        match error {
+            SubgraphError::Http { status, body } => {
+                assert_eq!(status, 429);
+                assert!(body.contains("rate limited"));
+            }
+            other => panic!("expected an HTTP error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_the_snapshot_when_the_subgraph_fails() {
+        let _turn = REMOTE_CONFIG.lock().await;
+        let server = StubServer::start(|_| StubResponse::json(503, json!("unavailable"))).await;
+        *SUBGRAPH_CONFIG.write().unwrap() = stub_config(&server, 10);
+
+        let catalog = templates().await;
+        assert!(matches!(catalog.source, CatalogSource::Snapshot));
+        assert!(matches!(catalog.error, Some(SubgraphError::Http { status: 503, .. })));
+        assert_eq!(catalog.items, catalog_snapshot::current().templates);
+        assert_eq!(server.requests().len(), 1);
+
+        *SUBGRAPH_CONFIG.write().unwrap() = SubgraphConfig { offline: true, ..stub_config(&server, 10) };
+        let catalog = childs().await;
+        assert!(matches!(catalog.error, Some(SubgraphError::Offline)));
+        assert_eq!(catalog.items, catalog_snapshot::current().childs);
+        assert_eq!(server.requests().len(), 1);
+
+        *SUBGRAPH_CONFIG.write().unwrap() = SubgraphConfig::default();
+    }
+}
@@ -0,0 +1,251 @@
+use once_cell::sync::Lazy;
+use serde::{ Deserialize, Serialize };
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::RwLock;
+use tauri::Manager;
+
+const REGISTRY_FILE: &str = "template_types.json";
+const BUNDLED_REGISTRY: &str = include_str!("../defaults/template_types.json");
+
+static REGISTRY: Lazy<RwLock<TemplateTypeRegistry>> = Lazy::new(|| RwLock::new(bundled()));
+
+/// Nesting settings a product type starts from; unset fields keep the app-wide defaults.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NestingDefaults {
+    #[serde(rename = "minItemSeparation", default, skip_serializing_if = "Option::is_none")]
+    pub min_item_separation: Option<f64>,
+    #[serde(rename = "allowedRotations", default, skip_serializing_if = "Option::is_none")]
+    pub allowed_rotations: Option<Vec<f64>>,
+    #[serde(rename = "stripWidthMultiplier", default, skip_serializing_if = "Option::is_none")]
+    pub strip_width_multiplier: Option<f64>,
+    #[serde(rename = "iterationLimit", default, skip_serializing_if = "Option::is_none")]
+    pub iteration_limit: Option<u64>,
+    #[serde(rename = "strikeLimit", default, skip_serializing_if = "Option::is_none")]
+    pub strike_limit: Option<u64>,
+    #[serde(rename = "garmentType", default, skip_serializing_if = "Option::is_none")]
+    pub garment_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateType {
+    pub id: String,
+    pub name: String,
+    /// IPFS CID of the cover image
+    pub image: String,
+    /// Template tags (case-insensitive) that place a template under this type
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// Material and color tags offered for this type; empty offers everything
+    #[serde(default)]
+    pub materials: Vec<String>,
+    #[serde(default)]
+    pub nesting: Option<NestingDefaults>,
+    /// Whether the type was derived from a template's own tags rather than configured
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub discovered: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateTypeRegistry {
+    pub version: u32,
+    /// Give templates that match no configured type a type named after their first meaningful tag
+    #[serde(rename = "discoverFromTags", default)]
+    pub discover_from_tags: bool,
+    /// Tags every template carries, never used to name a discovered type
+    #[serde(rename = "ignoredTags", default)]
+    pub ignored_tags: Vec<String>,
+    pub types: Vec<TemplateType>,
+}
+
+impl TemplateTypeRegistry {
+    fn validate(&self) -> Result<(), String> {
+        let mut ids = HashSet::new();
+        for template_type in &self.types {
+            if template_type.id.trim().is_empty() {
+                return Err(format!("Template type {} has no id", template_type.name));
+            }
+            if !ids.insert(template_type.id.as_str()) {
+                return Err(format!("Template type {} is defined twice", template_type.id));
+            }
+        }
+        Ok(())
+    }
+
+    /// The configured type whose aliases match `tags`, or with discovery enabled one built from the first
+    /// tag that is not ignored. `image` is the template's own image, used as the discovered type's cover.
+    pub fn type_for_tags(&self, tags: &[String], image: Option<&str>) -> Option<TemplateType> {
+        let tags: Vec<String> = tags
+            .iter()
+            .map(|tag| tag.trim().to_lowercase())
+            .collect();
+        let configured = tags.iter().find_map(|tag| {
+            self.types.iter().find(|t| t.aliases.iter().any(|alias| alias.to_lowercase() == *tag))
+        });
+        if let Some(template_type) = configured {
+            return Some(template_type.clone());
+        }
+        if !self.discover_from_tags {
+            return None;
+        }
+
+        let tag = tags
+            .into_iter()
+            .find(|tag| !tag.is_empty() && !self.ignored_tags.iter().any(|ignored| ignored.to_lowercase() == *tag))?;
+        let id: String = tag
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '-' })
+            .collect();
+        let mut name: Vec<char> = tag.chars().collect();
+        if let Some(first) = name.first_mut() {
+            *first = first.to_ascii_uppercase();
+        }
+        Some(TemplateType {
+            id,
+            name: name.into_iter().collect(),
+            image: image.map(|i| i.trim_start_matches("ipfs://").to_string()).unwrap_or_default(),
+            aliases: vec![tag],
+            materials: Vec::new(),
+            nesting: None,
+            discovered: true,
+        })
+    }
+}
+
+fn bundled() -> TemplateTypeRegistry {
+    serde_json::from_str(BUNDLED_REGISTRY).expect("bundled template type registry is valid")
+}
+
+pub fn registry() -> TemplateTypeRegistry {
+    REGISTRY.read().unwrap().clone()
+}
+
+fn registry_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    Ok(app_data_dir.join(REGISTRY_FILE))
+}
+
+/// Replaces the bundled registry with `template_types.json` from app data when one exists and is valid.
+pub fn load_registry(app: &tauri::AppHandle) {
+    let Ok(json) = registry_path(app).and_then(|path| std::fs::read_to_string(path).map_err(|e| e.to_string())) else {
+        return;
+    };
+    match
+        serde_json
+            ::from_str::<TemplateTypeRegistry>(&json)
+            .map_err(|e| e.to_string())
+            .and_then(|registry| registry.validate().map(|_| registry))
+    {
+        Ok(registry) => {
+            *REGISTRY.write().unwrap() = registry;
+        }
+        Err(e) => log::warn!("Ignoring unreadable {}: {}", REGISTRY_FILE, e),
+    }
+}
+
+#[tauri::command]
+pub async fn template_types_get() -> Result<TemplateTypeRegistry, String> {
+    Ok(registry())
+}
+
+#[tauri::command]
+pub async fn template_types_set(app: tauri::AppHandle, registry: TemplateTypeRegistry) -> Result<(), String> {
+    registry.validate()?;
+    let path = registry_path(&app)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    let json = serde_json
+        ::to_string_pretty(&registry)
+        .map_err(|e| format!("Failed to serialize template types: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write template types: {}", e))?;
+    *REGISTRY.write().unwrap() = registry;
+    Ok(())
+}
+
+/// Drops the app data override and goes back to the registry bundled with the app.
+#[tauri::command]
+pub async fn template_types_reset(app: tauri::AppHandle) -> Result<TemplateTypeRegistry, String> {
+    let path = registry_path(&app)?;
+    if path.exists() {
+        std::fs::remove_file(&path).map_err(|e| format!("Failed to remove template types: {}", e))?;
+    }
+    let registry = bundled();
+    *REGISTRY.write().unwrap() = registry.clone();
+    Ok(registry)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(tags: &[&str]) -> Vec<String> {
+        tags.iter().map(|tag| tag.to_string()).collect()
+    }
+
+    #[test]
+    fn bundled_registry_is_valid() {
+        let registry = bundled();
+        registry.validate().unwrap();
+        let garment = |id: &str| {
+            registry.types
+                .iter()
+                .find(|t| t.id == id)
+                .and_then(|t| t.nesting.as_ref())
+                .and_then(|n| n.garment_type.clone())
+        };
+        assert_eq!(garment("shirt").as_deref(), Some("tshirt"));
+        assert_eq!(garment("hoodie").as_deref(), Some("hoodie"));
+        assert_eq!(garment("poster"), None);
+    }
+
+    #[test]
+    fn aliases_match_case_insensitively() {
+        let registry = bundled();
+        let shirt = registry.type_for_tags(&tags(&["Template", " T-Shirt "]), None).unwrap();
+        assert_eq!(shirt.id, "shirt");
+        assert!(!shirt.discovered);
+        assert_eq!(registry.type_for_tags(&tags(&["front", "SHIRT"]), None).unwrap().id, "shirt");
+        assert_eq!(registry.type_for_tags(&tags(&["HOODIE"]), None).unwrap().id, "hoodie");
+    }
+
+    #[test]
+    fn discovers_a_type_from_the_first_meaningful_tag() {
+        let mut registry = bundled();
+        registry.discover_from_tags = true;
+        let discovered = registry
+            .type_for_tags(&tags(&["template", "Front", "", "tote bag", "canvas"]), Some("ipfs://QmCover"))
+            .unwrap();
+        assert_eq!(discovered.id, "tote-bag");
+        assert_eq!(discovered.name, "Tote bag");
+        assert_eq!(discovered.image, "QmCover");
+        assert_eq!(discovered.aliases, vec!["tote bag".to_string()]);
+        assert!(discovered.discovered);
+        assert!(discovered.nesting.is_none());
+
+        // A configured alias anywhere in the tags still wins over discovery.
+        assert_eq!(registry.type_for_tags(&tags(&["tote bag", "hoodie"]), None).unwrap().id, "hoodie");
+        assert!(registry.type_for_tags(&tags(&["template", "customizable"]), None).is_none());
+    }
+
+    #[test]
+    fn unknown_tags_have_no_type_without_discovery() {
+        let mut registry = bundled();
+        registry.discover_from_tags = false;
+        assert!(registry.type_for_tags(&tags(&["tote bag"]), None).is_none());
+    }
+
+    #[test]
+    fn rejects_duplicate_and_empty_ids() {
+        let mut registry = bundled();
+        registry.types.push(registry.types[0].clone());
+        assert!(registry.validate().unwrap_err().contains("defined twice"));
+
+        let mut registry = bundled();
+        registry.types[0].id = " ".to_string();
+        assert!(registry.validate().unwrap_err().contains("has no id"));
+    }
+}
@@ -0,0 +1,178 @@
+//! A minimal HTTP/1.1 server for tests that talk to remote services; one request per connection. `GET /ws` is
+//! upgraded to a websocket that plays back scripted frames.
+
+use crate::comfyui_schema::{ self, NodeSchema };
+use futures_util::{ SinkExt, StreamExt };
+use std::collections::{ BTreeMap, HashMap };
+use std::sync::{ Arc, Mutex };
+use std::time::Instant;
+use tokio::io::{ AsyncReadExt, AsyncWriteExt };
+use tokio::net::{ TcpListener, TcpStream };
+use tokio_tungstenite::tungstenite::Message;
+
+/// Held by tests that point the process-wide IPFS gateways or subgraph endpoint at a stub, so they take turns.
+pub static REMOTE_CONFIG: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+#[derive(Debug, Clone)]
+pub struct StubRequest {
+    pub method: String,
+    /// Path and query, as sent
+    pub path: String,
+    /// Lower-cased header names
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+    pub received: Instant,
+}
+
+impl StubRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(&name.to_lowercase()).map(String::as_str)
+    }
+
+    pub fn json(&self) -> serde_json::Value {
+        serde_json::from_slice(&self.body).unwrap_or(serde_json::Value::Null)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StubResponse {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: Vec<u8>,
+}
+
+impl StubResponse {
+    pub fn json(status: u16, body: serde_json::Value) -> Self {
+        StubResponse { status, content_type: "application/json", body: body.to_string().into_bytes() }
+    }
+
+    pub fn bytes(status: u16, body: &[u8]) -> Self {
+        StubResponse { status, content_type: "application/octet-stream", body: body.to_vec() }
+    }
+}
+
+/// An `/object_info` response in the form ComfyUI serves it, cut down to the nodes the tests use
+pub fn object_info() -> serde_json::Value {
+    serde_json::from_str(include_str!("../tests/fixtures/object_info.json")).unwrap()
+}
+
+pub fn schemas() -> BTreeMap<String, NodeSchema> {
+    object_info()
+        .as_object()
+        .unwrap()
+        .iter()
+        .map(|(class_type, info)| (class_type.clone(), comfyui_schema::parse_node_schema(class_type, info)))
+        .collect()
+}
+
+type Handler = Arc<dyn Fn(&StubRequest) -> StubResponse + Send + Sync>;
+type Frames = Arc<Vec<Message>>;
+
+pub struct StubServer {
+    pub url: String,
+    requests: Arc<Mutex<Vec<StubRequest>>>,
+}
+
+impl StubServer {
+    pub async fn start(handler: impl Fn(&StubRequest) -> StubResponse + Send + Sync + 'static) -> StubServer {
+        Self::with_socket(handler, Vec::new()).await
+    }
+
+    /// Also accepts websockets on `/ws`, sending `frames` to each one and then holding it open until the client
+    /// closes it.
+    pub async fn with_socket(
+        handler: impl Fn(&StubRequest) -> StubResponse + Send + Sync + 'static,
+        frames: Vec<Message>
+    ) -> StubServer {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let handler: Handler = Arc::new(handler);
+        let frames: Frames = Arc::new(frames);
+        let log = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let handler = handler.clone();
+                let frames = frames.clone();
+                let log = log.clone();
+                tokio::spawn(async move {
+                    let mut start = [0u8; 7];
+                    let upgrade = matches!(stream.peek(&mut start).await, Ok(7) if &start == b"GET /ws");
+                    let _ = if upgrade { play(stream, frames).await } else { serve(stream, handler, log).await };
+                });
+            }
+        });
+        StubServer { url, requests }
+    }
+
+    /// Every request received so far, in arrival order.
+    pub fn requests(&self) -> Vec<StubRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    pub fn requests_to(&self, method: &str, path_prefix: &str) -> Vec<StubRequest> {
+        self.requests()
+            .into_iter()
+            .filter(|r| r.method == method && r.path.starts_with(path_prefix))
+            .collect()
+    }
+}
+
+async fn play(stream: TcpStream, frames: Frames) -> std::io::Result<()> {
+    let mut socket = tokio_tungstenite::accept_async(stream).await.map_err(std::io::Error::other)?;
+    for frame in frames.iter() {
+        socket.send(frame.clone()).await.map_err(std::io::Error::other)?;
+    }
+    while let Some(Ok(_)) = socket.next().await {}
+    Ok(())
+}
+
+async fn serve(mut stream: TcpStream, handler: Handler, log: Arc<Mutex<Vec<StubRequest>>>) -> std::io::Result<()> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 8192];
+    let header_end = loop {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Ok(());
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+        if let Some(end) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break end;
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or("").split_whitespace();
+    let method = request_line.next().unwrap_or("").to_string();
+    let path = request_line.next().unwrap_or("").to_string();
+    let headers: HashMap<String, String> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_string()))
+        .collect();
+    let length: usize = headers
+        .get("content-length")
+        .and_then(|l| l.parse().ok())
+        .unwrap_or(0);
+    let mut body = buffer[header_end + 4..].to_vec();
+    while body.len() < length {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            break;
+        }
+        body.extend_from_slice(&chunk[..read]);
+    }
+
+    let request = StubRequest { method, path, headers, body, received: Instant::now() };
+    let response = handler(&request);
+    log.lock().unwrap().push(request);
+    let head = format!(
+        "HTTP/1.1 {} Stub\r\ncontent-type: {}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+        response.status,
+        response.content_type,
+        response.body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(&response.body).await?;
+    stream.shutdown().await
+}
@@ -8,6 +8,7 @@ import {
 } from "@tauri-apps/plugin-fs";
 import { invoke } from "@tauri-apps/api/core";
 import { useCallback } from "react";
+import { bytesToDataUrl, readAppFile, saveAppFile } from "../../../lib/binaryIpc";
 
 let directoriesInitialized = false;
 
@@ -221,10 +222,7 @@ export const useFileStorage = () => {
         const arrayBuffer = await blob.arrayBuffer();
         const uint8Array = new Uint8Array(arrayBuffer);
         
-        await invoke('save_binary_file', {
-          path: filePath,
-          data: Array.from(uint8Array)
-        });
+        await saveAppFile(filePath, uint8Array);
         
         return filePath;
       } catch (error) {
@@ -237,16 +235,14 @@ export const useFileStorage = () => {
   const getBinaryFileUrl = useCallback(
     async (filePath: string): Promise<string> => {
       try {
-        const base64Data = await invoke<string>('read_binary_file_as_base64', {
-          path: filePath
-        });
+        const data = await readAppFile(filePath);
         
         const extension = filePath.split('.').pop()?.toLowerCase();
         const mimeType = extension === 'png' ? 'image/png' : 
                         extension === 'jpg' || extension === 'jpeg' ? 'image/jpeg' : 
                         'application/octet-stream';
         
-        return `data:${mimeType};base64,${base64Data}`;
+        return bytesToDataUrl(data, mimeType);
       } catch (error) {
         throw error;
       }
@@ -1,10 +1,11 @@
 import { useApp } from "../../../context/AppContext";
 import { openUrl } from "@tauri-apps/plugin-opener";
+import { getGarmentType } from "../../Synth/utils/templateHelpers";
 
 export const useBlender = () => {
   const { selectedTemplate } = useApp();
 
-  const isApplicableItem = selectedTemplate?.template_type === "shirt" || selectedTemplate?.template_type === "hoodie";
+  const isApplicableItem = getGarmentType(selectedTemplate) !== null;
 
   const handleDownloadPlugin = async () => {
     try {
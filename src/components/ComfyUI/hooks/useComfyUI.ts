@@ -1,30 +1,84 @@
 import { invoke } from "@tauri-apps/api/core";
-import { ComfyUIWorkflow, NodeInfo } from "../types/comfyui.types";
+import {
+  ComfyUIWorkflow,
+  NodeInfo,
+  TemplateSubmission,
+  WorkflowTemplate,
+  WorkflowValidation,
+} from "../types/comfyui.types";
 export const useComfyUI = () => {
   const parseWorkflow = async (
-    workflowJson: string
+    workflowJson: string,
+    comfyUrl?: string
   ): Promise<ComfyUIWorkflow> => {
     try {
       const result = await invoke<ComfyUIWorkflow>("parse_comfyui_workflow", {
         workflowJson,
+        comfyUrl,
       });
       return result;
     } catch (error) {
       throw error;
     }
   };
-  const getNodeInfo = async (classType: string): Promise<NodeInfo> => {
+  const getNodeInfo = async (
+    classType: string,
+    comfyUrl?: string
+  ): Promise<NodeInfo> => {
     try {
       const result = await invoke<NodeInfo>("get_node_info", {
         classType,
+        comfyUrl,
       });
       return result;
     } catch (error) {
       throw error;
     }
   };
+  const validateWorkflow = async (
+    comfyUrl: string,
+    workflow: ComfyUIWorkflow
+  ): Promise<WorkflowValidation> => {
+    try {
+      const result = await invoke<WorkflowValidation>(
+        "validate_comfyui_workflow",
+        {
+          comfyUrl,
+          workflow,
+        }
+      );
+      return result;
+    } catch (error) {
+      throw error;
+    }
+  };
+  const executeTemplate = async (
+    comfyUrl: string,
+    template: WorkflowTemplate,
+    params: Record<string, any>,
+    clientId: string,
+    randomizeSeeds?: boolean
+  ): Promise<TemplateSubmission> => {
+    try {
+      const result = await invoke<TemplateSubmission>(
+        "comfyui_execute_template",
+        {
+          comfyUrl,
+          template,
+          params,
+          clientId,
+          randomizeSeeds,
+        }
+      );
+      return result;
+    } catch (error) {
+      throw error;
+    }
+  };
   return {
     parseWorkflow,
     getNodeInfo,
+    validateWorkflow,
+    executeTemplate,
   };
 };
@@ -108,7 +108,8 @@ export const useNodeCanvas = () => {
           };
           const renderNode: RenderNode = {
             ...node,
-            position: (savedPositions[node.id] || [
+            position: (savedPositions[node.id] ||
+              node.position || [
               startX + col * nodeSpacingX,
               startY + row * nodeSpacingY,
             ]) as [number, number],
@@ -145,7 +146,8 @@ export const useNodeCanvas = () => {
           };
           const renderNode: RenderNode = {
             ...node,
-            position: (savedPositions[node.id] || [
+            position: (savedPositions[node.id] ||
+              node.position || [
               startX + col * nodeSpacingX,
               startY + row * nodeSpacingY,
             ]) as [number, number],
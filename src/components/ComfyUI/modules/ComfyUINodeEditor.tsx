@@ -31,7 +31,7 @@ export default function ComfyUINodeEditor({
     onWorkflowChange,
     resetView,
     workflow,
-    parseWorkflow
+    (json) => parseWorkflow(json, comfyUrl)
   );
   const { connections } = useConnectionRenderer(workflow, renderNodes);
 
@@ -33,6 +33,89 @@ export interface NodeInfo {
   input_types: Record<string, string>;
   output_types: string[];
   color: string;
+  display_name?: string;
+  inputs?: InputSpec[];
+  outputs?: OutputSpec[];
+  output_node?: boolean;
+}
+export interface InputSpec {
+  name: string;
+  type: string;
+  required: boolean;
+  default?: any;
+  min?: number;
+  max?: number;
+  step?: number;
+  multiline?: boolean;
+  choices?: any[];
+  tooltip?: string;
+  forceInput?: boolean;
+  controlAfterGenerate?: boolean;
+}
+export interface OutputSpec {
+  name: string;
+  type: string;
+  isList: boolean;
+}
+export interface NodeSchema {
+  classType: string;
+  displayName: string;
+  description: string;
+  category: string;
+  inputs: InputSpec[];
+  outputs: OutputSpec[];
+  outputNode: boolean;
+}
+export interface WorkflowDiagnostic {
+  severity: "error" | "warning";
+  kind: string;
+  input?: string;
+  message: string;
+}
+export interface NodeDiagnostics {
+  nodeId: string;
+  classType: string;
+  diagnostics: WorkflowDiagnostic[];
+}
+export interface WorkflowValidation {
+  valid: boolean;
+  executionOrder: string[];
+  cycles: string[][];
+  nodes: NodeDiagnostics[];
+  workflow: WorkflowDiagnostic[];
+}
+export interface TemplateParameter {
+  bind: string[];
+  type: "string" | "int" | "float" | "boolean" | "seed" | "image" | "choice";
+  default?: any;
+  required?: boolean;
+  min?: number;
+  max?: number;
+  choices?: any[];
+  description?: string;
+}
+export interface WorkflowTemplate {
+  workflow: Record<string, any>;
+  parameters: Record<string, TemplateParameter>;
+}
+export interface TemplateSubmission {
+  promptId: string;
+  values: Record<string, any>;
+}
+export type ComfyUITlsMode =
+  | { mode: "system" }
+  | { mode: "customCa"; pem: string }
+  | { mode: "pinned"; sha256: string }
+  | { mode: "insecure"; confirmed: boolean };
+export type ComfyUIAuth =
+  | { type: "bearer"; credential: string }
+  | { type: "basic"; username: string; credential: string };
+export interface ComfyUIConnectionProfile {
+  url: string;
+  tls?: ComfyUITlsMode;
+  auth?: ComfyUIAuth | null;
+  connectTimeoutSecs?: number | null;
+  timeoutSecs?: number | null;
 }
 export interface NodeSize {
   width: number;
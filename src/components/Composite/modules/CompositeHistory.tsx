@@ -1,10 +1,10 @@
 import { useState, useEffect } from "react";
 import { useTranslation } from "react-i18next";
-import { invoke } from "@tauri-apps/api/core";
 import { save } from "@tauri-apps/plugin-dialog";
 import { useDesignStorage } from "../../Activity/hooks/useDesignStorage";
 import { useDesignContext } from "../../../context/DesignContext";
 import { CompositeHistoryProps } from "../types/composite.types";
+import { dataUrlToBytes, writeImageBytes } from "../../../lib/binaryIpc";
 
 export default function CompositeHistory({
   onImageSelected,
@@ -135,10 +135,10 @@ export default function CompositeHistory({
                           ],
                         });
                         if (filePath) {
-                          await invoke("write_image_file", {
-                            imageData: item.imageData,
-                            filePath: filePath,
-                          });
+                          await writeImageBytes(
+                            filePath,
+                            await dataUrlToBytes(item.imageData)
+                          );
                         }
                       } catch (error) {
                         alert(`${t("save_failed")}: ${error}`);
@@ -3,7 +3,6 @@ import { INFURA_GATEWAY } from "../../../lib/constants";
 import { useApp } from "../../../context/AppContext";
 import useFormat from "../hooks/useFormat";
 import { Link } from "react-router-dom";
-import { TemplateChoice } from "../types/format.types";
 export default function Format() {
   const { t } = useTranslation();
   const {
@@ -70,16 +69,11 @@ export default function Format() {
               <div className="absolute inset-0 flex items-center justify-center">
                 <div className="relative flex w-4/5 md:w-3/5 h-fit bg-black p-2 rounded-md border border-rosa text-white font-pixel text-xs flex-col break-all">
                   <div className="relative w-fit h-fit flex items-start justify-center text-left break-all">
-                    {getTemplateCategory(
-                      template.template_type as TemplateChoice["type"]
-                    )}
+                    {getTemplateCategory(template)}
                   </div>
                   <div className="relative w-full h-fit flex items-center justify-end text-right break-all">
                     <div className="relative w-fit h-fit flex">
-                      {"> " +
-                        getTemplateTypeName(
-                          template.template_type as TemplateChoice["type"]
-                        ).toUpperCase()}
+                      {"> " + getTemplateTypeName(template).toUpperCase()}
                     </div>
                   </div>
                 </div>
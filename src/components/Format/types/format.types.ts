@@ -1,6 +1,8 @@
+import { NestingSettings } from "../../Pattern/types/pattern.types";
+
 export interface TemplateChoice {
   name: string;
-  type: "hoodie" | "shirt" | "poster" | "sticker";
+  type: string;
   image: string;
 }
 
@@ -8,9 +10,29 @@ export interface GroupedTemplate {
   name: string;
   template_type: string;
   image: string;
+  materials: string[];
+  nesting?: Partial<NestingSettings> | null;
+  discovered: boolean;
   templates: Template[];
 }
 
+export interface TemplateType {
+  id: string;
+  name: string;
+  image: string;
+  aliases: string[];
+  materials: string[];
+  nesting?: Partial<NestingSettings> | null;
+  discovered?: boolean;
+}
+
+export interface TemplateTypeRegistry {
+  version: number;
+  discoverFromTags: boolean;
+  ignoredTags: string[];
+  types: TemplateType[];
+}
+
 export interface Template {
   price: string;
   childType: string;
@@ -25,6 +47,7 @@ export interface Template {
     ratio: number;
   };
   uri: string;
+  metadataError?: string | null;
   templateChoice?: TemplateChoice;
 }
 
@@ -45,6 +68,7 @@ export interface Child {
   };
   amount: number;
   childContract: string;
+  metadataError?: string | null;
   child: {
     uri: string;
     metadata: {
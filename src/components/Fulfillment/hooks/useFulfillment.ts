@@ -91,7 +91,7 @@ const useFulfillment = () => {
 
   const getFilteredMaterialsColors = () => {
     if (!selectedTemplate) return [];
-    return filterMaterialsColorsByTag(selectedTemplate.template_type);
+    return filterMaterialsColorsByTag(selectedTemplate.materials);
   };
 
   return {
@@ -1,6 +1,11 @@
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
-import { ChildData, Color, Material } from "../types/fulfillment.types";
+import {
+  CatalogSource,
+  ChildData,
+  Color,
+  Material,
+} from "../types/fulfillment.types";
 
 const useMaterialsAndColors = () => {
   const [materials, setMaterials] = useState<Material[]>([]);
@@ -14,6 +19,7 @@ const useMaterialsAndColors = () => {
       const childrenData = await invoke<{
         materials: ChildData[];
         colors: ChildData[];
+        source: CatalogSource;
       }>("fetch_children_materials_colors");
 
       const materialsData: Material[] = childrenData.materials.map((child) => ({
@@ -48,17 +54,14 @@ const useMaterialsAndColors = () => {
     fetchMaterialsColors();
   }, []);
 
-  const filterMaterialsColorsByTag = (templateType: string) => {
-    const targetTag =
-      templateType === "poster" || templateType === "sticker"
-        ? "print"
-        : "apparel";
+  const filterMaterialsColorsByTag = (categories: string[]) => {
+    const allowed = (tags?: string[]) =>
+      categories.length === 0 ||
+      categories.some((category) => tags?.includes(category));
 
     return {
-      materials: materials.filter((material) =>
-        material.tags?.includes(targetTag)
-      ),
-      colors: colors.filter((color) => color.tags?.includes(targetTag)),
+      materials: materials.filter((material) => allowed(material.tags)),
+      colors: colors.filter((color) => allowed(color.tags)),
     };
   };
 
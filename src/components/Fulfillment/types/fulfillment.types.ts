@@ -46,3 +46,72 @@ export interface ChildData {
   uri: string;
 }
 
+
+export type CatalogSource = "live" | "snapshot";
+
+export interface SubgraphConfig {
+  endpoint: string;
+  apiKeyName?: string | null;
+  pageSize: number;
+  timeoutSecs: number;
+  offline: boolean;
+}
+
+export type SubgraphError =
+  | { kind: "offline" }
+  | { kind: "credential"; message: string }
+  | { kind: "network"; message: string }
+  | { kind: "http"; status: number; body: string }
+  | {
+      kind: "graphql";
+      errors: { message: string; path?: (string | number)[] | null }[];
+    }
+  | { kind: "decode"; message: string };
+
+export interface CatalogSnapshotInfo {
+  version: number;
+  createdAt: string;
+  source: string;
+  templates: number;
+  childs: number;
+  bundled: boolean;
+  current: boolean;
+}
+
+export type CatalogSnapshotRef =
+  | { kind: "bundled" }
+  | { kind: "current" }
+  | { kind: "live" }
+  | { kind: "version"; version: number };
+
+export interface CatalogDiffEntry {
+  key: string;
+  title?: string | null;
+  price: string;
+}
+
+export interface CatalogChangedEntry {
+  key: string;
+  title?: string | null;
+  priceChange?: { from: string; to: string } | null;
+  fields: string[];
+}
+
+export interface CatalogEntityDiff {
+  added: CatalogDiffEntry[];
+  removed: CatalogDiffEntry[];
+  changed: CatalogChangedEntry[];
+}
+
+export interface CatalogDiff {
+  from: CatalogSnapshotInfo;
+  to: CatalogSnapshotInfo;
+  templates: CatalogEntityDiff;
+  childs: CatalogEntityDiff;
+}
+
+export interface CatalogRefreshResult {
+  saved: boolean;
+  snapshot: CatalogSnapshotInfo;
+  diff: CatalogDiff;
+}
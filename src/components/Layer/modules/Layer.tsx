@@ -8,6 +8,7 @@ import DesignCreationModal from "../../Design/modules/DesignCreationModal";
 import useLayer from "../hooks/useLayer";
 import { CreateDesignRequest, Design } from "../../Design/types/design.types";
 import { Template } from "../../Format/types/format.types";
+import { getGarmentType } from "../../Synth/utils/templateHelpers";
 
 export default function Layer() {
   const { t } = useTranslation();
@@ -24,25 +25,7 @@ export default function Layer() {
     setSelectedFront(null);
     setSelectedBack(null);
   }, [selectedTemplate]);
-  const getTemplateType = () => {
-    if (!selectedTemplate || !selectedTemplate.templates.length) return null;
-    const allTags = selectedTemplate.templates.flatMap(
-      (template) => template.metadata?.tags || []
-    );
-    for (const tag of allTags) {
-      const tagLower = tag.toLowerCase();
-      if (tagLower === "t-shirt" || tagLower === "shirt") return "shirt";
-      if (tagLower === "hoodie") return "hoodie";
-      if (tagLower === "poster") return "poster";
-      if (tagLower === "sticker") return "sticker";
-    }
-    return null;
-  };
-
-  const requiresBothSides = () => {
-    const type = getTemplateType();
-    return type === "shirt" || type === "hoodie";
-  };
+  const requiresBothSides = () => getGarmentType(selectedTemplate) !== null;
 
   const handleFrontClick = (layer: Template) => {
     setSelectedFront(layer);
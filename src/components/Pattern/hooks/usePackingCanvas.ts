@@ -1,8 +1,9 @@
-import { useState, useRef, useEffect, useCallback } from "react";
+import { useState, useRef, useEffect, useCallback, useMemo } from "react";
 import { useTranslation } from "react-i18next";
 import { usePatternNesting } from "./usePatternNesting";
 import { useLiveSparrowVisualization } from "./useLiveSparrowVisualization";
 import { useDesignContext } from "../../../context/DesignContext";
+import { useApp } from "../../../context/AppContext";
 import {
   CanvasPanel,
   NestingSettings,
@@ -19,9 +20,14 @@ export const usePackingCanvas = (selectedPieces: PatternPiece[]) => {
   const [selectedPanelId, setSelectedPanelId] = useState<string | null>(null);
   const baseCanvasWidth = 800;
   const baseCanvasHeight = 600;
+  const { selectedTemplate } = useApp();
 
+  const defaultNestingSettings = useMemo<NestingSettings>(
+    () => ({ ...DEFAULT_NESTING_SETTINGS, ...selectedTemplate?.nesting }),
+    [selectedTemplate]
+  );
   const [nestingSettings, setNestingSettings] = useState<NestingSettings>(
-    DEFAULT_NESTING_SETTINGS
+    defaultNestingSettings
   );
   const canvasWidth = baseCanvasWidth;
   const [canvasHeight, setCanvasHeight] = useState(baseCanvasHeight);
@@ -60,12 +66,12 @@ export const usePackingCanvas = (selectedPieces: PatternPiece[]) => {
       );
       if (savedSettings) {
         setNestingSettings({
-          ...DEFAULT_NESTING_SETTINGS,
+          ...defaultNestingSettings,
           ...savedSettings,
         });
       }
     } catch {}
-  }, [getItem]);
+  }, [getItem, defaultNestingSettings]);
 
   const saveSettings = useCallback(async () => {
     try {
@@ -80,11 +86,11 @@ export const usePackingCanvas = (selectedPieces: PatternPiece[]) => {
   useEffect(() => {
     if (
       JSON.stringify(nestingSettings) !==
-      JSON.stringify(DEFAULT_NESTING_SETTINGS)
+      JSON.stringify(defaultNestingSettings)
     ) {
       saveSettings();
     }
-  }, [nestingSettings, saveSettings]);
+  }, [nestingSettings, defaultNestingSettings, saveSettings]);
 
   const handleNestClick = useCallback(async () => {
     if (!selectedPieces.length) {
@@ -464,7 +470,7 @@ export const usePackingCanvas = (selectedPieces: PatternPiece[]) => {
       }
 
       if (settings) {
-        setNestingSettings({ ...DEFAULT_NESTING_SETTINGS, ...settings });
+        setNestingSettings({ ...defaultNestingSettings, ...settings });
       }
 
       if (savedLiveSvgContent || savedSavedSvgContent) {
@@ -477,7 +483,7 @@ export const usePackingCanvas = (selectedPieces: PatternPiece[]) => {
     } catch (error) {
       console.error("Failed to load pattern state:", error);
     }
-  }, [currentDesign, getItem]);
+  }, [currentDesign, getItem, defaultNestingSettings]);
 
   useEffect(() => {
     if (currentDesign) {
@@ -502,6 +508,7 @@ export const usePackingCanvas = (selectedPieces: PatternPiece[]) => {
     baseCanvasHeight,
     nestingSettings,
     setNestingSettings,
+    defaultNestingSettings,
     canvasWidth,
     canvasHeight,
     setCanvasHeight,
@@ -2,7 +2,7 @@ import { useState, useCallback } from "react";
 import { useDesignContext } from "../../../context/DesignContext";
 import { useApp } from "../../../context/AppContext";
 import { usePatternExport } from "../../Synth/hooks/usePatternExport";
-import { getCurrentTemplate } from "../../Synth/utils/templateHelpers";
+import { getCurrentTemplate, getGarmentType } from "../../Synth/utils/templateHelpers";
 import { useDesignStorage } from "../../Activity/hooks/useDesignStorage";
 import { INFURA_GATEWAY } from "../../../lib/constants";
 
@@ -95,10 +95,7 @@ export const usePattern = () => {
   );
 
   const isApplicableTemplate = useCallback(() => {
-    return (
-      selectedTemplate?.template_type === "shirt" ||
-      selectedTemplate?.template_type === "hoodie"
-    );
+    return getGarmentType(selectedTemplate) !== null;
   }, [selectedTemplate]);
 
   const handleExportPattern = useCallback(async () => {
@@ -6,8 +6,10 @@ import {
   GarmentSize,
   PatternPiece,
   CustomDimensions,
+  TileOptions,
 } from "../types/pattern.types";
 import {
+  DEFAULT_TILE_OPTIONS,
   HOODIE_FRONT_PANEL_DIMENSIONS,
   SHIRT_FRONT_PANEL_DIMENSIONS,
 } from "../../../lib/constants";
@@ -301,7 +303,9 @@ export const useSvgExport = () => {
       garmentType: "tshirt" | "hoodie",
       isManualMode: boolean | undefined,
       manualPieces?: any[],
-      abortSignal?: AbortSignal
+      abortSignal?: AbortSignal,
+      tileOptions: TileOptions = DEFAULT_TILE_OPTIONS,
+      calibration: boolean = true
     ): Promise<{ success: boolean; filePath?: string; error?: string }> => {
       try {
         if (abortSignal?.aborted) {
@@ -379,10 +383,14 @@ export const useSvgExport = () => {
         );
 
         const result = await invoke<string>("crop_svg", {
-          svgString,
-          canvasWidthPt,
-          canvasHeightPt,
-          outPath: filePath,
+          request: {
+            svgString,
+            canvasWidthPt,
+            canvasHeightPt,
+            outPath: filePath,
+            options: tileOptions,
+            calibration,
+          },
         });
 
         return {
@@ -1,8 +1,15 @@
 import { FunctionComponent, useState, useCallback, useMemo } from "react";
 import { useTranslation } from "react-i18next";
-import { GarmentSize, ExportDialogProps } from "../types/pattern.types";
+import { GarmentSize, ExportDialogProps, PaperSize, TileOptions } from "../types/pattern.types";
 import { useSvgExport } from "../hooks/useSvgExport";
-import { HOODIE_FRONT_PANEL_DIMENSIONS, SHIRT_FRONT_PANEL_DIMENSIONS } from "../../../lib/constants";
+import {
+  DEFAULT_TILE_OPTIONS,
+  HOODIE_FRONT_PANEL_DIMENSIONS,
+  PT_PER_MM,
+  SHIRT_FRONT_PANEL_DIMENSIONS,
+} from "../../../lib/constants";
+
+const PAPER_TYPES: PaperSize["type"][] = ["a4", "letter", "a3", "legal"];
 
 export const ExportDialog: FunctionComponent<ExportDialogProps> = ({
   isOpen,
@@ -22,6 +29,28 @@ export const ExportDialog: FunctionComponent<ExportDialogProps> = ({
   const [customWidth, setCustomWidth] = useState<string>("34.8");
   const [customHeight, setCustomHeight] = useState<string>("65.8");
   const [abortController, setAbortController] = useState<AbortController | null>(null);
+  const [paper, setPaper] = useState<PaperSize>(DEFAULT_TILE_OPTIONS.paper);
+  const [landscape, setLandscape] = useState(DEFAULT_TILE_OPTIONS.landscape);
+  const [overlapMm, setOverlapMm] = useState<string>(
+    (DEFAULT_TILE_OPTIONS.overlapPt / PT_PER_MM).toFixed(1)
+  );
+  const [alignmentMarks, setAlignmentMarks] = useState(DEFAULT_TILE_OPTIONS.alignmentMarks);
+  const [labels, setLabels] = useState(DEFAULT_TILE_OPTIONS.labels);
+  const [coverPage, setCoverPage] = useState(DEFAULT_TILE_OPTIONS.coverPage);
+  const [calibration, setCalibration] = useState(true);
+
+  const tileOptions = useMemo<TileOptions>(() => {
+    const overlap = parseFloat(overlapMm);
+    return {
+      ...DEFAULT_TILE_OPTIONS,
+      paper,
+      landscape,
+      overlapPt: Number.isFinite(overlap) && overlap >= 0 ? overlap * PT_PER_MM : DEFAULT_TILE_OPTIONS.overlapPt,
+      alignmentMarks,
+      labels,
+      coverPage,
+    };
+  }, [paper, landscape, overlapMm, alignmentMarks, labels, coverPage]);
 
   const garmentType = useMemo(() => {
     return patternPieces?.[0]?.garmentType || "hoodie";
@@ -63,7 +92,9 @@ export const ExportDialog: FunctionComponent<ExportDialogProps> = ({
         garmentType,
         isManualMode,
         manualPieces,
-        controller.signal
+        controller.signal,
+        tileOptions,
+        calibration
       );
 
       if (controller.signal.aborted) {
@@ -118,6 +149,8 @@ export const ExportDialog: FunctionComponent<ExportDialogProps> = ({
     garmentType,
     isManualMode,
     manualPieces,
+    tileOptions,
+    calibration,
   ]);
 
   if (!isOpen) return null;
@@ -200,6 +233,62 @@ export const ExportDialog: FunctionComponent<ExportDialogProps> = ({
               </div>
             </div>
           )}
+
+          <div className="space-y-3">
+            <label className="block text-white font-agency text-xs mb-2">
+              {t("print_layout")}
+            </label>
+            <div className="grid grid-cols-2 gap-3">
+              <div>
+                <label className="block text-crema font-agency text-xs mb-1">
+                  {t("paper_size")}
+                </label>
+                <select
+                  value={paper.type}
+                  onChange={(e) => setPaper({ type: e.target.value } as PaperSize)}
+                  className="w-full appearance-none bg-black text-white border border-crema rounded px-3 py-2 font-agency text-xs"
+                  style={{ backgroundImage: 'none' }}
+                >
+                  {PAPER_TYPES.map((type) => (
+                    <option key={type} value={type}>
+                      {t(`paper_${type}`)}
+                    </option>
+                  ))}
+                </select>
+              </div>
+              <div>
+                <label className="block text-crema font-agency text-xs mb-1">
+                  {t("tile_overlap_mm")}
+                </label>
+                <input
+                  type="number"
+                  value={overlapMm}
+                  onChange={(e) => setOverlapMm(e.target.value)}
+                  min="0"
+                  max="50"
+                  step="0.5"
+                  className="w-full bg-black text-white border border-crema rounded px-3 py-2 font-agency text-xs"
+                />
+              </div>
+            </div>
+            {[
+              { key: "landscape", checked: landscape, onChange: setLandscape },
+              { key: "alignment_marks", checked: alignmentMarks, onChange: setAlignmentMarks },
+              { key: "tile_labels", checked: labels, onChange: setLabels },
+              { key: "assembly_cover_page", checked: coverPage, onChange: setCoverPage },
+              { key: "calibration_marks", checked: calibration, onChange: setCalibration },
+            ].map(({ key, checked, onChange }) => (
+              <label key={key} className="flex items-center gap-2 cursor-pointer">
+                <input
+                  type="checkbox"
+                  checked={checked}
+                  onChange={(e) => onChange(e.target.checked)}
+                  className="w-3 h-3 rounded"
+                />
+                <span className="text-white font-agency text-xs">{t(key)}</span>
+              </label>
+            ))}
+          </div>
         </div>
 
         {exportStatus && (
@@ -11,6 +11,7 @@ export const NestingSettingsPanel: FunctionComponent<NestingSettingsProps> = ({
   settings,
   onSettingsChange,
   disabled,
+  defaults = DEFAULT_NESTING_SETTINGS,
 }) => {
   const { t } = useTranslation();
   const [localSettings, setLocalSettings] = useState<NestingSettings>(settings);
@@ -33,8 +34,8 @@ export const NestingSettingsPanel: FunctionComponent<NestingSettingsProps> = ({
     onSettingsChange(newSettings);
   };
   const resetToDefaults = () => {
-    setLocalSettings(DEFAULT_NESTING_SETTINGS);
-    onSettingsChange(DEFAULT_NESTING_SETTINGS);
+    setLocalSettings(defaults);
+    onSettingsChange(defaults);
   };
   const currentRotationPreset = ROTATION_PRESETS.find(
     (preset) =>
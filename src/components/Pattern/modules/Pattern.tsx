@@ -7,6 +7,7 @@ import { usePattern } from "../hooks/usePattern";
 import { usePackingCanvas } from "../hooks/usePackingCanvas";
 import { PatternPiece } from "../types/pattern.types";
 import { useApp } from "../../../context/AppContext";
+import { getGarmentType } from "../../Synth/utils/templateHelpers";
 
 const Pattern: FunctionComponent = () => {
   const { t } = useTranslation();
@@ -26,19 +27,13 @@ const Pattern: FunctionComponent = () => {
     selectedTemplate,
   } = usePattern();
 
-  const { nestingSettings, setNestingSettings, isNesting, isSparrowRunning } =
-    usePackingCanvas(loadedPatterns);
-
-  const getGarmentType = (): "tshirt" | "hoodie" | null => {
-    if (!selectedLayer?.front?.metadata?.tags) return null;
-    const tags = selectedLayer.front.metadata.tags;
-    for (const tag of tags) {
-      const tagLower = tag.toLowerCase();
-      if (tagLower === "t-shirt" || tagLower === "shirt") return "tshirt";
-      if (tagLower === "hoodie") return "hoodie";
-    }
-    return null;
-  };
+  const {
+    nestingSettings,
+    setNestingSettings,
+    defaultNestingSettings,
+    isNesting,
+    isSparrowRunning,
+  } = usePackingCanvas(loadedPatterns);
 
   const loadSVGDimensionsFromData = async (
     svgData: string
@@ -135,11 +130,11 @@ const Pattern: FunctionComponent = () => {
   };
 
   useEffect(() => {
-    const garmentType = getGarmentType();
+    const garmentType = getGarmentType(selectedTemplate);
     if (garmentType && selectedLayer) {
       loadActualPatterns(garmentType);
     }
-  }, [selectedLayer]);
+  }, [selectedLayer, selectedTemplate]);
 
   if (!currentDesign) {
     return (
@@ -170,6 +165,7 @@ const Pattern: FunctionComponent = () => {
             <NestingSettingsPanel
               settings={nestingSettings}
               onSettingsChange={setNestingSettings}
+              defaults={defaultNestingSettings}
               disabled={isNesting || isSparrowRunning}
             />
           </div>
@@ -2,22 +2,13 @@ import { useState, useEffect, FunctionComponent } from "react";
 import { useTranslation } from "react-i18next";
 import { PatternPiece } from "../types/pattern.types";
 import { useApp } from "../../../context/AppContext";
+import { getGarmentType } from "../../Synth/utils/templateHelpers";
 
 export const PatternLibrary: FunctionComponent = () => {
   const { t } = useTranslation();
-  const { selectedLayer } = useApp();
+  const { selectedLayer, selectedTemplate } = useApp();
   const [loadedPatterns, setLoadedPatterns] = useState<PatternPiece[]>([]);
   const [isLoading, setIsLoading] = useState(false);
-  const getGarmentType = (): "tshirt" | "hoodie" | null => {
-    if (!selectedLayer?.front?.metadata?.tags) return null;
-    const tags = selectedLayer.front.metadata.tags;
-    for (const tag of tags) {
-      const tagLower = tag.toLowerCase();
-      if (tagLower === "t-shirt" || tagLower === "shirt") return "tshirt";
-      if (tagLower === "hoodie") return "hoodie";
-    }
-    return null;
-  };
 
   const loadSVGDimensionsFromData = async (
     svgData: string
@@ -119,13 +110,13 @@ export const PatternLibrary: FunctionComponent = () => {
   };
 
   useEffect(() => {
-    const garmentType = getGarmentType();
+    const garmentType = getGarmentType(selectedTemplate);
     if (garmentType && selectedLayer) {
       loadActualPatterns(garmentType);
     }
-  }, [selectedLayer]);
+  }, [selectedLayer, selectedTemplate]);
 
-  const garmentType = getGarmentType();
+  const garmentType = getGarmentType(selectedTemplate);
 
   return (
     <div className="bg-black rounded p-2">
@@ -136,7 +127,9 @@ export const PatternLibrary: FunctionComponent = () => {
         <>
           <div className="w-full p-4 rounded border text-center bg-black text-white border-crema">
             <div className="text-xs font-agency">
-              {garmentType === "tshirt" ? t("shirt") : t("hoodie")}{" "}
+              {t(selectedTemplate!.template_type, {
+                defaultValue: selectedTemplate!.name,
+              })}{" "}
               {t("pattern")}
             </div>
             <div className="text-xs mt-1 text-crema">
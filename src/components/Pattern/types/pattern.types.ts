@@ -32,10 +32,28 @@ export interface ExportDialogProps {
   manualPieces?: CanvasPanel[];
 }
 
+export type PaperSize =
+  | { type: "a4" }
+  | { type: "letter" }
+  | { type: "a3" }
+  | { type: "legal" }
+  | { type: "custom"; widthPt: number; heightPt: number };
+
+export interface TileOptions {
+  paper: PaperSize;
+  landscape: boolean;
+  overlapPt: number;
+  marginPt: number;
+  alignmentMarks: boolean;
+  labels: boolean;
+  coverPage: boolean;
+}
+
 export interface NestingSettingsProps {
   settings: NestingSettings;
   onSettingsChange: (settings: NestingSettings) => void;
   disabled: boolean;
+  defaults?: NestingSettings;
 }
 
 export interface PackingCanvasProps {
@@ -4,8 +4,20 @@ import { fetch } from "@tauri-apps/plugin-http";
 import { invoke } from "@tauri-apps/api/core";
 import { useDesignStorage } from "../../Activity/hooks/useDesignStorage";
 import { useDesignContext } from "../../../context/DesignContext";
-import { UseGeneratorProps } from "../types/synth.types";
+import {
+  GeneratedImage,
+  GenerationJobStatus,
+  GenerationProviderConfig,
+  GenerationRequest,
+  UseGeneratorProps,
+} from "../types/synth.types";
 import { useFileStorage } from "../../Activity/hooks/useFileStorage";
+import { dataUrlToBytes, uploadComfyImage } from "../../../lib/binaryIpc";
+
+const REPLICATE_CREDENTIAL = "replicate";
+const REPLICATE_PROVIDER = "replicate";
+const COMFYUI_PROVIDER = "comfyui";
+const GENERATION_POLL_MS = 1000;
 
 const useGenerator = ({
   mode = "synth",
@@ -28,6 +40,9 @@ const useGenerator = ({
     replicate: "",
     comfy: "",
   });
+  const [storedKeys, setStoredKeys] = useState<{ replicate: boolean }>({
+    replicate: false,
+  });
   const [selectedModel, setSelectedModel] = useState<string>("");
   const [showModelDropdown, setShowModelDropdown] = useState<boolean>(false);
   const [selectedLora, setSelectedLora] = useState<string>("");
@@ -35,6 +50,9 @@ const useGenerator = ({
   const [useCanvasAsInput, setUseCanvasAsInput] = useState<boolean>(true);
   const [overwriteCanvas, setOverwriteCanvas] = useState<boolean>(false);
   const [isGenerating, setIsGenerating] = useState<boolean>(false);
+  const [generationProgress, setGenerationProgress] = useState<
+    number | null
+  >(null);
   const [generationController, setGenerationController] =
     useState<AbortController | null>(null);
   const [generationHistory, setGenerationHistory] = useState<
@@ -114,18 +132,47 @@ const useGenerator = ({
     promptNodes: [],
     hasImageInput: false,
   });
+  const restoreApiKeys = async (saved: any) => {
+    const keys = { openai: "", replicate: "", comfy: "", ...(saved || {}) };
+    if (keys.replicate) {
+      await invoke("credentials_set", {
+        name: REPLICATE_CREDENTIAL,
+        secret: keys.replicate,
+        provider: "replicate",
+      }).catch(() => {});
+    }
+    const stored = await invoke<Array<{ name: string }>>(
+      "credentials_list"
+    ).catch(() => []);
+    setStoredKeys({
+      replicate: stored.some((c) => c.name === REPLICATE_CREDENTIAL),
+    });
+    keys.replicate = "";
+    return keys;
+  };
+  const addToLibrary = (
+    item: { imageData: string; prompt: string; model: string; provider: string },
+    extra: { path?: string; workflow?: any; settings?: any } = {}
+  ) => {
+    const info = {
+      provider: item.provider,
+      model: item.model,
+      prompt: item.prompt,
+      workflow: extra.workflow,
+      tags: [mode],
+      metadata: extra.settings,
+    };
+    const request = extra.path
+      ? invoke("assets_add_file", { path: extra.path, info })
+      : invoke("assets_add", { data: item.imageData, info });
+    request.catch(() => {});
+  };
   const loadSettings = useCallback(async () => {
     try {
       const savedProvider = (await getItem("aiProvider")) as string;
       setAiProvider(savedProvider || "openai");
       const savedApiKeys = (await getItemFile("apiKeys", "global")) as any;
-      setApiKeys(
-        savedApiKeys || {
-          openai: "",
-          replicate: "",
-          comfy: "",
-        }
-      );
+      setApiKeys(await restoreApiKeys(savedApiKeys));
       const historyKey =
         mode === "composite" ? "aiCompositeHistory" : "aiGenerationHistory";
       const savedHistory = (await getItem(historyKey)) as [];
@@ -236,10 +283,27 @@ const useGenerator = ({
   }, [aiProvider, mode, setItem]);
   useEffect(() => {
     const timeoutId = setTimeout(() => {
-      setItemFile("apiKeys", apiKeys, "global").catch(() => {});
+      setItemFile("apiKeys", { ...apiKeys, replicate: "" }, "global").catch(
+        () => {}
+      );
     }, 300);
     return () => clearTimeout(timeoutId);
   }, [apiKeys, setItem]);
+  useEffect(() => {
+    if (!apiKeys.replicate) {
+      return;
+    }
+    const timeoutId = setTimeout(() => {
+      invoke("credentials_set", {
+        name: REPLICATE_CREDENTIAL,
+        secret: apiKeys.replicate,
+        provider: "replicate",
+      })
+        .then(() => setStoredKeys({ replicate: true }))
+        .catch(() => {});
+    }, 500);
+    return () => clearTimeout(timeoutId);
+  }, [apiKeys.replicate]);
   useEffect(() => {
     const timeoutId = setTimeout(() => {
       const promptKey =
@@ -335,9 +399,12 @@ const useGenerator = ({
     };
     setApiKeys(newApiKeys);
     try {
-      await setItemFile("apiKeys", newApiKeys, "global");
+      await setItemFile("apiKeys", { ...newApiKeys, replicate: "" }, "global");
     } catch (error) {}
   };
+  const hasApiKey = (provider: string) =>
+    !!apiKeys[provider as keyof typeof apiKeys] ||
+    (provider === "replicate" && storedKeys.replicate);
   const handleComfyUrlChange = async (value: string) => {
     const newSettings = { ...comfySettings, url: value };
     setComfySettings(newSettings);
@@ -434,13 +501,7 @@ const useGenerator = ({
   const loadApiKeys = async () => {
     try {
       const savedKeys = (await getItemFile("apiKeys", "global")) as any;
-      setApiKeys(
-        savedKeys || {
-          openai: "",
-          replicate: "",
-          comfy: "",
-        }
-      );
+      setApiKeys(await restoreApiKeys(savedKeys));
     } catch (error) {}
   };
   const reloadSettings = useCallback(async () => {
@@ -631,11 +692,52 @@ const useGenerator = ({
       setIsGenerating(false);
     }
   };
+  const runGenerationJob = async (
+    providerId: string,
+    config: GenerationProviderConfig,
+    request: GenerationRequest,
+    signal: AbortSignal
+  ): Promise<GeneratedImage[]> => {
+    await invoke("generation_configure_provider", { providerId, config });
+    const jobId = await invoke<string>("generation_submit", {
+      providerId,
+      request,
+    });
+    const cancelJob = () => {
+      invoke("generation_cancel", { jobId }).catch(() => {});
+    };
+    signal.addEventListener("abort", cancelJob);
+    try {
+      while (true) {
+        const status = await invoke<GenerationJobStatus>("generation_status", {
+          jobId,
+        });
+        setGenerationProgress(status.progress ?? null);
+        if (status.state === "succeeded") {
+          return await invoke<GeneratedImage[]>("generation_outputs", {
+            jobId,
+          });
+        }
+        if (status.state === "failed") {
+          throw new Error(status.error || status.message || "Generation failed");
+        }
+        if (signal.aborted) {
+          throw new DOMException("Generation cancelled", "AbortError");
+        }
+        if (status.state === "canceled") {
+          throw new Error("Generation cancelled");
+        }
+        await new Promise((resolve) =>
+          setTimeout(resolve, GENERATION_POLL_MS)
+        );
+      }
+    } finally {
+      signal.removeEventListener("abort", cancelJob);
+      setGenerationProgress(null);
+    }
+  };
   const generateImage = async () => {
-    if (
-      aiProvider !== "comfy" &&
-      !apiKeys?.[aiProvider as keyof typeof apiKeys]
-    ) {
+    if (aiProvider !== "comfy" && !hasApiKey(aiProvider)) {
       alert(t("enter_api_key", { provider: aiProvider.toUpperCase() }));
       return;
     }
@@ -781,6 +883,7 @@ const useGenerator = ({
           timestamp: new Date(),
           settings: { ...openAiSettings, useCanvasAsInput, overwriteCanvas },
         };
+        addToLibrary(historyItem, { settings: historyItem.settings });
         const newHistory = [historyItem, ...generationHistory].slice(0, 50);
         setGenerationHistory(newHistory);
         const storageKey =
@@ -827,50 +930,16 @@ const useGenerator = ({
             input.image = canvasDataURL;
           }
         }
-        const createPredictionResponse = await invoke(
-          "replicate_create_prediction",
-          {
-            modelName: replicateModel,
-            apiKey: apiKeys?.replicate || "",
-            input,
-          }
+        const [image] = await runGenerationJob(
+          REPLICATE_PROVIDER,
+          { type: "replicate", credential: REPLICATE_CREDENTIAL },
+          { model: replicateModel, prompt: prompt.trim(), input },
+          controller.signal
         );
-        const predictionId = (createPredictionResponse as any).id;
-        let prediction: any;
-        let attempts = 0;
-        const maxAttempts = 60;
-        while (attempts < maxAttempts) {
-          if (controller.signal.aborted) {
-            throw new Error("Generation cancelled");
-          }
-          await new Promise((resolve) => setTimeout(resolve, 1000));
-          prediction = await invoke("replicate_get_prediction", {
-            predictionId,
-            apiKey: apiKeys?.replicate || "",
-          });
-          if ((prediction as any).status === "succeeded") {
-            break;
-          } else if ((prediction as any).status === "failed") {
-            throw new Error(
-              (prediction as any).error || "Replicate generation failed"
-            );
-          }
-          attempts++;
-        }
-        if (attempts >= maxAttempts) {
-          throw new Error("Generation timed out");
-        }
-        const imageUrl = Array.isArray((prediction as any).output)
-          ? (prediction as any).output.find(
-              (url: string) => typeof url === "string" && url.startsWith("http")
-            )
-          : (prediction as any).output;
-        if (!imageUrl) {
+        if (!image) {
           throw new Error("No output image from Replicate");
         }
-        const imageDataUrl = await invoke("download_image_as_base64", {
-          imageUrl,
-        });
+        const imageDataUrl = `data:${image.mimeType};base64,${image.data}`;
         const historyItem = {
           id: `gen-${Date.now()}`,
           imageData: imageDataUrl as string,
@@ -880,6 +949,7 @@ const useGenerator = ({
           timestamp: new Date(),
           settings: { ...replicateSettings, useCanvasAsInput, overwriteCanvas },
         };
+        addToLibrary(historyItem, { settings: historyItem.settings });
         const newHistory = [historyItem, ...generationHistory].slice(0, 50);
         setGenerationHistory(newHistory);
         const storageKey =
@@ -921,10 +991,10 @@ const useGenerator = ({
           }
 
           if (canvasDataURL) {
-            const uploadedFilename = await invoke("comfyui_upload_image", {
-              comfyUrl: comfySettings.url,
-              imageData: canvasDataURL,
-            });
+            const uploadedFilename = await uploadComfyImage(
+              comfySettings.url,
+              await dataUrlToBytes(canvasDataURL)
+            );
 
             for (const nodeId in workflowToExecute) {
               if (nodeId.startsWith("#")) continue;
@@ -956,96 +1026,47 @@ const useGenerator = ({
           }
         }
 
-        const clientId = `client_${Date.now()}_${Math.random()}`;
-        const promptId = await invoke("comfyui_execute_workflow", {
-          comfyUrl: comfySettings.url,
-          workflowJson: cleanedWorkflow,
-          clientId,
+        const [image] = await runGenerationJob(
+          COMFYUI_PROVIDER,
+          { type: "comfyui", url: comfySettings.url },
+          { prompt: prompt.trim(), input: cleanedWorkflow },
+          controller.signal
+        );
+        if (!image) {
+          throw new Error("No output image from workflow");
+        }
+        const imageDataUrl = `data:${image.mimeType};base64,${image.data}`;
+        const historyItem = {
+          id: `gen-${Date.now()}`,
+          imageData: imageDataUrl,
+          prompt: prompt.trim(),
+          model: "custom-workflow",
+          provider: aiProvider,
+          timestamp: new Date(),
+          settings: { workflowFileName: comfySettings.workflowFileName, useCanvasAsInput, overwriteCanvas },
+        };
+        addToLibrary(historyItem, {
+          workflow: comfySettings.workflowJson,
+          settings: historyItem.settings,
         });
 
-        let history: any = null;
-        let attempts = 0;
-        const maxAttempts = 120;
-
-        while (attempts < maxAttempts) {
-          if (controller.signal.aborted) {
-            throw new Error("Generation cancelled");
-          }
-
-          await new Promise((resolve) => setTimeout(resolve, 1000));
-
-          const historyDataRaw = await invoke("comfyui_get_history", {
-            comfyUrl: comfySettings.url,
-            promptId,
-          });
-          const historyData = historyDataRaw as Record<string, any>;
-
-          if (historyData && historyData[promptId as string]) {
-            history = historyData[promptId as string];
-            break;
-          }
-
-          attempts++;
-        }
+        const newHistory = [historyItem, ...generationHistory].slice(0, 50);
+        setGenerationHistory(newHistory);
+        const storageKey = mode === "composite" ? "aiCompositeHistory" : "aiGenerationHistory";
+        await setItem(storageKey, newHistory);
 
-        if (!history) {
-          throw new Error("Workflow execution timed out");
+        if (currentDesign) {
+          await refreshDesigns();
         }
 
-
-        let outputFilename: string | null = null;
-
-        if (history.outputs) {
-          for (const nodeId in history.outputs) {
-            const nodeOutput = history.outputs[nodeId];
-
-            if (nodeOutput.images && nodeOutput.images.length > 0) {
-              const imageInfo = nodeOutput.images[0];
-              outputFilename = imageInfo.filename;
-              const subfolder = imageInfo.subfolder ? imageInfo.subfolder.replace(/\\/g, "/") : null;
-
-              const imageDataUrl = await invoke("comfyui_download_image", {
-                comfyUrl: comfySettings.url,
-                filename: outputFilename,
-                subfolder: subfolder || "",
-                imageType: "output",
-              });
-
-              const historyItem = {
-                id: `gen-${Date.now()}`,
-                imageData: imageDataUrl as string,
-                prompt: prompt.trim(),
-                model: "custom-workflow",
-                provider: aiProvider,
-                timestamp: new Date(),
-                settings: { workflowFileName: comfySettings.workflowFileName, useCanvasAsInput, overwriteCanvas },
-              };
-
-              const newHistory = [historyItem, ...generationHistory].slice(0, 50);
-              setGenerationHistory(newHistory);
-              const storageKey = mode === "composite" ? "aiCompositeHistory" : "aiGenerationHistory";
-              await setItem(storageKey, newHistory);
-
-              if (currentDesign) {
-                await refreshDesigns();
-              }
-
-              if (mode === "composite") {
-                window.dispatchEvent(new Event("compositeImageGenerated"));
-                if (onImageGenerated) {
-                  onImageGenerated(imageDataUrl as string);
-                }
-              } else {
-                window.dispatchEvent(new Event("synthImageGenerated"));
-                addImageToCanvas(imageDataUrl as string, overwriteCanvas);
-              }
-              break;
-            }
+        if (mode === "composite") {
+          window.dispatchEvent(new Event("compositeImageGenerated"));
+          if (onImageGenerated) {
+            onImageGenerated(imageDataUrl);
           }
-        }
-
-        if (!outputFilename) {
-          throw new Error("No output image from workflow");
+        } else {
+          window.dispatchEvent(new Event("synthImageGenerated"));
+          addImageToCanvas(imageDataUrl, overwriteCanvas);
         }
       }
     } catch (error: any) {
@@ -1138,6 +1159,8 @@ const useGenerator = ({
     handleProviderChange,
     showApiKey,
     apiKeys,
+    storedKeys,
+    hasApiKey,
     handleApiKeyChange,
     setShowApiKey,
     handleComfyUrlChange,
@@ -1157,6 +1180,7 @@ const useGenerator = ({
     setOverwriteCanvas,
     overwriteCanvas,
     isGenerating,
+    generationProgress,
     generateImage,
     cancelGeneration,
     showSizeDropdown,
@@ -1,10 +1,10 @@
 import { useState, useEffect } from "react";
 import { useTranslation } from "react-i18next";
-import { invoke } from "@tauri-apps/api/core";
 import { save } from "@tauri-apps/plugin-dialog";
 import { addImageToCanvas } from "../utils/addImageToCanvas";
 import { useDesignStorage } from "../../Activity/hooks/useDesignStorage";
 import { useDesignContext } from "../../../context/DesignContext";
+import { dataUrlToBytes, writeImageBytes } from "../../../lib/binaryIpc";
 export default function GenerationHistory() {
   const { t } = useTranslation();
   const { getItem, setItem } = useDesignStorage();
@@ -126,10 +126,10 @@ export default function GenerationHistory() {
                           ],
                         });
                         if (filePath) {
-                          await invoke("write_image_file", {
-                            imageData: item.imageData,
-                            filePath: filePath,
-                          });
+                          await writeImageBytes(
+                            filePath,
+                            await dataUrlToBytes(item.imageData)
+                          );
                         }
                       } catch (error) {
                         alert(`${t("save_failed")}: ${error}`);
@@ -28,6 +28,8 @@ export default function Generator({
     handleProviderChange,
     showApiKey,
     apiKeys,
+    storedKeys,
+    hasApiKey,
     handleApiKeyChange,
     setShowApiKey,
     handleComfyUrlChange,
@@ -46,6 +48,7 @@ export default function Generator({
     setOverwriteCanvas,
     overwriteCanvas,
     isGenerating,
+    generationProgress,
     generateImage,
     cancelGeneration,
     showSizeDropdown,
@@ -360,7 +363,11 @@ export default function Generator({
                     type={showApiKey ? "text" : "password"}
                     value={apiKeys[aiProvider as keyof typeof apiKeys]}
                     onChange={(e) => handleApiKeyChange(e.target.value)}
-                    placeholder={`Enter ${aiProvider.toUpperCase()} API key`}
+                    placeholder={
+                      storedKeys[aiProvider as keyof typeof storedKeys]
+                        ? t("api_key_stored")
+                        : `Enter ${aiProvider.toUpperCase()} API key`
+                    }
                     className="w-full px-3 py-2 pr-10 bg-oscuro border border-oscurazul text-white rounded font-agency text-xs"
                   />
                   <div
@@ -622,7 +629,7 @@ export default function Generator({
                   disabled={
                     aiProvider === "comfy"
                       ? !comfySettings.url || !comfySettings.workflowJson
-                      : !apiKeys[aiProvider as keyof typeof apiKeys] ||
+                      : !hasApiKey(aiProvider) ||
                         !selectedModel ||
                         !prompt.trim()
                   }
@@ -636,6 +643,8 @@ export default function Generator({
                   <div className="flex-1 lowercase px-2 py-1 text-xs font-count rounded-sm border-2 border-azul bg-viol text-white text-center" style={{ transform: "skewX(-15deg)" }}>
                     <span style={{ transform: "skewX(15deg)" }} className="relative inline-block">
                       {t("generating")}
+                      {generationProgress !== null &&
+                        ` ${Math.round(generationProgress * 100)}%`}
                     </span>
                   </div>
                   <div
@@ -148,3 +148,103 @@ export interface ShowCanvasProps {
   templateChild: Template | null;
   onChildClick?: (childUri: string) => void;
 }
+
+export type GenerationJobState =
+  | "queued"
+  | "running"
+  | "succeeded"
+  | "failed"
+  | "canceled";
+
+export type GenerationProviderConfig =
+  | { type: "comfyui"; url: string }
+  | { type: "replicate"; credential: string; baseUrl?: string }
+  | { type: "fake"; steps?: number; fail?: boolean };
+
+export interface GenerationRequest {
+  model?: string;
+  prompt?: string;
+  input: any;
+}
+
+export interface GenerationJobStatus {
+  id: string;
+  state: GenerationJobState;
+  progress?: number;
+  message?: string;
+  error?: string;
+}
+
+export interface GeneratedImage {
+  data: string;
+  mimeType: string;
+  metadata: Record<string, any>;
+}
+
+export type CredentialKeySource = "machine" | "passphrase";
+
+export interface CredentialInfo {
+  name: string;
+  provider?: string;
+  origins: string[];
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface CredentialStatus {
+  keySource: CredentialKeySource;
+  locked: boolean;
+  count: number;
+}
+
+export interface CredentialTest {
+  ok: boolean;
+  message: string;
+}
+
+export interface Asset {
+  id: string;
+  file: string;
+  mimeType: string;
+  size: number;
+  width?: number;
+  height?: number;
+  provider?: string;
+  model?: string;
+  prompt?: string;
+  seed?: number;
+  workflow?: any;
+  parent?: string;
+  tags: string[];
+  favourite: boolean;
+  metadata: any;
+  createdAt: string;
+}
+
+export interface AssetInfo {
+  provider?: string;
+  model?: string;
+  prompt?: string;
+  seed?: number;
+  workflow?: any;
+  parent?: string;
+  tags?: string[];
+  metadata?: any;
+}
+
+export interface AssetQuery {
+  text?: string;
+  provider?: string;
+  tags?: string[];
+  favourite?: boolean;
+  parent?: string;
+  since?: string;
+  until?: string;
+  offset?: number;
+  limit?: number;
+}
+
+export interface AssetPage {
+  total: number;
+  assets: Asset[];
+}
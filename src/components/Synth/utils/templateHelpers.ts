@@ -1,4 +1,5 @@
-import { Template } from "../../Format/types/format.types";
+import { GroupedTemplate, Template } from "../../Format/types/format.types";
+import { NestingSettings } from "../../Pattern/types/pattern.types";
 
 export const getCurrentTemplate = (
   selectedLayer: {front: Template, back?: Template} | null,
@@ -14,4 +15,9 @@ export const getCurrentTemplateId = (
 ): string => {
   const template = getCurrentTemplate(selectedLayer, isBackSide);
   return template?.templateId || "";
-};
\ No newline at end of file
+};
+
+export const getGarmentType = (
+  selectedTemplate: GroupedTemplate | null | undefined
+): NonNullable<NestingSettings["garmentType"]> | null =>
+  selectedTemplate?.nesting?.garmentType ?? null;
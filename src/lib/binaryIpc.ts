@@ -0,0 +1,136 @@
+import { convertFileSrc, invoke } from "@tauri-apps/api/core";
+
+const PROTOCOL = "coinop-file";
+
+export const appFileUrl = (path: string): string =>
+  convertFileSrc(`file/${path}`, PROTOCOL);
+
+export const assetUrl = (id: string): string =>
+  convertFileSrc(`asset/${id}`, PROTOCOL);
+
+export const thumbnailUrl = (id: string, size?: number): string =>
+  convertFileSrc(`thumbnail/${id}`, PROTOCOL) + (size ? `?size=${size}` : "");
+
+export const dataUrlToBytes = async (dataUrl: string): Promise<Uint8Array> => {
+  const response = await fetch(dataUrl);
+  return new Uint8Array(await response.arrayBuffer());
+};
+
+export const bytesToDataUrl = (
+  bytes: ArrayBuffer | Uint8Array,
+  mimeType = "image/png"
+): Promise<string> =>
+  new Promise((resolve, reject) => {
+    const reader = new FileReader();
+    reader.onload = () => resolve(reader.result as string);
+    reader.onerror = () => reject(reader.error);
+    reader.readAsDataURL(new Blob([bytes], { type: mimeType }));
+  });
+
+export const readAppFile = async (path: string): Promise<ArrayBuffer> =>
+  invoke<ArrayBuffer>("read_binary_file", { path });
+
+export const saveAppFile = async (
+  path: string,
+  bytes: Uint8Array
+): Promise<void> =>
+  invoke("save_binary_file_raw", bytes, {
+    headers: { path: encodeURIComponent(path) },
+  });
+
+export const writeImageBytes = async (
+  filePath: string,
+  bytes: Uint8Array
+): Promise<string> =>
+  invoke<string>("write_image_bytes", bytes, {
+    headers: { "file-path": encodeURIComponent(filePath) },
+  });
+
+export const uploadComfyImage = async (
+  comfyUrl: string,
+  bytes: Uint8Array
+): Promise<string> =>
+  invoke<string>("comfyui_upload_image_bytes", bytes, {
+    headers: { "comfy-url": encodeURIComponent(comfyUrl) },
+  });
+
+export const downloadComfyImage = async (
+  comfyUrl: string,
+  filename: string,
+  subfolder?: string,
+  imageType?: string
+): Promise<ArrayBuffer> =>
+  invoke<ArrayBuffer>("comfyui_download_image_bytes", {
+    comfyUrl,
+    filename,
+    subfolder,
+    imageType,
+  });
+
+export interface IpcBenchmarkResult {
+  method: string;
+  bytes: number;
+  transferred: number;
+  averageMs: number;
+  heapDelta: number | null;
+}
+
+const usedHeap = (): number | null =>
+  (performance as any).memory?.usedJSHeapSize ?? null;
+
+/**
+ * Reads the same app-data file through the base64 command, the raw binary command
+ * and the protocol handler, reporting average latency, bytes moved over IPC and
+ * JS heap growth where the webview exposes it (Chromium-based webviews only).
+ */
+export const benchmarkImageIpc = async (
+  path: string,
+  runs = 5
+): Promise<IpcBenchmarkResult[]> => {
+  const methods: Array<[string, () => Promise<{ bytes: number; transferred: number }>]> = [
+    [
+      "base64",
+      async () => {
+        const base64 = await invoke<string>("read_binary_file_as_base64", {
+          path,
+        });
+        const bytes = await dataUrlToBytes(`data:application/octet-stream;base64,${base64}`);
+        return { bytes: bytes.byteLength, transferred: base64.length };
+      },
+    ],
+    [
+      "binary",
+      async () => {
+        const buffer = await readAppFile(path);
+        return { bytes: buffer.byteLength, transferred: buffer.byteLength };
+      },
+    ],
+    [
+      "protocol",
+      async () => {
+        const buffer = await (await fetch(appFileUrl(path))).arrayBuffer();
+        return { bytes: buffer.byteLength, transferred: buffer.byteLength };
+      },
+    ],
+  ];
+  const results: IpcBenchmarkResult[] = [];
+  for (const [method, read] of methods) {
+    await read();
+    const heapBefore = usedHeap();
+    let size = { bytes: 0, transferred: 0 };
+    const start = performance.now();
+    for (let i = 0; i < runs; i++) {
+      size = await read();
+    }
+    const elapsed = performance.now() - start;
+    const heapAfter = usedHeap();
+    results.push({
+      method,
+      ...size,
+      averageMs: elapsed / runs,
+      heapDelta:
+        heapBefore !== null && heapAfter !== null ? heapAfter - heapBefore : null,
+    });
+  }
+  return results;
+};
@@ -1,7 +1,6 @@
 import { NetworkConfig } from "../components/Common/types/common.types";
-import { TemplateChoice } from "../components/Format/types/format.types";
 import { Fulfiller } from "../components/Fulfillment/types/fulfillment.types";
-import { GarmentSize } from "../components/Pattern/types/pattern.types";
+import { GarmentSize, TileOptions } from "../components/Pattern/types/pattern.types";
 
 export const INFURA_GATEWAY: string = "https://thedial.infura-ipfs.io";
 export type Environment = "testnet" | "mainnet";
@@ -39,29 +38,6 @@ export const FULFILLERS: Fulfiller[] = [
   },
 ];
 
-export const TEMPLATE_CHOICES: TemplateChoice[] = [
-  {
-    name: "Hoodie",
-    type: "hoodie",
-    image: "QmVhSyXB67nUj1yH7GmojxvkoAdsrAumJkXs5rECnN7Cfj",
-  },
-  {
-    name: "Shirt",
-    type: "shirt",
-    image: "QmRXrv2icSyRi5P7VEx9yWh66VQB9UiiYPSt2NDkuGAcB9",
-  },
-  {
-    name: "Poster",
-    type: "poster",
-    image: "QmXSKZvk6iHtqRN9e3GEZPKRiDTUD72RY7w84ya2t3mRdZ",
-  },
-  {
-    name: "Sticker",
-    type: "sticker",
-    image: "QmV3Au8Vz2HZ4cfP5Jp5WsD47umg67rN11Y47a5mdL7dnm",
-  },
-];
-
 export const printTypeToNumber: { [key in string]: number } = {
   ["sticker"]: 0,
   ["poster"]: 1,
@@ -122,6 +98,18 @@ export const SHIRT_FRONT_PANEL_DIMENSIONS: Record<
   CUSTOM: { widthCm: 0, heightCm: 0 },
 };
 
+export const PT_PER_MM = 72 / 25.4;
+
+export const DEFAULT_TILE_OPTIONS: TileOptions = {
+  paper: { type: "a4" },
+  landscape: false,
+  overlapPt: 18,
+  marginPt: 18,
+  alignmentMarks: true,
+  labels: true,
+  coverPage: true,
+};
+
 export const PATTERN_COLORS: string[] = [
   "#8B5CF6",
   "#3B82F6",